@@ -454,6 +454,10 @@ mod tests {
             size: 100,
             modified_date: Utc::now(),
             file_type,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         }
     }
 
@@ -1,9 +1,13 @@
 // CLI module for argument parsing and configuration
 #![allow(dead_code)]
 
-use crate::domain::FileType;
-use clap::{ArgAction, Parser, ValueEnum};
-use std::path::PathBuf;
+use crate::domain::{
+    compile_glob, compile_regex, ArchiveFormat, FileType, ImageHash, SortBy, DEFAULT_MAX_DISTANCE,
+};
+use chrono::Duration;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use std::hash::{BuildHasher, RandomState};
+use std::path::{Path, PathBuf};
 
 /// Fswp - A terminal-based file decluttering tool
 ///
@@ -11,12 +15,17 @@ use std::path::PathBuf;
 #[derive(Parser, Debug, Clone)]
 #[command(name = "fswp")]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Args {
-    /// Directory to scan for files
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Directories to scan for files
     ///
-    /// If not specified, defaults to the current directory.
-    #[arg(default_value = ".")]
-    pub directory: PathBuf,
+    /// If not specified, defaults to the current directory. Several can be
+    /// given to review them in one pass.
+    #[arg(value_name = "DIRECTORY", default_value = ".")]
+    pub roots: Vec<PathBuf>,
 
     /// Filter by file type(s)
     ///
@@ -33,13 +42,136 @@ pub struct Args {
     pub dry_run: bool,
 
     /// Sort files by specified criteria
-    #[arg(short = 's', long = "sort", value_enum, default_value = "date")]
-    pub sort_by: SortOrder,
+    ///
+    /// Several keys can be given, separated by commas; each one breaks the
+    /// ties left by the ones before it. Example: --sort type,size
+    ///
+    /// Defaults to date. Files from --stdin or --from-list keep the order of
+    /// the list unless a sort order is given.
+    #[arg(short = 's', long = "sort", value_enum, value_delimiter = ',')]
+    pub sort_by: Vec<SortOrder>,
+
+    /// Seed for --sort random, to get the same order again
+    #[arg(long = "seed", value_name = "N")]
+    pub seed: Option<u64>,
 
     /// Reverse sort order
     #[arg(short = 'r', long = "reverse", action = ArgAction::SetTrue)]
     pub reverse: bool,
 
+    /// Review groups of files with identical contents instead of single files
+    ///
+    /// Files are compared by size, then by a hash of their first block, then
+    /// by a hash of their whole contents. Other filters narrow the files compared.
+    #[arg(short = 'D', long = "duplicates", action = ArgAction::SetTrue, conflicts_with = "resume")]
+    pub duplicates: bool,
+
+    /// Review groups of images that look alike: resized, re-encoded or burst shots
+    #[arg(
+        long = "similar-images",
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["duplicates", "resume"]
+    )]
+    pub similar_images: bool,
+
+    /// How many of the 64 hash bits two similar images may differ in (requires --similar-images)
+    ///
+    /// Lower is stricter: 0 only groups images that hash identically, around
+    /// 10 catches resized and re-encoded copies, 20 and up catches burst shots
+    /// but also unrelated images.
+    #[arg(
+        long = "similarity",
+        value_name = "BITS",
+        default_value_t = DEFAULT_MAX_DISTANCE,
+        value_parser = clap::value_parser!(u32).range(0..=64),
+        requires = "similar_images"
+    )]
+    pub similarity: u32,
+
+    /// Perceptual hash used to compare images (requires --similar-images)
+    #[arg(
+        long = "image-hash",
+        value_enum,
+        default_value = "dhash",
+        requires = "similar_images"
+    )]
+    pub image_hash: ImageHashKind,
+
+    /// Also review files in subdirectories
+    #[arg(short = 'R', long = "recursive", action = ArgAction::SetTrue)]
+    pub recursive: bool,
+
+    /// How many levels of subdirectories to descend into (requires --recursive)
+    #[arg(long = "max-depth", value_name = "N", requires = "recursive")]
+    pub max_depth: Option<usize>,
+
+    /// Review each subdirectory as a single item, next to the files
+    ///
+    /// A folder's size and file count add up everything inside it, and its
+    /// age is that of the newest file inside. Trashing it trashes it whole.
+    #[arg(
+        long = "dirs",
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["recursive", "duplicates", "similar_images"]
+    )]
+    pub directories: bool,
+
+    /// Review the biggest files and folders in the whole tree first
+    ///
+    /// Folders are sized by everything inside them. Keeping a folder queues
+    /// its contents, biggest first, so the review works down to where the
+    /// space goes. The header shows the space reclaimed so far.
+    #[arg(
+        long = "hogs",
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["recursive", "directories", "duplicates", "similar_images", "reverse"]
+    )]
+    pub disk_hogs: bool,
+
+    /// Review the files whose paths are piped in, instead of a directory
+    ///
+    /// Paths go one per line, or separated by NUL bytes as `fd -0` and
+    /// `find -print0` write them. Example: fd -e log --changed-before 30d | fswp --stdin
+    #[arg(
+        long = "stdin",
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["roots", "from_list", "recursive", "disk_hogs", "resume", "fresh"]
+    )]
+    pub stdin: bool,
+
+    /// Review the files listed in FILE, instead of a directory
+    ///
+    /// Same format as --stdin. Type, size, age and name filters still apply.
+    #[arg(
+        long = "from-list",
+        value_name = "FILE",
+        conflicts_with_all = ["roots", "recursive", "disk_hogs", "resume", "fresh"]
+    )]
+    pub from_list: Option<PathBuf>,
+
+    /// Only review files matching this glob (repeatable)
+    ///
+    /// Patterns without a slash match the file name; others match the path
+    /// from the reviewed directory. Example: --include '*.dmg' --include '*.iso'
+    #[arg(long = "include", value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files matching this glob (repeatable)
+    ///
+    /// Example: --exclude '*.rs' --exclude 'vendor/**'
+    #[arg(long = "exclude", value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Only review files whose name matches this regular expression
+    ///
+    /// Example: --regex '^IMG_\d{4}\.(jpe?g|heic)$'
+    #[arg(long = "regex", value_name = "PATTERN")]
+    pub regex: Option<String>,
+
+    /// Also review files matched by .gitignore, .ignore and .fswpignore
+    #[arg(long = "no-ignore", action = ArgAction::SetTrue)]
+    pub no_ignore: bool,
+
     /// Show hidden files (files starting with .)
     #[arg(long = "hidden", action = ArgAction::SetTrue)]
     pub show_hidden: bool,
@@ -52,6 +184,22 @@ pub struct Args {
     #[arg(long = "max-size")]
     pub max_size: Option<String>,
 
+    /// Only review files last modified longer ago than this (e.g., "90d", "6m", "1y")
+    #[arg(long = "older-than", value_name = "AGE")]
+    pub older_than: Option<String>,
+
+    /// Only review files modified within this long (e.g., "2w", "12h")
+    #[arg(long = "newer-than", value_name = "AGE")]
+    pub newer_than: Option<String>,
+
+    /// Only review files not opened for this long (e.g., "6m")
+    ///
+    /// Relies on access times, which many systems update lazily (relatime)
+    /// or not at all (noatime). Files without one are judged by their
+    /// modification time instead.
+    #[arg(long = "not-accessed-since", value_name = "AGE")]
+    pub not_accessed_since: Option<String>,
+
     /// Skip confirmation prompts for trash actions
     #[arg(short = 'y', long = "yes", action = ArgAction::SetTrue)]
     pub yes: bool,
@@ -59,17 +207,104 @@ pub struct Args {
     /// Show welcome dialog on startup
     #[arg(long = "welcome", action = ArgAction::SetTrue)]
     pub welcome: bool,
+
+    /// Permanently delete files instead of sending them to the system trash
+    ///
+    /// For machines without a usable trash (headless servers, containers).
+    /// Each delete must be confirmed by typing the file name, and the files
+    /// are only removed after typing their count when quitting.
+    #[arg(long = "delete", action = ArgAction::SetTrue)]
+    pub delete: bool,
+
+    /// Overwrite file contents with zeros before deleting (requires --delete)
+    ///
+    /// Not effective on SSDs, copy-on-write filesystems or snapshots.
+    #[arg(long = "shred", action = ArgAction::SetTrue, requires = "delete")]
+    pub shred: bool,
+
+    /// Destination folder for a move slot, bound to keys 1-9 in order
+    ///
+    /// Can be specified up to 9 times. Relative paths are resolved against
+    /// the reviewed directory. Overrides the slots saved in the config file.
+    /// Example: --move-to Receipts --move-to Photos/2024
+    #[arg(short = 'm', long = "move-to", value_name = "DIR")]
+    pub move_to: Vec<PathBuf>,
+
+    /// Folder for the session archive that the archive key (a) appends files to
+    ///
+    /// Relative paths are resolved against the reviewed directory. Overrides
+    /// the folder saved in the config file; defaults to the reviewed directory.
+    #[arg(long = "archive-to", value_name = "DIR")]
+    pub archive_to: Option<PathBuf>,
+
+    /// Also show files kept in earlier runs
+    ///
+    /// Kept files are normally skipped until they change. Press K on one to
+    /// forget that it was kept.
+    #[arg(long = "review-kept", action = ArgAction::SetTrue)]
+    pub review_kept: bool,
+
+    /// Resume the saved review of this directory without asking
+    #[arg(long = "resume", action = ArgAction::SetTrue, conflicts_with = "fresh")]
+    pub resume: bool,
+
+    /// Ignore and discard the saved review of this directory
+    #[arg(long = "fresh", action = ArgAction::SetTrue)]
+    pub fresh: bool,
+
+    /// Format of the session archive
+    #[arg(long = "archive-format", value_enum, default_value = "tar-gz")]
+    pub archive_format: ArchiveKind,
+}
+
+/// Number of move slots, one per number key
+pub const MAX_MOVE_SLOTS: usize = 9;
+
+/// Subcommands that run instead of the review session
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Restore or commit files left in staging by an interrupted session
+    ///
+    /// Without a flag, each interrupted session is listed and you are asked what to do.
+    Recover {
+        /// Move every staged file back to its original location
+        #[arg(long, conflicts_with = "commit")]
+        restore: bool,
+
+        /// Carry out the recorded decisions (send staged files to the trash)
+        ///
+        /// Files marked for permanent deletion go to the trash as well, since
+        /// their deletion was never confirmed.
+        #[arg(long)]
+        commit: bool,
+    },
 }
 
 /// File type filter options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum FileTypeFilter {
+    /// Directories reviewed as a whole (with --dirs)
+    Directory,
     /// Text files (txt, md, rs, py, js, etc.)
     Text,
     /// Image files (png, jpg, gif, etc.)
     Image,
     /// PDF files
     Pdf,
+    /// Word processing documents, presentations and e-books (docx, odt, epub, etc.)
+    Document,
+    /// Spreadsheets (xlsx, ods, numbers, etc.)
+    Spreadsheet,
+    /// Archives, compressed files and disk images (zip, tar.gz, 7z, dmg, etc.)
+    Archive,
+    /// Audio files (mp3, flac, wav, etc.)
+    Audio,
+    /// Video files (mp4, mkv, mov, etc.)
+    Video,
+    /// Fonts (ttf, otf, woff, etc.)
+    Font,
+    /// Programs, libraries and installers (exe, AppImage, so, etc.)
+    Executable,
     /// Binary/other files
     Binary,
 }
@@ -77,26 +312,85 @@ pub enum FileTypeFilter {
 impl From<FileTypeFilter> for FileType {
     fn from(filter: FileTypeFilter) -> Self {
         match filter {
+            FileTypeFilter::Directory => FileType::Directory,
             FileTypeFilter::Text => FileType::Text,
             FileTypeFilter::Image => FileType::Image,
             FileTypeFilter::Pdf => FileType::Pdf,
+            FileTypeFilter::Document => FileType::Document,
+            FileTypeFilter::Spreadsheet => FileType::Spreadsheet,
+            FileTypeFilter::Archive => FileType::Archive,
+            FileTypeFilter::Audio => FileType::Audio,
+            FileTypeFilter::Video => FileType::Video,
+            FileTypeFilter::Font => FileType::Font,
+            FileTypeFilter::Executable => FileType::Executable,
             FileTypeFilter::Binary => FileType::Binary,
         }
     }
 }
 
+/// Session archive formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ArchiveKind {
+    /// Gzip-compressed tarball
+    #[default]
+    TarGz,
+    /// Zip archive
+    Zip,
+}
+
+impl From<ArchiveKind> for ArchiveFormat {
+    fn from(kind: ArchiveKind) -> Self {
+        match kind {
+            ArchiveKind::TarGz => ArchiveFormat::TarGz,
+            ArchiveKind::Zip => ArchiveFormat::Zip,
+        }
+    }
+}
+
+/// Perceptual hash options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ImageHashKind {
+    /// Average hash: fastest, sensitive to brightness changes
+    Ahash,
+    /// Difference hash: fast, robust to resizing and re-encoding
+    #[default]
+    Dhash,
+    /// DCT-based perceptual hash: slowest, most robust to edits
+    Phash,
+}
+
+impl From<ImageHashKind> for ImageHash {
+    fn from(kind: ImageHashKind) -> Self {
+        match kind {
+            ImageHashKind::Ahash => ImageHash::Average,
+            ImageHashKind::Dhash => ImageHash::Difference,
+            ImageHashKind::Phash => ImageHash::Perceptual,
+        }
+    }
+}
+
 /// Sort order options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
 pub enum SortOrder {
     /// Sort by modification date (oldest first)
     #[default]
     Date,
-    /// Sort by file name (alphabetical)
+    /// Sort by file name (alphabetical, with numbers in order: file9 before file10)
     Name,
     /// Sort by file size (smallest first)
     Size,
     /// Sort by file type
     Type,
+    /// Sort by file extension
+    Extension,
+    /// Sort by path from the reviewed directory, keeping folders together
+    Path,
+    /// Sort by last access (least recently used first)
+    Accessed,
+    /// Sort by creation date (oldest first)
+    Created,
+    /// Shuffle, for reviewing an unbiased sample
+    Random,
 }
 
 impl Args {
@@ -144,6 +438,33 @@ impl Args {
         Some((num * multiplier as f64) as u64)
     }
 
+    /// Parse an age string (e.g., "90d", "2w", "6m") into a duration
+    ///
+    /// Units are s, min, h, d, w, m (30-day months) and y (365-day years).
+    pub fn parse_duration(duration_str: &str) -> Option<Duration> {
+        let duration_str = duration_str.trim().to_lowercase();
+        let split = duration_str
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(duration_str.len());
+        let (num_str, unit) = duration_str.split_at(split);
+
+        let num: i64 = num_str.parse().ok()?;
+
+        let days = |n: i64| n.checked_mul(24 * 60 * 60);
+        let seconds = match unit.trim() {
+            "s" => Some(num),
+            "min" => num.checked_mul(60),
+            "h" => num.checked_mul(60 * 60),
+            "d" => days(num),
+            "w" => days(num.checked_mul(7)?),
+            "m" => days(num.checked_mul(30)?),
+            "y" => days(num.checked_mul(365)?),
+            _ => None,
+        }?;
+
+        Duration::try_seconds(seconds)
+    }
+
     /// Get minimum size in bytes
     pub fn get_min_size(&self) -> Option<u64> {
         self.min_size.as_ref().and_then(|s| Self::parse_size(s))
@@ -154,21 +475,62 @@ impl Args {
         self.max_size.as_ref().and_then(|s| Self::parse_size(s))
     }
 
+    /// The first directory to review, which relative move destinations and
+    /// the archive folder resolve against
+    pub fn directory(&self) -> &Path {
+        self.roots
+            .first()
+            .map_or(Path::new("."), |root| root.as_path())
+    }
+
     /// Validate the arguments and return any errors
     pub fn validate(&self) -> Result<(), String> {
-        // Check if directory exists
-        if !self.directory.exists() {
-            return Err(format!(
-                "Directory does not exist: {}",
-                self.directory.display()
-            ));
+        // Check if every directory exists
+        for root in &self.roots {
+            if !root.exists() {
+                return Err(format!("Directory does not exist: {}", root.display()));
+            }
+
+            if !root.is_dir() {
+                return Err(format!("Path is not a directory: {}", root.display()));
+            }
         }
 
-        if !self.directory.is_dir() {
-            return Err(format!(
-                "Path is not a directory: {}",
-                self.directory.display()
-            ));
+        if let Some(ref list) = self.from_list {
+            if !list.exists() {
+                return Err(format!("File list does not exist: {}", list.display()));
+            }
+
+            if list.is_dir() {
+                return Err(format!(
+                    "File list is a directory: {}; pass it as DIRECTORY instead",
+                    list.display()
+                ));
+            }
+        }
+
+        // A directory inside another would have its files reviewed twice
+        let canonical: Vec<PathBuf> = self
+            .roots
+            .iter()
+            .map(|root| root.canonicalize().unwrap_or_else(|_| root.clone()))
+            .collect();
+        for (i, a) in canonical.iter().enumerate() {
+            for (j, b) in canonical.iter().enumerate() {
+                if i < j && a == b {
+                    return Err(format!(
+                        "Directory given twice: {}",
+                        self.roots[j].display()
+                    ));
+                }
+                if i != j && a != b && a.starts_with(b) {
+                    return Err(format!(
+                        "Directory {} is inside {}; give only one of them",
+                        self.roots[i].display(),
+                        self.roots[j].display()
+                    ));
+                }
+            }
         }
 
         // Validate size strings if provided
@@ -190,6 +552,57 @@ impl Args {
             }
         }
 
+        for (flag, age) in [
+            ("older-than", &self.older_than),
+            ("newer-than", &self.newer_than),
+            ("not-accessed-since", &self.not_accessed_since),
+        ] {
+            if let Some(age) = age {
+                if Self::parse_duration(age).is_none() {
+                    return Err(format!(
+                        "Invalid {} format: '{}'. Use format like '90d', '2w', '6m', '1y'",
+                        flag, age
+                    ));
+                }
+            }
+        }
+
+        for pattern in self.include.iter().chain(&self.exclude) {
+            compile_glob(pattern)?;
+        }
+
+        if let Some(ref pattern) = self.regex {
+            compile_regex(pattern)?;
+        }
+
+        if self.move_to.len() > MAX_MOVE_SLOTS {
+            return Err(format!(
+                "Too many move destinations: {} (at most {})",
+                self.move_to.len(),
+                MAX_MOVE_SLOTS
+            ));
+        }
+
+        for dest in &self.move_to {
+            let resolved = self.directory().join(dest);
+            if resolved.exists() && !resolved.is_dir() {
+                return Err(format!(
+                    "Move destination is not a directory: {}",
+                    resolved.display()
+                ));
+            }
+        }
+
+        if let Some(ref dir) = self.archive_to {
+            let resolved = self.directory().join(dir);
+            if resolved.exists() && !resolved.is_dir() {
+                return Err(format!(
+                    "Archive folder is not a directory: {}",
+                    resolved.display()
+                ));
+            }
+        }
+
         // Check min <= max if both specified
         if let (Some(min), Some(max)) = (self.get_min_size(), self.get_max_size()) {
             if min > max {
@@ -205,34 +618,120 @@ impl Args {
     }
 }
 
+/// Where a list of files to review is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileList {
+    Stdin,
+    File(PathBuf),
+}
+
 /// Configuration derived from CLI arguments
 #[derive(Debug, Clone)]
 pub struct AppConfig {
-    pub directory: PathBuf,
+    /// Directories to review, in the order given
+    pub roots: Vec<PathBuf>,
+    /// Where the paths to review come from instead, if they are listed
+    pub file_list: Option<FileList>,
     pub file_type_filters: Option<Vec<FileType>>,
     pub dry_run: bool,
-    pub sort_by: SortOrder,
+    /// Sort keys given with --sort; none for the default order
+    pub sort_by: Vec<SortOrder>,
+    /// Seed for `SortOrder::Random`, picked at random unless given with --seed
+    pub seed: u64,
     pub reverse: bool,
     pub show_hidden: bool,
+    /// Review groups of identical files
+    pub duplicates: bool,
+    /// Review groups of similar images
+    pub similar_images: bool,
+    /// Hash bits two similar images may differ in
+    pub similarity: u32,
+    pub image_hash: ImageHash,
+    pub recursive: bool,
+    pub max_depth: Option<usize>,
+    /// Review subdirectories as single items
+    pub directories: bool,
+    /// Review the biggest files and folders in the tree first
+    pub disk_hogs: bool,
+    /// Skip files matched by ignore files
+    pub respect_ignore_files: bool,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub name_regex: Option<String>,
     pub min_size: Option<u64>,
     pub max_size: Option<u64>,
+    /// Only files modified longer ago than this
+    pub older_than: Option<Duration>,
+    /// Only files modified within this long
+    pub newer_than: Option<Duration>,
+    /// Only files not accessed for this long
+    pub not_accessed_since: Option<Duration>,
     pub skip_confirm: bool,
     pub show_welcome: bool,
+    /// Move destinations given on the command line, as passed
+    pub move_destinations: Vec<PathBuf>,
+    /// Permanently delete instead of trashing
+    pub delete_mode: bool,
+    /// Overwrite deleted files before unlinking them
+    pub shred: bool,
+    /// Archive folder given on the command line, as passed
+    pub archive_dir: Option<PathBuf>,
+    pub archive_format: ArchiveFormat,
+    /// Show files kept in earlier runs
+    pub review_kept: bool,
+    /// Resume a saved review without asking
+    pub resume: bool,
+    /// Start over, discarding any saved review
+    pub fresh: bool,
 }
 
 impl From<Args> for AppConfig {
     fn from(args: Args) -> Self {
         AppConfig {
-            directory: args.directory.clone(),
+            roots: args.roots.clone(),
+            file_list: args
+                .from_list
+                .clone()
+                .map(FileList::File)
+                .or(args.stdin.then_some(FileList::Stdin)),
             file_type_filters: args.get_file_type_filters(),
             dry_run: args.dry_run,
-            sort_by: args.sort_by,
+            sort_by: args.sort_by.clone(),
+            seed: args
+                .seed
+                .unwrap_or_else(|| RandomState::new().hash_one(0u8)),
             reverse: args.reverse,
             show_hidden: args.show_hidden,
+            duplicates: args.duplicates,
+            similar_images: args.similar_images,
+            similarity: args.similarity,
+            image_hash: args.image_hash.into(),
+            recursive: args.recursive,
+            max_depth: args.max_depth,
+            directories: args.directories,
+            disk_hogs: args.disk_hogs,
+            respect_ignore_files: !args.no_ignore,
+            include: args.include.clone(),
+            exclude: args.exclude.clone(),
+            name_regex: args.regex.clone(),
             min_size: args.get_min_size(),
             max_size: args.get_max_size(),
+            older_than: args.older_than.as_deref().and_then(Args::parse_duration),
+            newer_than: args.newer_than.as_deref().and_then(Args::parse_duration),
+            not_accessed_since: args
+                .not_accessed_since
+                .as_deref()
+                .and_then(Args::parse_duration),
             skip_confirm: args.yes,
             show_welcome: args.welcome,
+            move_destinations: args.move_to,
+            delete_mode: args.delete,
+            shred: args.shred,
+            archive_dir: args.archive_to,
+            archive_format: args.archive_format.into(),
+            review_kept: args.review_kept,
+            resume: args.resume,
+            fresh: args.fresh,
         }
     }
 }
@@ -240,16 +739,111 @@ impl From<Args> for AppConfig {
 impl Default for AppConfig {
     fn default() -> Self {
         AppConfig {
-            directory: PathBuf::from("."),
+            roots: vec![PathBuf::from(".")],
+            file_list: None,
             file_type_filters: None,
             dry_run: false,
-            sort_by: SortOrder::Date,
+            sort_by: vec![SortOrder::Date],
+            seed: 0,
             reverse: false,
             show_hidden: false,
+            duplicates: false,
+            similar_images: false,
+            similarity: DEFAULT_MAX_DISTANCE,
+            image_hash: ImageHash::Difference,
+            recursive: false,
+            max_depth: None,
+            directories: false,
+            disk_hogs: false,
+            respect_ignore_files: true,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            name_regex: None,
             min_size: None,
             max_size: None,
+            older_than: None,
+            newer_than: None,
+            not_accessed_since: None,
             skip_confirm: false,
             show_welcome: false,
+            move_destinations: Vec::new(),
+            delete_mode: false,
+            shred: false,
+            archive_dir: None,
+            archive_format: ArchiveFormat::TarGz,
+            review_kept: false,
+            resume: false,
+            fresh: false,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Whether files are reviewed in groups of duplicates or similar images
+    pub fn reviews_groups(&self) -> bool {
+        self.duplicates || self.similar_images
+    }
+
+    /// The first directory to review, which relative move destinations and
+    /// the archive folder resolve against
+    pub fn directory(&self) -> &Path {
+        self.roots
+            .first()
+            .map_or(Path::new("."), |root| root.as_path())
+    }
+
+    /// Whether more than one directory is reviewed
+    pub fn has_several_roots(&self) -> bool {
+        self.roots.len() > 1
+    }
+
+    /// The sort keys for discovery, in order: by date unless others were
+    /// given, except that listed files keep the order of the list
+    pub fn sort_keys(&self) -> Vec<SortBy> {
+        if self.sort_by.is_empty() {
+            return match self.file_list {
+                Some(_) => Vec::new(),
+                None => vec![SortBy::Date],
+            };
+        }
+        self.sort_by
+            .iter()
+            .map(|order| match order {
+                SortOrder::Date => SortBy::Date,
+                SortOrder::Name => SortBy::Name,
+                SortOrder::Size => SortBy::Size,
+                SortOrder::Type => SortBy::Type,
+                SortOrder::Extension => SortBy::Extension,
+                SortOrder::Path => SortBy::Path,
+                SortOrder::Accessed => SortBy::Accessed,
+                SortOrder::Created => SortBy::Created,
+                SortOrder::Random => SortBy::Random { seed: self.seed },
+            })
+            .collect()
+    }
+
+    /// Resolves the move slots for keys 1-9: the command-line destinations if
+    /// any were given, otherwise the saved ones, relative to the reviewed directory
+    pub fn move_slots(&self, saved: &[PathBuf]) -> Vec<PathBuf> {
+        let destinations = if self.move_destinations.is_empty() {
+            saved
+        } else {
+            &self.move_destinations
+        };
+
+        destinations
+            .iter()
+            .take(MAX_MOVE_SLOTS)
+            .map(|dest| self.directory().join(dest))
+            .collect()
+    }
+
+    /// Resolves the session archive folder: the command-line one if given,
+    /// otherwise the saved one, otherwise the reviewed directory
+    pub fn archive_location(&self, saved: Option<&Path>) -> PathBuf {
+        match self.archive_dir.as_deref().or(saved) {
+            Some(dir) => self.directory().join(dir),
+            None => self.directory().to_path_buf(),
         }
     }
 }
@@ -258,6 +852,48 @@ impl Default for AppConfig {
 mod tests {
     use super::*;
 
+    fn base_args() -> Args {
+        Args {
+            command: None,
+            roots: vec![PathBuf::from(".")],
+            file_types: vec![],
+            dry_run: false,
+            sort_by: vec![SortOrder::Date],
+            seed: None,
+            reverse: false,
+            show_hidden: false,
+            duplicates: false,
+            similar_images: false,
+            similarity: DEFAULT_MAX_DISTANCE,
+            image_hash: ImageHashKind::Dhash,
+            recursive: false,
+            max_depth: None,
+            directories: false,
+            disk_hogs: false,
+            stdin: false,
+            from_list: None,
+            include: vec![],
+            exclude: vec![],
+            regex: None,
+            no_ignore: false,
+            min_size: None,
+            max_size: None,
+            older_than: None,
+            newer_than: None,
+            not_accessed_since: None,
+            yes: false,
+            welcome: false,
+            delete: false,
+            shred: false,
+            move_to: vec![],
+            archive_to: None,
+            review_kept: false,
+            resume: false,
+            fresh: false,
+            archive_format: ArchiveKind::TarGz,
+        }
+    }
+
     mod args_tests {
         use super::*;
 
@@ -300,32 +936,355 @@ mod tests {
             assert_eq!(Args::parse_size(""), None);
         }
 
+        #[test]
+        fn test_parse_duration_units() {
+            assert_eq!(Args::parse_duration("30s"), Some(Duration::seconds(30)));
+            assert_eq!(Args::parse_duration("15min"), Some(Duration::minutes(15)));
+            assert_eq!(Args::parse_duration("12h"), Some(Duration::hours(12)));
+            assert_eq!(Args::parse_duration("90d"), Some(Duration::days(90)));
+            assert_eq!(Args::parse_duration("2w"), Some(Duration::weeks(2)));
+            assert_eq!(Args::parse_duration("6m"), Some(Duration::days(180)));
+            assert_eq!(Args::parse_duration("1Y"), Some(Duration::days(365)));
+        }
+
+        #[test]
+        fn test_parse_duration_invalid() {
+            assert_eq!(Args::parse_duration("90"), None);
+            assert_eq!(Args::parse_duration("d"), None);
+            assert_eq!(Args::parse_duration("1.5d"), None);
+            assert_eq!(Args::parse_duration("-3d"), None);
+            assert_eq!(Args::parse_duration("3 fortnights"), None);
+            assert_eq!(Args::parse_duration("99999999999999y"), None);
+            assert_eq!(Args::parse_duration(""), None);
+        }
+
+        #[test]
+        fn test_parse_age_filters() {
+            let args =
+                Args::try_parse_from(["fswp", "--older-than", "90d", "--not-accessed-since", "6m"])
+                    .unwrap();
+            assert!(args.validate().is_ok());
+
+            let config: AppConfig = args.into();
+            assert_eq!(config.older_than, Some(Duration::days(90)));
+            assert_eq!(config.not_accessed_since, Some(Duration::days(180)));
+            assert!(config.newer_than.is_none());
+
+            let args = Args {
+                newer_than: Some("2 weeks".to_string()),
+                ..base_args()
+            };
+            assert!(args.validate().unwrap_err().contains("newer-than"));
+        }
+
         #[test]
         fn test_file_type_filter_conversion() {
             assert_eq!(FileType::from(FileTypeFilter::Text), FileType::Text);
             assert_eq!(FileType::from(FileTypeFilter::Image), FileType::Image);
             assert_eq!(FileType::from(FileTypeFilter::Pdf), FileType::Pdf);
             assert_eq!(FileType::from(FileTypeFilter::Binary), FileType::Binary);
+            assert_eq!(FileType::from(FileTypeFilter::Archive), FileType::Archive);
+            assert_eq!(
+                FileType::from(FileTypeFilter::Spreadsheet),
+                FileType::Spreadsheet
+            );
         }
 
         #[test]
-        fn test_args_default_values() {
+        fn test_parse_new_file_types() {
+            let args =
+                Args::try_parse_from(["fswp", "-t", "archive", "-t", "video", "-t", "executable"])
+                    .unwrap();
+            assert_eq!(
+                args.get_file_type_filters(),
+                Some(vec![
+                    FileType::Archive,
+                    FileType::Video,
+                    FileType::Executable
+                ])
+            );
+        }
+
+        #[test]
+        fn test_parse_recover_subcommand() {
+            let args = Args::try_parse_from(["fswp", "recover", "--restore"]).unwrap();
+            assert_eq!(
+                args.command,
+                Some(Command::Recover {
+                    restore: true,
+                    commit: false
+                })
+            );
+
+            let args = Args::try_parse_from(["fswp", "/tmp"]).unwrap();
+            assert!(args.command.is_none());
+            assert_eq!(args.roots, vec![PathBuf::from("/tmp")]);
+
+            assert!(Args::try_parse_from(["fswp", "recover", "--restore", "--commit"]).is_err());
+        }
+
+        #[test]
+        fn test_parse_move_to() {
+            let args =
+                Args::try_parse_from(["fswp", "-m", "Receipts", "--move-to", "/archive"]).unwrap();
+            assert_eq!(
+                args.move_to,
+                vec![PathBuf::from("Receipts"), PathBuf::from("/archive")]
+            );
+        }
+
+        #[test]
+        fn test_parse_delete_flags() {
+            let args = Args::try_parse_from(["fswp", "--delete", "--shred"]).unwrap();
+            let config: AppConfig = args.into();
+            assert!(config.delete_mode);
+            assert!(config.shred);
+
+            // Shredding only makes sense when deleting
+            assert!(Args::try_parse_from(["fswp", "--shred"]).is_err());
+        }
+
+        #[test]
+        fn test_parse_archive_flags() {
+            let args = Args::try_parse_from([
+                "fswp",
+                "--archive-to",
+                "old-logs",
+                "--archive-format",
+                "zip",
+            ])
+            .unwrap();
+            let config: AppConfig = args.into();
+
+            assert_eq!(config.archive_format, ArchiveFormat::Zip);
+            assert_eq!(config.archive_location(None), PathBuf::from("./old-logs"));
+
+            let config: AppConfig = base_args().into();
+            assert_eq!(config.archive_format, ArchiveFormat::TarGz);
+            assert_eq!(
+                config.archive_location(Some(Path::new("/srv/archive"))),
+                PathBuf::from("/srv/archive")
+            );
+            assert_eq!(config.archive_location(None), PathBuf::from("."));
+        }
+
+        #[test]
+        fn test_parse_recursive_flags() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "-R", "--max-depth", "2"])
+                .unwrap()
+                .into();
+            assert!(config.recursive);
+            assert_eq!(config.max_depth, Some(2));
+
+            // A depth limit without recursion would do nothing
+            assert!(Args::try_parse_from(["fswp", "--max-depth", "2"]).is_err());
+        }
+
+        #[test]
+        fn test_parse_dirs() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "--dirs", "-t", "directory"])
+                .unwrap()
+                .into();
+            assert!(config.directories);
+            assert_eq!(config.file_type_filters, Some(vec![FileType::Directory]));
+
+            // Folders are either reviewed whole or descended into, not both
+            assert!(Args::try_parse_from(["fswp", "--dirs", "-R"]).is_err());
+            assert!(Args::try_parse_from(["fswp", "--dirs", "--duplicates"]).is_err());
+        }
+
+        #[test]
+        fn test_parse_hogs() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "--hogs", "--min-size", "1GB"])
+                .unwrap()
+                .into();
+            assert!(config.disk_hogs);
+            assert!(!config.reviews_groups());
+
+            // Hog review always walks the whole tree, biggest first
+            assert!(Args::try_parse_from(["fswp", "--hogs", "-R"]).is_err());
+            assert!(Args::try_parse_from(["fswp", "--hogs", "--reverse"]).is_err());
+            assert!(Args::try_parse_from(["fswp", "--hogs", "--dirs"]).is_err());
+        }
+
+        #[test]
+        fn test_parse_file_list() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "--stdin", "--type", "text"])
+                .unwrap()
+                .into();
+            assert_eq!(config.file_list, Some(FileList::Stdin));
+            // Listed files keep the list's order unless asked otherwise
+            assert!(config.sort_keys().is_empty());
+
+            let config: AppConfig =
+                Args::try_parse_from(["fswp", "--from-list", "old.txt", "--sort", "size"])
+                    .unwrap()
+                    .into();
+            assert_eq!(
+                config.file_list,
+                Some(FileList::File(PathBuf::from("old.txt")))
+            );
+            assert_eq!(config.sort_keys(), vec![SortBy::Size]);
+
+            let config: AppConfig = Args::try_parse_from(["fswp"]).unwrap().into();
+            assert_eq!(config.file_list, None);
+            assert_eq!(config.sort_keys(), vec![SortBy::Date]);
+
+            // A list replaces the directory walk
+            assert!(Args::try_parse_from(["fswp", "--stdin", "~/Downloads"]).is_err());
+            assert!(Args::try_parse_from(["fswp", "--stdin", "-R"]).is_err());
+            assert!(Args::try_parse_from(["fswp", "--stdin", "--from-list", "a.txt"]).is_err());
+        }
+
+        #[test]
+        fn test_args_validate_file_list() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let list = temp_dir.path().join("candidates.txt");
+            let with_list = |list: &Path| Args {
+                from_list: Some(list.to_path_buf()),
+                ..base_args()
+            };
+
+            let result = with_list(&list).validate();
+            assert!(result.unwrap_err().contains("does not exist"));
+
+            std::fs::write(&list, "a.txt\n").unwrap();
+            assert!(with_list(&list).validate().is_ok());
+
+            let result = with_list(temp_dir.path()).validate();
+            assert!(result.unwrap_err().contains("is a directory"));
+        }
+
+        #[test]
+        fn test_parse_duplicates() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "--duplicates", "-R"])
+                .unwrap()
+                .into();
+            assert!(config.duplicates);
+            assert!(!AppConfig::default().duplicates);
+
+            // Duplicate review does not save progress to resume
+            assert!(Args::try_parse_from(["fswp", "-D", "--resume"]).is_err());
+        }
+
+        #[test]
+        fn test_parse_similar_images() {
+            let config: AppConfig = Args::try_parse_from([
+                "fswp",
+                "--similar-images",
+                "--similarity",
+                "4",
+                "--image-hash",
+                "phash",
+            ])
+            .unwrap()
+            .into();
+            assert!(config.similar_images);
+            assert_eq!(config.similarity, 4);
+            assert_eq!(config.image_hash, ImageHash::Perceptual);
+
+            let config: AppConfig = Args::try_parse_from(["fswp", "--similar-images"])
+                .unwrap()
+                .into();
+            assert_eq!(config.similarity, DEFAULT_MAX_DISTANCE);
+            assert_eq!(config.image_hash, ImageHash::Difference);
+
+            // A threshold only means something when comparing images
+            assert!(Args::try_parse_from(["fswp", "--similarity", "4"]).is_err());
+            assert!(
+                Args::try_parse_from(["fswp", "--similar-images", "--similarity", "65"]).is_err()
+            );
+            assert!(Args::try_parse_from(["fswp", "--similar-images", "--duplicates"]).is_err());
+        }
+
+        #[test]
+        fn test_parse_name_filters() {
+            let args = Args::try_parse_from([
+                "fswp",
+                "--include",
+                "*.dmg",
+                "--include",
+                "*.iso",
+                "--exclude",
+                "old/**",
+                "--regex",
+                "^setup",
+            ])
+            .unwrap();
+            assert!(args.validate().is_ok());
+
+            let config: AppConfig = args.into();
+            assert_eq!(config.include, vec!["*.dmg", "*.iso"]);
+            assert_eq!(config.exclude, vec!["old/**"]);
+            assert_eq!(config.name_regex.as_deref(), Some("^setup"));
+        }
+
+        #[test]
+        fn test_validate_invalid_name_filters() {
+            let args = Args {
+                include: vec!["*.{dmg".to_string()],
+                ..base_args()
+            };
+            assert!(args.validate().unwrap_err().contains("Invalid glob"));
+
+            let args = Args {
+                regex: Some("(unclosed".to_string()),
+                ..base_args()
+            };
+            assert!(args.validate().unwrap_err().contains("Invalid regex"));
+        }
+
+        #[test]
+        fn test_parse_no_ignore() {
+            let config: AppConfig = base_args().into();
+            assert!(config.respect_ignore_files);
+
+            let config: AppConfig = Args::try_parse_from(["fswp", "--no-ignore"])
+                .unwrap()
+                .into();
+            assert!(!config.respect_ignore_files);
+        }
+
+        #[test]
+        fn test_parse_review_kept() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "--review-kept"])
+                .unwrap()
+                .into();
+            assert!(config.review_kept);
+            assert!(!AppConfig::default().review_kept);
+        }
+
+        #[test]
+        fn test_parse_resume_flags() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "--resume"]).unwrap().into();
+            assert!(config.resume);
+            assert!(!config.fresh);
+
+            let config: AppConfig = Args::try_parse_from(["fswp", "--fresh"]).unwrap().into();
+            assert!(config.fresh);
+
+            assert!(Args::try_parse_from(["fswp", "--resume", "--fresh"]).is_err());
+        }
+
+        #[test]
+        fn test_validate_too_many_move_destinations() {
             let args = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
-                min_size: None,
-                max_size: None,
-                yes: false,
-                welcome: false,
+                move_to: (0..10)
+                    .map(|i| PathBuf::from(format!("dir{}", i)))
+                    .collect(),
+                ..base_args()
             };
 
-            assert_eq!(args.directory, PathBuf::from("."));
+            assert!(args.validate().unwrap_err().contains("Too many"));
+        }
+
+        #[test]
+        fn test_args_default_values() {
+            let args = base_args();
+
+            assert_eq!(args.roots, vec![PathBuf::from(".")]);
             assert!(!args.dry_run);
-            assert_eq!(args.sort_by, SortOrder::Date);
+            assert_eq!(args.sort_by, vec![SortOrder::Date]);
             assert!(!args.reverse);
             assert!(!args.show_hidden);
             assert!(!args.yes);
@@ -335,16 +1294,8 @@ mod tests {
         #[test]
         fn test_args_yes_flag() {
             let args_with_yes = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
-                min_size: None,
-                max_size: None,
                 yes: true,
-                welcome: false,
+                ..base_args()
             };
 
             assert!(args_with_yes.yes);
@@ -353,33 +1304,14 @@ mod tests {
         #[test]
         fn test_config_skip_confirm_propagation() {
             // Test that skip_confirm is properly set from args.yes
-            let args_no = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
-                min_size: None,
-                max_size: None,
-                yes: false,
-                welcome: false,
-            };
+            let args_no = base_args();
 
             let config: AppConfig = args_no.into();
             assert!(!config.skip_confirm);
 
             let args_yes = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
-                min_size: None,
-                max_size: None,
                 yes: true,
-                welcome: false,
+                ..base_args()
             };
 
             let config: AppConfig = args_yes.into();
@@ -394,18 +1326,7 @@ mod tests {
 
         #[test]
         fn test_args_get_file_type_filters_empty() {
-            let args = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
-                min_size: None,
-                max_size: None,
-                yes: false,
-                welcome: false,
-            };
+            let args = base_args();
 
             assert!(args.get_file_type_filters().is_none());
         }
@@ -413,16 +1334,8 @@ mod tests {
         #[test]
         fn test_args_get_file_type_filters_multiple() {
             let args = Args {
-                directory: PathBuf::from("."),
                 file_types: vec![FileTypeFilter::Text, FileTypeFilter::Image],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
-                min_size: None,
-                max_size: None,
-                yes: false,
-                welcome: false,
+                ..base_args()
             };
 
             let filters = args.get_file_type_filters().unwrap();
@@ -434,16 +1347,8 @@ mod tests {
         #[test]
         fn test_args_validate_nonexistent_directory() {
             let args = Args {
-                directory: PathBuf::from("/nonexistent/path/12345"),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
-                min_size: None,
-                max_size: None,
-                yes: false,
-                welcome: false,
+                roots: vec![PathBuf::from("/nonexistent/path/12345")],
+                ..base_args()
             };
 
             let result = args.validate();
@@ -451,19 +1356,36 @@ mod tests {
             assert!(result.unwrap_err().contains("does not exist"));
         }
 
+        #[test]
+        fn test_args_validate_several_directories() {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let downloads = temp_dir.path().join("Downloads");
+            let desktop = temp_dir.path().join("Desktop");
+            std::fs::create_dir_all(downloads.join("old")).unwrap();
+            std::fs::create_dir_all(&desktop).unwrap();
+            let with_roots = |roots: &[&Path]| Args {
+                roots: roots.iter().map(|root| root.to_path_buf()).collect(),
+                ..base_args()
+            };
+
+            assert!(with_roots(&[&downloads, &desktop]).validate().is_ok());
+
+            let result = with_roots(&[&downloads, &downloads.join("old")]).validate();
+            assert!(result.unwrap_err().contains("is inside"));
+
+            let result = with_roots(&[&desktop, &downloads, &desktop]).validate();
+            assert!(result.unwrap_err().contains("given twice"));
+
+            let config: AppConfig = with_roots(&[&downloads, &desktop]).into();
+            assert_eq!(config.directory(), downloads);
+            assert!(config.has_several_roots());
+        }
+
         #[test]
         fn test_args_validate_invalid_size_format() {
             let args = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
                 min_size: Some("invalid".to_string()),
-                max_size: None,
-                yes: false,
-                welcome: false,
+                ..base_args()
             };
 
             let result = args.validate();
@@ -474,16 +1396,9 @@ mod tests {
         #[test]
         fn test_args_validate_min_greater_than_max() {
             let args = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
                 min_size: Some("10MB".to_string()),
                 max_size: Some("1MB".to_string()),
-                yes: false,
-                welcome: false,
+                ..base_args()
             };
 
             let result = args.validate();
@@ -494,16 +1409,9 @@ mod tests {
         #[test]
         fn test_args_validate_success() {
             let args = Args {
-                directory: PathBuf::from("."),
-                file_types: vec![],
-                dry_run: false,
-                sort_by: SortOrder::Date,
-                reverse: false,
-                show_hidden: false,
                 min_size: Some("1KB".to_string()),
                 max_size: Some("100MB".to_string()),
-                yes: false,
-                welcome: false,
+                ..base_args()
             };
 
             assert!(args.validate().is_ok());
@@ -516,23 +1424,22 @@ mod tests {
         #[test]
         fn test_app_config_from_args() {
             let args = Args {
-                directory: PathBuf::from("/test/path"),
+                roots: vec![PathBuf::from("/test/path")],
                 file_types: vec![FileTypeFilter::Text],
                 dry_run: true,
-                sort_by: SortOrder::Name,
+                sort_by: vec![SortOrder::Name],
                 reverse: true,
                 show_hidden: true,
                 min_size: Some("1KB".to_string()),
                 max_size: Some("1MB".to_string()),
-                yes: false,
-                welcome: false,
+                ..base_args()
             };
 
             let config: AppConfig = args.into();
 
-            assert_eq!(config.directory, PathBuf::from("/test/path"));
+            assert_eq!(config.directory(), Path::new("/test/path"));
             assert!(config.dry_run);
-            assert_eq!(config.sort_by, SortOrder::Name);
+            assert_eq!(config.sort_by, vec![SortOrder::Name]);
             assert!(config.reverse);
             assert!(config.show_hidden);
             assert_eq!(config.min_size, Some(1024));
@@ -544,9 +1451,9 @@ mod tests {
         fn test_app_config_default() {
             let config = AppConfig::default();
 
-            assert_eq!(config.directory, PathBuf::from("."));
+            assert_eq!(config.directory(), Path::new("."));
             assert!(!config.dry_run);
-            assert_eq!(config.sort_by, SortOrder::Date);
+            assert_eq!(config.sort_by, vec![SortOrder::Date]);
             assert!(!config.reverse);
             assert!(!config.show_hidden);
             assert!(config.min_size.is_none());
@@ -558,5 +1465,51 @@ mod tests {
         fn test_sort_order_default() {
             assert_eq!(SortOrder::default(), SortOrder::Date);
         }
+
+        #[test]
+        fn test_parse_multiple_sort_keys() {
+            let config: AppConfig = Args::try_parse_from(["fswp", "--sort", "type,size"])
+                .unwrap()
+                .into();
+            assert_eq!(config.sort_keys(), vec![SortBy::Type, SortBy::Size]);
+
+            let config: AppConfig =
+                Args::try_parse_from(["fswp", "-s", "extension", "-s", "random", "--seed", "7"])
+                    .unwrap()
+                    .into();
+            assert_eq!(
+                config.sort_keys(),
+                vec![SortBy::Extension, SortBy::Random { seed: 7 }]
+            );
+
+            assert!(Args::try_parse_from(["fswp", "--sort", "type,colour"]).is_err());
+        }
+
+        #[test]
+        fn test_move_slots_resolve_against_directory() {
+            let config = AppConfig {
+                roots: vec![PathBuf::from("/review")],
+                move_destinations: vec![PathBuf::from("Receipts"), PathBuf::from("/archive")],
+                ..AppConfig::default()
+            };
+
+            assert_eq!(
+                config.move_slots(&[PathBuf::from("ignored")]),
+                vec![PathBuf::from("/review/Receipts"), PathBuf::from("/archive")]
+            );
+        }
+
+        #[test]
+        fn test_move_slots_fall_back_to_saved() {
+            let config = AppConfig {
+                roots: vec![PathBuf::from("/review")],
+                ..AppConfig::default()
+            };
+
+            assert_eq!(
+                config.move_slots(&[PathBuf::from("Photos/2024")]),
+                vec![PathBuf::from("/review/Photos/2024")]
+            );
+        }
     }
 }
@@ -1,7 +1,9 @@
 //! User configuration and preferences
 
+use crate::domain::ExtensionTypes;
 use crate::error::{FileTinderError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -9,6 +11,15 @@ use std::path::PathBuf;
 pub struct UserConfig {
     /// Whether the welcome dialog has been shown
     pub welcome_shown: bool,
+    /// Folders bound to the move keys 1-9, used when none are given with --move-to
+    #[serde(default)]
+    pub move_destinations: Vec<PathBuf>,
+    /// Folder for session archives, used when none is given with --archive-to
+    #[serde(default)]
+    pub archive_dir: Option<PathBuf>,
+    /// File types for extensions, overriding detection: {"vue": "text"}
+    #[serde(default)]
+    pub file_types: BTreeMap<String, String>,
 }
 
 impl UserConfig {
@@ -36,6 +47,16 @@ impl UserConfig {
         })
     }
 
+    /// The `file_types` mappings, checked for unknown type names
+    pub fn extension_types(&self) -> Result<ExtensionTypes> {
+        ExtensionTypes::parse(
+            self.file_types
+                .iter()
+                .map(|(extension, name)| (extension.as_str(), name.as_str())),
+        )
+        .map_err(|e| FileTinderError::ConfigError(format!("Invalid \"file_types\": {}", e)))
+    }
+
     /// Save config to file
     pub fn save(&self) -> Result<()> {
         let path = Self::config_path().ok_or_else(|| {
@@ -64,6 +85,7 @@ impl UserConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::FileType;
 
     #[test]
     fn test_default_config() {
@@ -72,12 +94,44 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_config_serialization() {
         let config = UserConfig {
             welcome_shown: true,
+            move_destinations: vec![PathBuf::from("Receipts")],
+            archive_dir: Some(PathBuf::from("/srv/archive")),
+            file_types: BTreeMap::from([("vue".to_string(), "text".to_string())]),
         };
         let json = serde_json::to_string(&config).unwrap();
         let deserialized: UserConfig = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.welcome_shown, true);
+        assert_eq!(deserialized.move_destinations, config.move_destinations);
+        assert_eq!(deserialized.archive_dir, config.archive_dir);
+        assert_eq!(deserialized.file_types, config.file_types);
+    }
+
+    #[test]
+    fn test_config_without_move_destinations() {
+        let config: UserConfig = serde_json::from_str(r#"{"welcome_shown": true}"#).unwrap();
+        assert!(config.move_destinations.is_empty());
+        assert!(config.archive_dir.is_none());
+        assert!(config.file_types.is_empty());
+    }
+
+    #[test]
+    fn test_config_extension_types() {
+        let config: UserConfig = serde_json::from_str(
+            r#"{"welcome_shown": true, "file_types": {"heic": "image", ".LUA": "text"}}"#,
+        )
+        .unwrap();
+        let types = config.extension_types().unwrap();
+        assert_eq!(types.get("heic"), Some(FileType::Image));
+        assert_eq!(types.get("lua"), Some(FileType::Text));
+
+        let config: UserConfig =
+            serde_json::from_str(r#"{"welcome_shown": true, "file_types": {"vue": "code"}}"#)
+                .unwrap();
+        let err = config.extension_types().unwrap_err().to_string();
+        assert!(err.contains("unknown file type \"code\" for .vue"));
     }
 }
@@ -1,10 +1,29 @@
-use super::{Decision, FileEntry};
+use super::{FileEntry, KeepList};
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub struct AppState {
     pub files: Vec<FileEntry>,
     pub current_index: usize,
-    pub decisions_stack: Vec<(usize, Decision)>,
+    /// Reviewed directory, for showing where nested files live
+    pub root: PathBuf,
+    /// Whether files come from several reviewed directories, so each one's
+    /// origin is shown
+    pub several_roots: bool,
+    /// Whether the trash key permanently deletes files (--delete)
+    pub delete_mode: bool,
+    /// Files kept in earlier runs; only shown when reviewing kept files again
+    pub keep_list: KeepList,
+    /// Where each group of identical files sits in `files` when reviewing duplicates
+    pub duplicate_groups: Vec<Range<usize>>,
+    /// Whether discovery is still adding files
+    pub scanning: bool,
+    /// Whether the biggest files and folders are reviewed first (--hogs)
+    pub disk_hogs: bool,
+    /// Why the last action failed, shown in the footer until the next key
+    pub notice: Option<String>,
 }
 
 impl AppState {
@@ -12,10 +31,66 @@ impl AppState {
         Self {
             files,
             current_index: 0,
-            decisions_stack: Vec::new(),
+            root: PathBuf::new(),
+            several_roots: false,
+            delete_mode: false,
+            keep_list: KeepList::new(),
+            duplicate_groups: Vec::new(),
+            scanning: false,
+            disk_hogs: false,
+            notice: None,
         }
     }
 
+    /// Creates a state that reviews `groups` of duplicates one after another
+    pub fn from_duplicate_groups(groups: Vec<Vec<FileEntry>>) -> Self {
+        let mut duplicate_groups = Vec::with_capacity(groups.len());
+        let mut files = Vec::new();
+        for group in groups {
+            let start = files.len();
+            files.extend(group);
+            duplicate_groups.push(start..files.len());
+        }
+
+        Self {
+            duplicate_groups,
+            ..Self::new(files)
+        }
+    }
+
+    /// Position and file range of the duplicate group the cursor is in
+    pub fn current_group(&self) -> Option<(usize, Range<usize>)> {
+        self.duplicate_groups
+            .iter()
+            .position(|group| group.contains(&self.current_index))
+            .map(|position| (position, self.duplicate_groups[position].clone()))
+    }
+
+    /// Moves the cursor to the first file of the next duplicate group, if any
+    pub fn next_group(&mut self) {
+        if let Some((position, _)) = self.current_group() {
+            if let Some(next) = self.duplicate_groups.get(position + 1) {
+                self.current_index = next.start;
+            }
+        }
+    }
+
+    /// Adds files found while the review is under way. Files from `unseen`
+    /// on have not been looked at yet, so the new ones are sorted in among
+    /// them; the files before keep their positions.
+    pub fn add_files(
+        &mut self,
+        mut files: Vec<FileEntry>,
+        unseen: usize,
+        compare: impl Fn(&FileEntry, &FileEntry) -> Ordering,
+    ) {
+        let mut tail = self.files.split_off(unseen.min(self.files.len()));
+        // Both runs are sorted already, which the stable sort merges cheaply
+        tail.append(&mut files);
+        tail.sort_by(compare);
+        self.files.append(&mut tail);
+    }
+
     pub fn next(&mut self) {
         if self.current_index < self.files.len().saturating_sub(1) {
             self.current_index += 1;
@@ -32,12 +107,15 @@ impl AppState {
         self.files.get(self.current_index)
     }
 
-    pub fn record_decision(&mut self, decision: Decision) {
-        self.decisions_stack.push((self.current_index, decision));
+    /// Path of `file` relative to the reviewed directory it was found in
+    pub fn relative_path<'a>(&self, file: &'a FileEntry) -> &'a Path {
+        let root = file.root.as_deref().unwrap_or(&self.root);
+        file.path.strip_prefix(root).unwrap_or(&file.path)
     }
 
-    pub fn undo(&mut self) -> Option<(usize, Decision)> {
-        self.decisions_stack.pop()
+    /// Moves the cursor to `index`, clamped to the last file
+    pub fn jump_to(&mut self, index: usize) {
+        self.current_index = index.min(self.files.len().saturating_sub(1));
     }
 }
 
@@ -55,6 +133,10 @@ mod tests {
             size: 0,
             modified_date: Utc::now(),
             file_type: FileType::Text,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         }
     }
 
@@ -69,7 +151,22 @@ mod tests {
 
         assert_eq!(state.files.len(), 2);
         assert_eq!(state.current_index, 0);
-        assert_eq!(state.decisions_stack.len(), 0);
+    }
+
+    #[test]
+    fn test_app_state_add_files() {
+        let by_name = |a: &FileEntry, b: &FileEntry| a.name.cmp(&b.name);
+        let mut state = AppState::new(vec![create_test_entry("c.txt"), create_test_entry("e.txt")]);
+
+        // The first file was seen already, so "a" cannot go before it
+        state.add_files(
+            vec![create_test_entry("a.txt"), create_test_entry("d.txt")],
+            1,
+            by_name,
+        );
+
+        let names: Vec<_> = state.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["c.txt", "a.txt", "d.txt", "e.txt"]);
     }
 
     #[test]
@@ -124,43 +221,63 @@ mod tests {
     }
 
     #[test]
-    fn test_app_state_record_decision() {
-        let files = vec![
-            create_test_entry("file1.txt"),
-            create_test_entry("file2.txt"),
-        ];
-        let mut state = AppState::new(files);
+    fn test_app_state_relative_path() {
+        let mut file = create_test_entry("notes.txt");
+        file.path = PathBuf::from("/home/me/Downloads/old/notes.txt");
+        let mut state = AppState::new(vec![file.clone()]);
+
+        state.root = PathBuf::from("/home/me/Downloads");
+        assert_eq!(state.relative_path(&file), Path::new("old/notes.txt"));
 
-        state.record_decision(Decision::Keep);
+        state.root = PathBuf::from("/elsewhere");
+        assert_eq!(state.relative_path(&file), file.path);
 
-        assert_eq!(state.decisions_stack.len(), 1);
-        assert_eq!(state.decisions_stack[0], (0, Decision::Keep));
+        // A file found in another reviewed directory is relative to that one
+        file.root = Some(PathBuf::from("/home/me/Downloads/old"));
+        assert_eq!(state.relative_path(&file), Path::new("notes.txt"));
     }
 
     #[test]
-    fn test_app_state_undo() {
-        let files = vec![
-            create_test_entry("file1.txt"),
-            create_test_entry("file2.txt"),
-        ];
-        let mut state = AppState::new(files);
+    fn test_app_state_duplicate_groups() {
+        let mut state = AppState::from_duplicate_groups(vec![
+            vec![create_test_entry("a.txt"), create_test_entry("a copy.txt")],
+            vec![
+                create_test_entry("b.txt"),
+                create_test_entry("b copy.txt"),
+                create_test_entry("b copy 2.txt"),
+            ],
+        ]);
 
-        state.record_decision(Decision::Keep);
-        state.current_index = 1;
-        state.record_decision(Decision::Trash);
+        assert_eq!(state.files.len(), 5);
+        assert_eq!(state.current_group(), Some((0, 0..2)));
+
+        state.next();
+        state.next_group();
+        assert_eq!(state.current_index, 2);
+        assert_eq!(state.current_group(), Some((1, 2..5)));
 
-        let undone = state.undo();
-        assert!(undone.is_some());
-        assert_eq!(undone.unwrap(), (1, Decision::Trash));
-        assert_eq!(state.decisions_stack.len(), 1);
+        // Already in the last group
+        state.next_group();
+        assert_eq!(state.current_index, 2);
+
+        assert!(AppState::new(vec![create_test_entry("a.txt")])
+            .current_group()
+            .is_none());
     }
 
     #[test]
-    fn test_app_state_undo_empty() {
-        let files = vec![create_test_entry("file1.txt")];
+    fn test_app_state_jump_to() {
+        let files = vec![
+            create_test_entry("file1.txt"),
+            create_test_entry("file2.txt"),
+            create_test_entry("file3.txt"),
+        ];
         let mut state = AppState::new(files);
 
-        let undone = state.undo();
-        assert!(undone.is_none());
+        state.jump_to(1);
+        assert_eq!(state.current_index, 1);
+
+        state.jump_to(10);
+        assert_eq!(state.current_index, 2);
     }
 }
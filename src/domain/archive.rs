@@ -0,0 +1,555 @@
+//! Session archive backing the archive decision
+//!
+//! Archived files are appended to one tar.gz or zip per session, next to a JSON
+//! manifest recording where each entry came from so they can be extracted back.
+//! Every change writes a complete new archive to a temporary file that replaces
+//! the old one only once it has been synced, so a crash never leaves a torn archive.
+//! Since that costs a pass over everything already archived, the decision engine
+//! stages files during the session and adds them all in one go at its end.
+
+use super::rename::numbered_name;
+use chrono::{DateTime, Local, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, Metadata};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Name of the manifest entry stored inside every session archive
+pub const MANIFEST_NAME: &str = "fswp-manifest.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarGz => "tar.gz",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+
+    /// Guesses the format of an existing archive from its file name
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Where an archived file came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Name of the entry inside the archive
+    pub name: String,
+    pub original_path: PathBuf,
+    pub size: u64,
+    pub modified_date: DateTime<Utc>,
+    /// Unix permission bits, restored on extraction
+    #[serde(default)]
+    pub mode: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// A tar.gz or zip file that archived files are appended to.
+/// The file itself is only created when the first entry is added.
+#[derive(Debug)]
+pub struct SessionArchive {
+    path: PathBuf,
+    format: ArchiveFormat,
+    manifest: ArchiveManifest,
+}
+
+impl SessionArchive {
+    pub fn new(path: PathBuf, format: ArchiveFormat) -> Self {
+        Self {
+            path,
+            format,
+            manifest: ArchiveManifest::default(),
+        }
+    }
+
+    /// A new timestamped archive inside `dir`, named so it never replaces an existing one
+    pub fn in_dir(dir: &Path, format: ArchiveFormat) -> Self {
+        let stem = format!("fswp-archive-{}", Local::now().format("%Y%m%d-%H%M%S"));
+        let path = (0..)
+            .map(|n| match n {
+                0 => dir.join(format!("{}.{}", stem, format.extension())),
+                n => dir.join(format!("{}-{}.{}", stem, n, format.extension())),
+            })
+            .find(|path| !path.exists())
+            .expect("unbounded range always yields a free name");
+
+        Self::new(path, format)
+    }
+
+    /// Opens an existing archive and reads its manifest
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let format = ArchiveFormat::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Not a .tar.gz or .zip archive: {:?}", path),
+            )
+        })?;
+
+        let mut archive = Self::new(path.to_path_buf(), format);
+        let mut json = Vec::new();
+        archive.read_entry(MANIFEST_NAME, &mut json)?;
+        archive.manifest = serde_json::from_slice(&json)?;
+        Ok(archive)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn format(&self) -> ArchiveFormat {
+        self.format
+    }
+
+    pub fn manifest(&self) -> &ArchiveManifest {
+        &self.manifest
+    }
+
+    /// Appends the file at `path` and returns the name of its entry.
+    /// The original file is left in place.
+    pub fn add(&mut self, path: &Path) -> io::Result<String> {
+        let mut names = self.add_all(&[(path, path)])?;
+        Ok(names.remove(0))
+    }
+
+    /// Appends several files in one rewrite and returns their entry names in
+    /// order. Each file is given as where to read it and the original path to
+    /// record for it; the files are left in place.
+    pub fn add_all(&mut self, files: &[(&Path, &Path)]) -> io::Result<Vec<String>> {
+        if self.manifest.entries.is_empty() && self.path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Archive already exists: {:?}", self.path),
+            ));
+        }
+
+        let mut manifest = self.manifest.clone();
+        let mut added = Vec::with_capacity(files.len());
+        for &(source, original_path) in files {
+            let metadata = fs::metadata(source)?;
+            if !metadata.is_file() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Only regular files can be archived: {:?}", original_path),
+                ));
+            }
+
+            let file_name = original_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "file".to_string());
+            let taken = |name: &str| {
+                name == MANIFEST_NAME || manifest.entries.iter().any(|e| e.name == name)
+            };
+            let name = if taken(&file_name) {
+                (1..)
+                    .map(|n| numbered_name(&file_name, n))
+                    .find(|name| !taken(name))
+                    .expect("unbounded range always yields a free name")
+            } else {
+                file_name
+            };
+
+            manifest.entries.push(ManifestEntry {
+                name: name.clone(),
+                original_path: original_path.to_path_buf(),
+                size: metadata.len(),
+                modified_date: metadata.modified()?.into(),
+                mode: file_mode(&metadata),
+            });
+            added.push((name, source));
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let new_entries: Vec<(&str, &Path)> = added
+            .iter()
+            .map(|(name, source)| (name.as_str(), *source))
+            .collect();
+        self.rewrite(&manifest, None, &new_entries)?;
+        self.manifest = manifest;
+        Ok(added.into_iter().map(|(name, _)| name).collect())
+    }
+
+    /// Drops an entry, deleting the archive once it holds nothing else
+    pub fn remove(&mut self, name: &str) -> io::Result<()> {
+        self.entry(name).ok_or_else(|| not_in_archive(name))?;
+
+        let mut manifest = self.manifest.clone();
+        manifest.entries.retain(|entry| entry.name != name);
+
+        if manifest.entries.is_empty() {
+            fs::remove_file(&self.path)?;
+        } else {
+            self.rewrite(&manifest, Some(name), &[])?;
+        }
+        self.manifest = manifest;
+        Ok(())
+    }
+
+    /// Writes an entry back out to `to` and removes it from the archive.
+    /// Refuses to overwrite an existing file.
+    pub fn extract(&mut self, name: &str, to: &Path) -> io::Result<()> {
+        let entry = self
+            .entry(name)
+            .cloned()
+            .ok_or_else(|| not_in_archive(name))?;
+
+        if to.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Original path is occupied: {:?}", to),
+            ));
+        }
+
+        let mut file = File::create_new(to)?;
+        let written = self.read_entry(name, &mut file).and_then(|_| {
+            if file.metadata()?.len() != entry.size {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Archived copy of {:?} has the wrong size", name),
+                ));
+            }
+            file.set_modified(entry.modified_date.into())?;
+            file.sync_all()
+        });
+        drop(file);
+        if let Err(e) = written {
+            fs::remove_file(to).ok();
+            return Err(e);
+        }
+        set_file_mode(to, entry.mode);
+
+        // Keep the file in exactly one place if the archive cannot be rewritten
+        if let Err(e) = self.remove(name) {
+            fs::remove_file(to).ok();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn entry(&self, name: &str) -> Option<&ManifestEntry> {
+        self.manifest
+            .entries
+            .iter()
+            .find(|entry| entry.name == name)
+    }
+
+    /// Copies the contents of the entry called `name` into `out`
+    fn read_entry(&self, name: &str, out: &mut impl Write) -> io::Result<()> {
+        let file = File::open(&self.path)?;
+
+        match self.format {
+            ArchiveFormat::TarGz => {
+                let mut archive = tar::Archive::new(GzDecoder::new(file));
+                for entry in archive.entries()? {
+                    let mut entry = entry?;
+                    if entry.path()?.to_string_lossy() == name {
+                        io::copy(&mut entry, out)?;
+                        return Ok(());
+                    }
+                }
+                Err(not_in_archive(name))
+            }
+            ArchiveFormat::Zip => {
+                let mut archive = ZipArchive::new(file)?;
+                let mut entry = archive.by_name(name).map_err(|_| not_in_archive(name))?;
+                io::copy(&mut entry, out)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces the archive with one holding `manifest`, every current entry
+    /// except `skip`, and the files in `add` under their entry names
+    fn rewrite(
+        &self,
+        manifest: &ArchiveManifest,
+        skip: Option<&str>,
+        add: &[(&str, &Path)],
+    ) -> io::Result<()> {
+        let file_name = self.path.file_name().unwrap_or_default().to_string_lossy();
+        let temp_path = self.path.with_file_name(format!(".{}.tmp", file_name));
+        let json = serde_json::to_vec_pretty(manifest)?;
+
+        let written = match self.format {
+            ArchiveFormat::TarGz => self.write_tar_gz(&temp_path, &json, skip, add),
+            ArchiveFormat::Zip => self.write_zip(&temp_path, &json, skip, add),
+        };
+        if let Err(e) = written.and_then(|_| fs::rename(&temp_path, &self.path)) {
+            fs::remove_file(&temp_path).ok();
+            return Err(e);
+        }
+
+        if let Some(parent) = self.path.parent() {
+            if let Ok(dir) = File::open(parent) {
+                dir.sync_all().ok();
+            }
+        }
+        Ok(())
+    }
+
+    fn write_tar_gz(
+        &self,
+        temp_path: &Path,
+        manifest_json: &[u8],
+        skip: Option<&str>,
+        add: &[(&str, &Path)],
+    ) -> io::Result<()> {
+        let file = File::create(temp_path)?;
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+        if !self.manifest.entries.is_empty() {
+            let mut archive = tar::Archive::new(GzDecoder::new(File::open(&self.path)?));
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let name = entry.path()?.to_string_lossy().into_owned();
+                if name == MANIFEST_NAME || Some(name.as_str()) == skip {
+                    continue;
+                }
+                let mut header = entry.header().clone();
+                builder.append_data(&mut header, &name, &mut entry)?;
+            }
+        }
+
+        for &(name, path) in add {
+            builder.append_path_with_name(path, name)?;
+        }
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest_json.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(Utc::now().timestamp().max(0) as u64);
+        builder.append_data(&mut header, MANIFEST_NAME, manifest_json)?;
+
+        let file = builder.into_inner()?.finish()?;
+        file.sync_all()
+    }
+
+    fn write_zip(
+        &self,
+        temp_path: &Path,
+        manifest_json: &[u8],
+        skip: Option<&str>,
+        add: &[(&str, &Path)],
+    ) -> io::Result<()> {
+        let mut writer = ZipWriter::new(File::create(temp_path)?);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        if !self.manifest.entries.is_empty() {
+            let mut archive = ZipArchive::new(File::open(&self.path)?)?;
+            for i in 0..archive.len() {
+                let entry = archive.by_index_raw(i)?;
+                let name = entry.name();
+                if name == MANIFEST_NAME || Some(name) == skip {
+                    continue;
+                }
+                writer.raw_copy_file(entry)?;
+            }
+        }
+
+        for &(name, path) in add {
+            let mut source = File::open(path)?;
+            let metadata = source.metadata()?;
+            let mut file_options = options.large_file(metadata.len() >= u32::MAX as u64);
+            if let Some(mode) = file_mode(&metadata) {
+                file_options = file_options.unix_permissions(mode);
+            }
+            writer.start_file(name, file_options)?;
+            io::copy(&mut source, &mut writer)?;
+        }
+
+        writer.start_file(MANIFEST_NAME, options)?;
+        writer.write_all(manifest_json)?;
+
+        let file = writer.finish()?;
+        file.sync_all()
+    }
+}
+
+fn not_in_archive(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{:?} is not in the archive", name),
+    )
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: Option<u32>) {
+    use std::os::unix::fs::PermissionsExt;
+    if let Some(mode) = mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).ok();
+    }
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: Option<u32>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn round_trip(format: ArchiveFormat) {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("notes.txt");
+        fs::write(&file, b"keep me somewhere").unwrap();
+        let mut archive = SessionArchive::in_dir(temp_dir.path(), format);
+
+        let name = archive.add(&file).unwrap();
+        fs::remove_file(&file).unwrap();
+        assert!(archive.path().exists());
+        assert_eq!(archive.manifest().entries[0].original_path, file);
+
+        archive.extract(&name, &file).unwrap();
+
+        assert_eq!(fs::read(&file).unwrap(), b"keep me somewhere");
+        assert!(archive.manifest().entries.is_empty());
+        assert!(!archive.path().exists());
+    }
+
+    #[test]
+    fn test_archive_round_trip_tar_gz() {
+        round_trip(ArchiveFormat::TarGz);
+    }
+
+    #[test]
+    fn test_archive_round_trip_zip() {
+        round_trip(ArchiveFormat::Zip);
+    }
+
+    #[test]
+    fn test_archive_manifest_is_stored_inside() {
+        for format in [ArchiveFormat::TarGz, ArchiveFormat::Zip] {
+            let temp_dir = TempDir::new().unwrap();
+            let a = temp_dir.path().join("a.log");
+            let b = temp_dir.path().join("b.log");
+            fs::write(&a, b"a").unwrap();
+            fs::write(&b, b"bb").unwrap();
+
+            let mut archive = SessionArchive::in_dir(temp_dir.path(), format);
+            archive.add(&a).unwrap();
+            archive.add(&b).unwrap();
+
+            let loaded = SessionArchive::load(archive.path()).unwrap();
+            assert_eq!(loaded.format(), format);
+            assert_eq!(loaded.manifest(), archive.manifest());
+            assert_eq!(loaded.manifest().entries[1].size, 2);
+        }
+    }
+
+    #[test]
+    fn test_archive_add_all_records_original_paths() {
+        for format in [ArchiveFormat::TarGz, ArchiveFormat::Zip] {
+            let temp_dir = TempDir::new().unwrap();
+            let staged = temp_dir.path().join("staged");
+            fs::create_dir_all(&staged).unwrap();
+            fs::write(staged.join("file_0"), b"first").unwrap();
+            fs::write(staged.join("file_1"), b"second").unwrap();
+            let originals = [
+                temp_dir.path().join("one").join("app.log"),
+                temp_dir.path().join("two").join("app.log"),
+            ];
+            let first = staged.join("file_0");
+            let second = staged.join("file_1");
+
+            let mut archive = SessionArchive::in_dir(temp_dir.path(), format);
+            let names = archive
+                .add_all(&[(&first, &originals[0]), (&second, &originals[1])])
+                .unwrap();
+
+            assert_eq!(names, vec!["app.log", "app (1).log"]);
+            let loaded = SessionArchive::load(archive.path()).unwrap();
+            assert_eq!(loaded.manifest().entries[1].original_path, originals[1]);
+            assert_eq!(loaded.manifest().entries[1].size, 6);
+
+            fs::create_dir_all(originals[1].parent().unwrap()).unwrap();
+            archive.extract("app (1).log", &originals[1]).unwrap();
+            assert_eq!(fs::read(&originals[1]).unwrap(), b"second");
+        }
+    }
+
+    #[test]
+    fn test_archive_dedupes_entry_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("one").join("photo.jpg");
+        let second = temp_dir.path().join("two").join("photo.jpg");
+        for path in [&first, &second] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, path.to_string_lossy().as_bytes()).unwrap();
+        }
+        let mut archive = SessionArchive::in_dir(temp_dir.path(), ArchiveFormat::TarGz);
+
+        assert_eq!(archive.add(&first).unwrap(), "photo.jpg");
+        assert_eq!(archive.add(&second).unwrap(), "photo (1).jpg");
+
+        fs::remove_file(&second).unwrap();
+        archive.extract("photo (1).jpg", &second).unwrap();
+        assert_eq!(
+            fs::read_to_string(&second).unwrap(),
+            second.to_string_lossy()
+        );
+        assert_eq!(archive.manifest().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_archive_extract_refuses_occupied_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("report.pdf");
+        fs::write(&file, b"archived").unwrap();
+        let mut archive = SessionArchive::in_dir(temp_dir.path(), ArchiveFormat::Zip);
+
+        let name = archive.add(&file).unwrap();
+        fs::write(&file, b"newer").unwrap();
+
+        let err = archive.extract(&name, &file).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read(&file).unwrap(), b"newer");
+        assert_eq!(archive.manifest().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_archive_in_dir_never_reuses_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = SessionArchive::in_dir(temp_dir.path(), ArchiveFormat::Zip);
+        fs::write(first.path(), b"someone else's archive").unwrap();
+
+        let second = SessionArchive::in_dir(temp_dir.path(), ArchiveFormat::Zip);
+
+        assert_ne!(first.path(), second.path());
+    }
+}
@@ -1,7 +1,24 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use super::{FileEntry, History};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Decision {
     Keep,
     Trash,
+    /// Permanently delete the entry instead of sending it to the system trash
+    Delete,
+    /// File the entry into the `dest` directory
+    Move {
+        dest: PathBuf,
+    },
+    /// Keep the entry in place under a new file name
+    Rename {
+        name: String,
+    },
+    /// Append the entry to the session archive and remove the original
+    Archive,
 }
 
 #[derive(Debug, Clone)]
@@ -9,4 +26,100 @@ pub struct DecisionStatistics {
     pub total_files: usize,
     pub kept: usize,
     pub trashed: usize,
+    pub deleted: usize,
+    pub moved: usize,
+    pub renamed: usize,
+    pub archived: usize,
+    /// Number of files moved into each destination directory
+    pub moved_by_destination: BTreeMap<PathBuf, usize>,
+    /// Bytes freed by trashed and deleted files
+    pub freed_bytes: u64,
+    /// Bytes of decided files. Kept folders are left out, since their
+    /// contents come up for review on their own in disk-hog mode.
+    pub decided_bytes: u64,
+    /// Bytes of files without a decision yet
+    pub pending_bytes: u64,
+}
+
+impl DecisionStatistics {
+    /// Tallies the decisions in effect in `history` for `files`
+    pub fn tally(history: &History, files: &[FileEntry]) -> Self {
+        let mut stats = DecisionStatistics {
+            total_files: files.len(),
+            kept: 0,
+            trashed: 0,
+            deleted: 0,
+            moved: 0,
+            renamed: 0,
+            archived: 0,
+            moved_by_destination: BTreeMap::new(),
+            freed_bytes: 0,
+            decided_bytes: 0,
+            pending_bytes: 0,
+        };
+        let mut decided = vec![false; files.len()];
+
+        for entry in history.applied() {
+            let Some(file) = files.get(entry.index) else {
+                continue;
+            };
+            decided[entry.index] = true;
+
+            match &entry.decision {
+                Decision::Keep => stats.kept += 1,
+                Decision::Trash => stats.trashed += 1,
+                Decision::Delete => stats.deleted += 1,
+                Decision::Move { dest } => {
+                    stats.moved += 1;
+                    *stats.moved_by_destination.entry(dest.clone()).or_insert(0) += 1;
+                }
+                Decision::Rename { .. } => stats.renamed += 1,
+                Decision::Archive => stats.archived += 1,
+            }
+
+            if matches!(entry.decision, Decision::Trash | Decision::Delete) {
+                stats.freed_bytes += file.size;
+            }
+            if !(entry.decision == Decision::Keep && file.is_dir()) {
+                stats.decided_bytes += file.size;
+            }
+        }
+
+        stats.pending_bytes = files
+            .iter()
+            .zip(&decided)
+            .filter(|(_, &decided)| !decided)
+            .map(|(file, _)| file.size)
+            .sum();
+        stats
+    }
+
+    /// Bytes likely freed by the end of the review: what is freed so far, plus
+    /// the same share of the files still pending
+    pub fn projected_bytes(&self) -> u64 {
+        if self.decided_bytes == 0 {
+            return self.freed_bytes;
+        }
+        let share = u128::from(self.pending_bytes) * u128::from(self.freed_bytes)
+            / u128::from(self.decided_bytes);
+        self.freed_bytes + share as u64
+    }
+
+    /// Number of files that have a decision
+    pub fn decided(&self) -> usize {
+        self.kept + self.trashed + self.deleted + self.moved + self.renamed + self.archived
+    }
+}
+
+/// Outcome of handing staged files over to the system trash or deleting them
+#[derive(Debug, Clone, Default)]
+pub struct CommitReport {
+    /// Original paths of files that are now in the system trash
+    pub trashed: Vec<PathBuf>,
+    /// Original paths of files that were permanently deleted
+    pub deleted: Vec<PathBuf>,
+    /// Original paths of files written into the session archive
+    pub archived: Vec<PathBuf>,
+    /// Files that could not be trashed or deleted, with the reason. These were left restorable.
+    pub failed: Vec<(PathBuf, String)>,
 }
@@ -1,33 +1,67 @@
-use super::{Decision, DecisionStatistics, FileEntry};
-use std::fs;
-use std::io;
-use std::path::PathBuf;
+use super::archive::SessionArchive;
+use super::journal::{self, Journal, JournalEntry};
+use super::rename::{numbered_name, validate_file_name};
+use super::session::absolute;
+use super::staging;
+use super::{CommitReport, Decision, DecisionStatistics, FileEntry, History, HistoryEntry};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Debug)]
 pub struct DecisionEngine {
     pub files: Vec<FileEntry>,
-    pub decisions: Vec<(usize, Decision)>,
+    pub history: History,
+    /// Session directory holding the journal, and staging for files on its device
     staging_dir: PathBuf,
+    /// Reviewed directories, used to stage files from other devices at their root
+    review_roots: Vec<PathBuf>,
+    /// Every directory this session has staged files into
+    staging_dirs: BTreeSet<PathBuf>,
+    /// Where each trashed or deleted file currently waits, by file index
+    staged_paths: HashMap<usize, PathBuf>,
+    /// Where each moved or renamed file ended up, by file index
+    moved_paths: HashMap<usize, PathBuf>,
+    /// Where archived files go; archiving fails until one is set
+    archive: Option<SessionArchive>,
+    journal: Journal,
+    /// Held for the engine's lifetime so recovery can tell this session is alive
+    session_lock: Option<File>,
     dry_run: bool,
 }
 
 impl DecisionEngine {
+    /// Creates an engine that stages files in the system temp directory
     pub fn new(files: Vec<FileEntry>) -> Self {
+        Self::with_sessions_dir(files, &std::env::temp_dir())
+    }
+
+    /// Creates an engine whose staging directory and journal live under `sessions_dir`
+    pub fn with_sessions_dir(files: Vec<FileEntry>, sessions_dir: &Path) -> Self {
         use std::time::UNIX_EPOCH;
 
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_nanos();
-        let staging_dir =
-            std::env::temp_dir().join(format!("fswp-{}-{}", std::process::id(), timestamp));
+        let staging_dir = sessions_dir.join(format!("fswp-{}-{}", std::process::id(), timestamp));
         fs::create_dir_all(&staging_dir).ok();
+        let session_lock = journal::lock_session(&staging_dir).ok();
 
         Self {
             files,
-            decisions: Vec::new(),
+            history: History::new(),
+            journal: Journal::new(&staging_dir),
             staging_dir,
+            review_roots: Vec::new(),
+            staging_dirs: BTreeSet::new(),
+            staged_paths: HashMap::new(),
+            moved_paths: HashMap::new(),
+            archive: None,
+            session_lock,
             dry_run: false,
         }
     }
@@ -40,6 +74,42 @@ impl DecisionEngine {
         self.dry_run
     }
 
+    /// Registers a reviewed directory. Files from a device other than the session
+    /// directory's are staged in a hidden `.fswp-staging` directory at this root.
+    pub fn add_review_root(&mut self, root: &Path) {
+        self.review_roots.push(root.to_path_buf());
+    }
+
+    /// Replaces the files from `from` on with `files`, for when discovery
+    /// adds files during the review. `files[0]` is the file for index `from`.
+    /// Files that have a decision, even an undone one, are never replaced:
+    /// `from` is raised past them and the matching part of `files` skipped.
+    pub fn replace_undecided(&mut self, from: usize, files: &[FileEntry]) {
+        let start = from.max(self.first_replaceable());
+        self.files.truncate(start);
+        self.files
+            .extend_from_slice(files.get(start - from..).unwrap_or_default());
+    }
+
+    /// Index of the first file after every file with a decision, undone or not
+    pub fn first_replaceable(&self) -> usize {
+        self.history
+            .entries()
+            .iter()
+            .map(|entry| entry.index + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Sets the archive that `Decision::Archive` appends files to
+    pub fn set_archive(&mut self, archive: SessionArchive) {
+        self.archive = Some(archive);
+    }
+
+    pub fn archive(&self) -> Option<&SessionArchive> {
+        self.archive.as_ref()
+    }
+
     pub fn record_decision(&mut self, index: usize, decision: Decision) -> io::Result<()> {
         if index >= self.files.len() {
             return Err(io::Error::new(
@@ -48,17 +118,159 @@ impl DecisionEngine {
             ));
         }
 
+        self.apply(index, &decision)?;
+        self.history.push(HistoryEntry { index, decision });
+        Ok(())
+    }
+
+    /// Keeps the file at `keep` and records `discard` (Trash or Delete) for the
+    /// rest of `group`. Files that already have a decision are left alone. If
+    /// any decision fails, the ones made so far are undone; the failure is
+    /// returned either way, mentioning any decision that could not be undone.
+    pub fn resolve_duplicates(
+        &mut self,
+        group: Range<usize>,
+        keep: usize,
+        discard: Decision,
+    ) -> io::Result<()> {
+        let mut recorded = 0;
+        for index in group {
+            if self.is_decided(index) {
+                continue;
+            }
+            let decision = if index == keep {
+                Decision::Keep
+            } else {
+                discard.clone()
+            };
+
+            if let Err(e) = self.record_decision(index, decision) {
+                for _ in 0..recorded {
+                    // Later undos would only hit the same decision again
+                    if let Err(rollback) = self.undo() {
+                        return Err(io::Error::new(
+                            e.kind(),
+                            format!("{}; undoing the rest of the group failed: {}", e, rollback),
+                        ));
+                    }
+                }
+                return Err(e);
+            }
+            recorded += 1;
+        }
+        Ok(())
+    }
+
+    /// Whether the file at `index` has a decision in effect
+    pub fn is_decided(&self, index: usize) -> bool {
+        self.history
+            .applied()
+            .iter()
+            .any(|entry| entry.index == index)
+    }
+
+    /// Undoes the most recent decision in effect
+    pub fn undo(&mut self) -> io::Result<HistoryEntry> {
+        let entry =
+            self.history.last_applied().cloned().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "No decisions to undo")
+            })?;
+
+        self.unapply(entry.index, &entry.decision)?;
+        self.history.undo();
+        Ok(entry)
+    }
+
+    /// Re-applies the most recently undone decision
+    pub fn redo(&mut self) -> io::Result<HistoryEntry> {
+        let entry =
+            self.history.next_redo().cloned().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "No decisions to redo")
+            })?;
+
+        self.apply(entry.index, &entry.decision)?;
+        self.history.redo();
+        Ok(entry)
+    }
+
+    /// Undoes or redoes decisions until exactly `position` of them are in effect.
+    /// Stops at the first step that fails, leaving the decisions before it in effect.
+    pub fn jump_to(&mut self, position: usize) -> io::Result<()> {
+        let position = position.min(self.history.entries().len());
+
+        while self.history.len() > position {
+            self.undo()?;
+        }
+        while self.history.len() < position {
+            self.redo()?;
+        }
+        Ok(())
+    }
+
+    /// Reverts the decision at `position` in the timeline without touching later ones
+    pub fn revert(&mut self, position: usize) -> io::Result<HistoryEntry> {
+        let entry = self
+            .history
+            .applied()
+            .get(position)
+            .cloned()
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "Decision is not in effect")
+            })?;
+
+        self.unapply(entry.index, &entry.decision)?;
+        self.history.remove(position);
+        Ok(entry)
+    }
+
+    /// Carries out the filesystem side of a decision
+    fn apply(&mut self, index: usize, decision: &Decision) -> io::Result<()> {
         let file_entry = &self.files[index];
         let original_path = &file_entry.path;
 
         match decision {
-            Decision::Keep => {
-                self.decisions.push((index, decision));
+            Decision::Keep => Ok(()),
+            Decision::Trash | Decision::Delete | Decision::Archive => {
+                if *decision == Decision::Archive {
+                    self.check_archivable(file_entry)?;
+                }
+
+                if self.dry_run {
+                    return Ok(());
+                }
+
+                if !original_path.exists() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("File not found: {:?}", original_path),
+                    ));
+                }
+
+                let staging_dir =
+                    staging::staging_dir_for(original_path, &self.staging_dir, &self.review_roots);
+                let staged_path = staging_dir.join(format!("file_{}", index));
+                fs::create_dir_all(&staging_dir)?;
+                self.staging_dirs.insert(staging_dir);
+
+                // The journal entry must hit the disk before the file moves
+                self.journal.push(JournalEntry {
+                    original_path: original_path.clone(),
+                    staged_path: staged_path.clone(),
+                    size: file_entry.size,
+                    modified_date: file_entry.modified_date,
+                    decision: decision.clone(),
+                })?;
+
+                if let Err(e) = staging::move_file(original_path, &staged_path) {
+                    self.journal.remove_entry(&staged_path).ok();
+                    return Err(e);
+                }
+
+                self.staged_paths.insert(index, staged_path);
                 Ok(())
             }
-            Decision::Trash => {
+            Decision::Move { dest } => {
                 if self.dry_run {
-                    self.decisions.push((index, decision));
                     return Ok(());
                 }
 
@@ -69,32 +281,86 @@ impl DecisionEngine {
                     ));
                 }
 
-                let staged_path = self.get_staged_path(index);
-                fs::create_dir_all(staged_path.parent().unwrap())?;
-                fs::rename(original_path, &staged_path)?;
+                fs::create_dir_all(dest)?;
+                let target = available_path(dest, &file_entry.name);
+                staging::move_file(original_path, &target)?;
+                self.moved_paths.insert(index, target);
+                Ok(())
+            }
+            Decision::Rename { name } => {
+                validate_file_name(name)?;
+
+                let target = original_path.with_file_name(name);
+                if &target == original_path {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "File name is unchanged",
+                    ));
+                }
+
+                if self.dry_run {
+                    return Ok(());
+                }
+
+                if !original_path.exists() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("File not found: {:?}", original_path),
+                    ));
+                }
 
-                self.decisions.push((index, decision));
+                // Never overwrite; the user picked this name and should pick another
+                staging::move_file(original_path, &target).map_err(|e| {
+                    if e.kind() == io::ErrorKind::AlreadyExists {
+                        io::Error::new(
+                            io::ErrorKind::AlreadyExists,
+                            format!("A file named {:?} already exists", name),
+                        )
+                    } else {
+                        e
+                    }
+                })?;
+                self.moved_paths.insert(index, target);
                 Ok(())
             }
         }
     }
 
-    pub fn undo(&mut self) -> io::Result<()> {
-        let (index, decision) = self
-            .decisions
-            .pop()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "No decisions to undo"))?;
+    /// Whether `file_entry` can go into the session archive, which is only
+    /// written at the end of the session
+    fn check_archivable(&self, file_entry: &FileEntry) -> io::Result<()> {
+        if file_entry.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Directories cannot be archived",
+            ));
+        }
+
+        let archive = self
+            .archive
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "No archive configured"))?;
+        if absolute(&file_entry.path) == absolute(archive.path()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The session archive cannot be archived into itself",
+            ));
+        }
+        Ok(())
+    }
 
+    /// Reverses the filesystem side of a decision
+    fn unapply(&mut self, index: usize, decision: &Decision) -> io::Result<()> {
         if self.dry_run {
             return Ok(());
         }
 
         match decision {
             Decision::Keep => Ok(()),
-            Decision::Trash => {
+            Decision::Trash | Decision::Delete | Decision::Archive => {
                 let file_entry = &self.files[index];
                 let original_path = &file_entry.path;
-                let staged_path = self.get_staged_path(index);
+                let staged_path = self.staged_paths.get(&index).cloned().unwrap_or_default();
 
                 if !staged_path.exists() {
                     return Err(io::Error::new(
@@ -103,58 +369,321 @@ impl DecisionEngine {
                     ));
                 }
 
-                fs::rename(&staged_path, original_path)?;
+                move_back(&staged_path, original_path)?;
+                self.staged_paths.remove(&index);
+                self.journal.remove_entry(&staged_path)?;
+                Ok(())
+            }
+            Decision::Move { .. } | Decision::Rename { .. } => {
+                let original_path = &self.files[index].path;
+                let moved_path = self.moved_paths.get(&index).cloned().unwrap_or_default();
+
+                if !moved_path.exists() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("File not found at its new path: {:?}", moved_path),
+                    ));
+                }
+
+                if original_path.exists() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AlreadyExists,
+                        format!("Original path is occupied: {:?}", original_path),
+                    ));
+                }
+
+                staging::move_file(&moved_path, original_path)?;
+                self.moved_paths.remove(&index);
                 Ok(())
             }
         }
     }
 
     pub fn get_statistics(&self) -> DecisionStatistics {
-        let mut kept = 0;
-        let mut trashed = 0;
+        DecisionStatistics::tally(&self.history, &self.files)
+    }
+
+    /// Hands every staged file over to the system trash.
+    ///
+    /// Each file is first moved back to its original path so the trash records
+    /// where it came from. A file that cannot be trashed is left at its original
+    /// path (or in staging if that path is now occupied) and reported as failed.
+    pub fn commit_trash_decisions(&mut self) -> CommitReport {
+        self.commit_trash_decisions_with(system_trash)
+    }
+
+    /// Like `commit_trash_decisions`, with `trash` standing in for the system trash
+    pub fn commit_trash_decisions_with(
+        &mut self,
+        trash: impl Fn(&Path) -> io::Result<()>,
+    ) -> CommitReport {
+        let mut report = CommitReport::default();
+
+        if self.dry_run {
+            return report;
+        }
+
+        for (index, staged_path) in self.staged_with(&Decision::Trash) {
+            let original_path = &self.files[index].path;
+            match send_to_trash(&staged_path, original_path, &trash) {
+                Ok(()) => report.trashed.push(original_path.clone()),
+                Err(e) => report.failed.push((original_path.clone(), e.to_string())),
+            }
 
-        for (_, decision) in &self.decisions {
-            match decision {
-                Decision::Keep => kept += 1,
-                Decision::Trash => trashed += 1,
+            if !staged_path.exists() {
+                self.staged_paths.remove(&index);
+                self.journal.remove_entry(&staged_path).ok();
             }
         }
 
-        DecisionStatistics {
-            total_files: self.files.len(),
-            kept,
-            trashed,
+        report
+    }
+
+    /// Writes every file staged with `Decision::Archive` into the session
+    /// archive in one go. If the archive cannot be written, the files are moved
+    /// back to their original paths (or left in staging if those are occupied)
+    /// and reported as failed.
+    pub fn commit_archive_decisions(&mut self) -> CommitReport {
+        let mut report = CommitReport::default();
+        let staged = self.staged_with(&Decision::Archive);
+        if self.dry_run || staged.is_empty() {
+            return report;
+        }
+
+        let files: Vec<(&Path, &Path)> = staged
+            .iter()
+            .map(|(index, staged_path)| (staged_path.as_path(), self.files[*index].path.as_path()))
+            .collect();
+        let written = match self.archive.as_mut() {
+            Some(archive) => archive.add_all(&files),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "No archive configured",
+            )),
+        };
+
+        for (index, staged_path) in staged {
+            let original_path = self.files[index].path.clone();
+            match &written {
+                Ok(_) => match fs::remove_file(&staged_path) {
+                    Ok(()) => report.archived.push(original_path),
+                    Err(e) => report.failed.push((original_path, e.to_string())),
+                },
+                Err(e) => {
+                    let reason = match move_back(&staged_path, &original_path) {
+                        Ok(()) => e.to_string(),
+                        Err(restore_error) => format!("{}; {}", e, restore_error),
+                    };
+                    report.failed.push((original_path, reason));
+                }
+            }
+
+            if !staged_path.exists() {
+                self.staged_paths.remove(&index);
+                self.journal.remove_entry(&staged_path).ok();
+            }
         }
+
+        report
     }
 
-    pub fn commit_trash_decisions(&self) -> io::Result<()> {
-        for (index, decision) in &self.decisions {
-            if *decision == Decision::Trash {
-                let staged_path = self.get_staged_path(*index);
-                if staged_path.exists() {
-                    trash::delete(&staged_path).map_err(|e| io::Error::other(e.to_string()))?;
+    /// Permanently deletes every file staged with `Decision::Delete`, optionally
+    /// overwriting its contents first. Failures are left in staging and reported.
+    pub fn commit_delete_decisions(&mut self, overwrite: bool) -> CommitReport {
+        let mut report = CommitReport::default();
+
+        if self.dry_run {
+            return report;
+        }
+
+        for (index, staged_path) in self.staged_with(&Decision::Delete) {
+            let original_path = self.files[index].path.clone();
+            match delete_permanently(&staged_path, overwrite) {
+                Ok(()) => {
+                    self.staged_paths.remove(&index);
+                    self.journal.remove_entry(&staged_path).ok();
+                    report.deleted.push(original_path);
                 }
+                Err(e) => report.failed.push((original_path, e.to_string())),
+            }
+        }
+
+        report
+    }
+
+    /// Files currently staged for permanent deletion
+    pub fn pending_deletes(&self) -> Vec<&FileEntry> {
+        self.staged_with(&Decision::Delete)
+            .into_iter()
+            .map(|(index, _)| &self.files[index])
+            .collect()
+    }
+
+    /// Staged files whose decision in effect is `decision`, in file order
+    fn staged_with(&self, decision: &Decision) -> Vec<(usize, PathBuf)> {
+        let mut staged: Vec<(usize, PathBuf)> = self
+            .history
+            .applied()
+            .iter()
+            .filter(|entry| &entry.decision == decision)
+            .filter_map(|entry| {
+                self.staged_paths
+                    .get(&entry.index)
+                    .map(|path| (entry.index, path.clone()))
+            })
+            .collect();
+        staged.sort();
+        staged.dedup();
+        staged
+    }
+
+    /// Moves every file still sitting in staging back to its original path
+    fn restore_staged(&mut self) {
+        let staged: Vec<(usize, PathBuf)> = self.staged_paths.drain().collect();
+
+        for (index, staged_path) in staged {
+            let original_path = &self.files[index].path;
+            if !original_path.exists() && staging::move_file(&staged_path, original_path).is_ok() {
+                self.journal.remove_entry(&staged_path).ok();
+            } else {
+                self.staged_paths.insert(index, staged_path);
             }
         }
-        Ok(())
     }
 
-    fn get_staged_path(&self, index: usize) -> PathBuf {
-        self.staging_dir.join(format!("file_{}", index))
+    /// Where the file at `index` is currently staged, if it is
+    pub fn staged_path(&self, index: usize) -> Option<&Path> {
+        self.staged_paths.get(&index).map(PathBuf::as_path)
     }
+
+    /// Where the file at `index` was moved or renamed to, if it was
+    pub fn moved_path(&self, index: usize) -> Option<&Path> {
+        self.moved_paths.get(&index).map(PathBuf::as_path)
+    }
+}
+
+/// Picks a path for `name` inside `dir` that does not exist yet, adding
+/// " (1)", " (2)", ... before the extension on collisions
+fn available_path(dir: &Path, name: &str) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    (1..)
+        .map(|n| dir.join(numbered_name(name, n)))
+        .find(|path| !path.exists())
+        .expect("unbounded range always yields a free name")
+}
+
+/// Moves a staged file back to its original path and sends it to the system trash
+///
+/// Restoring first lets the trash record where the file came from. If the trash
+/// backend fails, the file stays at its original path rather than being lost.
+pub(crate) fn send_to_trash(
+    staged_path: &Path,
+    original_path: &Path,
+    trash: impl Fn(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    move_back(staged_path, original_path)?;
+    trash(original_path)
+}
+
+/// Moves a staged file back to its original path, unless something else is there now
+pub(crate) fn move_back(staged_path: &Path, original_path: &Path) -> io::Result<()> {
+    if original_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "Original path is occupied, file kept in staging at {:?}",
+                staged_path
+            ),
+        ));
+    }
+
+    staging::move_file(staged_path, original_path)
+}
+
+/// Sends `path` to the system trash
+pub(crate) fn system_trash(path: &Path) -> io::Result<()> {
+    trash::delete(path).map_err(|e| io::Error::other(e.to_string()))
+}
+
+/// Unlinks a staged file or directory tree, first overwriting file contents
+/// with zeros if asked.
+///
+/// Overwriting only helps on filesystems that rewrite blocks in place; SSDs,
+/// copy-on-write filesystems and snapshots may still keep the old data.
+pub(crate) fn delete_permanently(staged_path: &Path, overwrite: bool) -> io::Result<()> {
+    if fs::symlink_metadata(staged_path)?.is_dir() {
+        if overwrite {
+            overwrite_tree(staged_path)?;
+        }
+        return fs::remove_dir_all(staged_path);
+    }
+
+    if overwrite {
+        overwrite_with_zeros(staged_path)?;
+    }
+    fs::remove_file(staged_path)
+}
+
+/// Overwrites every regular file under `dir`, without following symlinks
+fn overwrite_tree(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            overwrite_tree(&entry.path())?;
+        } else if file_type.is_file() {
+            overwrite_with_zeros(&entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+fn overwrite_with_zeros(path: &Path) -> io::Result<()> {
+    let len = fs::metadata(path)?.len();
+    let mut file = fs::OpenOptions::new().write(true).open(path)?;
+    let zeros = [0u8; 64 * 1024];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()
 }
 
 impl Drop for DecisionEngine {
     fn drop(&mut self) {
-        fs::remove_dir_all(&self.staging_dir).ok();
+        // Anything still staged was never committed, so put it back rather than lose it.
+        // Whatever cannot be restored keeps its journal entry for `fswp recover`.
+        self.restore_staged();
+        for dir in &self.staging_dirs {
+            if *dir != self.staging_dir {
+                staging::remove_empty_staging_dir(dir);
+            }
+        }
+        if self.journal.entries.is_empty() {
+            self.journal.remove().ok();
+            journal::remove_session_dir(&self.staging_dir);
+        }
+        // Only now may `fswp recover` take over what is left
+        self.session_lock.take();
+        if self.journal.entries.is_empty() {
+            // Windows keeps an unlinked lock file around until its handle is closed
+            fs::remove_dir(&self.staging_dir).ok();
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::FileType;
+    use crate::domain::{ArchiveFormat, FileType};
     use chrono::Utc;
+    use std::cell::RefCell;
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -170,6 +699,10 @@ mod tests {
             size: 0,
             modified_date: Utc::now(),
             file_type: FileType::Text,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         }
     }
 
@@ -182,7 +715,7 @@ mod tests {
         let files = vec![create_test_entry_with_path(file_path)];
         let engine = DecisionEngine::new(files);
 
-        assert_eq!(engine.decisions.len(), 0);
+        assert_eq!(engine.history.len(), 0);
     }
 
     #[test]
@@ -196,7 +729,7 @@ mod tests {
 
         let result = engine.record_decision(0, Decision::Keep);
         assert!(result.is_ok());
-        assert_eq!(engine.decisions.len(), 1);
+        assert_eq!(engine.history.len(), 1);
 
         // Keep should not modify the filesystem
         assert!(file_path.exists());
@@ -213,7 +746,7 @@ mod tests {
 
         let result = engine.record_decision(0, Decision::Trash);
         assert!(result.is_ok());
-        assert_eq!(engine.decisions.len(), 1);
+        assert_eq!(engine.history.len(), 1);
 
         // Trash should move file to trash
         assert!(!file_path.exists());
@@ -229,11 +762,11 @@ mod tests {
         let mut engine = DecisionEngine::new(vec![entry]);
 
         engine.record_decision(0, Decision::Keep).unwrap();
-        assert_eq!(engine.decisions.len(), 1);
+        assert_eq!(engine.history.len(), 1);
 
         let result = engine.undo();
         assert!(result.is_ok());
-        assert_eq!(engine.decisions.len(), 0);
+        assert_eq!(engine.history.len(), 0);
 
         // File should still exist after undoing Keep
         assert!(file_path.exists());
@@ -253,12 +786,83 @@ mod tests {
 
         let result = engine.undo();
         assert!(result.is_ok());
-        assert_eq!(engine.decisions.len(), 0);
+        assert_eq!(engine.history.len(), 0);
 
         // File should be restored after undoing Trash
         assert!(file_path.exists());
     }
 
+    #[test]
+    fn test_decision_engine_undo_trash_refuses_occupied_original() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::with_sessions_dir(vec![entry], sessions_dir.path());
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        fs::write(&file_path, b"replacement").unwrap();
+
+        let err = engine.undo().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(engine.history.len(), 1);
+        assert_eq!(fs::read(&file_path).unwrap(), b"replacement");
+
+        let staged_path = engine.staged_path(0).unwrap().to_path_buf();
+        assert_eq!(fs::read(&staged_path).unwrap(), b"content");
+        assert_eq!(engine.journal.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_replace_undecided_keeps_decided_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|name| temp_dir.path().join(name))
+            .collect();
+        let files: Vec<FileEntry> = paths
+            .iter()
+            .map(|path| create_test_entry_with_path(path.clone()))
+            .collect();
+        let mut engine = DecisionEngine::new(files);
+        engine.record_decision(1, Decision::Keep).unwrap();
+        engine.undo().unwrap();
+
+        let replacements: Vec<FileEntry> = ["x.txt", "y.txt", "z.txt"]
+            .iter()
+            .map(|name| create_test_entry_with_path(temp_dir.path().join(name)))
+            .collect();
+        engine.replace_undecided(0, &replacements);
+
+        let names: Vec<&str> = engine.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["a.txt", "b.txt", "z.txt"]);
+    }
+
+    #[test]
+    fn test_decision_engine_jump_to_stops_at_failed_step() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first.txt");
+        let second = temp_dir.path().join("second.txt");
+        fs::write(&first, b"first").unwrap();
+        fs::write(&second, b"second").unwrap();
+
+        let files = vec![
+            create_test_entry_with_path(first.clone()),
+            create_test_entry_with_path(second.clone()),
+        ];
+        let mut engine = DecisionEngine::new(files);
+        engine.record_decision(0, Decision::Trash).unwrap();
+        engine.record_decision(1, Decision::Trash).unwrap();
+        engine.jump_to(0).unwrap();
+        fs::remove_file(&second).unwrap();
+
+        assert!(engine.jump_to(2).is_err());
+        assert_eq!(engine.history.len(), 1);
+        assert!(!first.exists());
+    }
+
     #[test]
     fn test_decision_engine_undo_empty() {
         let temp_dir = TempDir::new().unwrap();
@@ -296,7 +900,7 @@ mod tests {
         engine.record_decision(1, Decision::Trash).unwrap();
         engine.record_decision(2, Decision::Keep).unwrap();
 
-        assert_eq!(engine.decisions.len(), 3);
+        assert_eq!(engine.history.len(), 3);
         assert!(file1.exists());
         assert!(!file2.exists());
         assert!(file3.exists());
@@ -324,19 +928,102 @@ mod tests {
 
         assert!(!file1.exists());
         assert!(!file2.exists());
-        assert_eq!(engine.decisions.len(), 2);
+        assert_eq!(engine.history.len(), 2);
 
         // Undo second trash
         engine.undo().unwrap();
         assert!(!file1.exists());
         assert!(file2.exists());
-        assert_eq!(engine.decisions.len(), 1);
+        assert_eq!(engine.history.len(), 1);
 
         // Undo first trash
         engine.undo().unwrap();
         assert!(file1.exists());
         assert!(file2.exists());
-        assert_eq!(engine.decisions.len(), 0);
+        assert_eq!(engine.history.len(), 0);
+    }
+
+    #[test]
+    fn test_decision_engine_redo_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        engine.undo().unwrap();
+        assert!(file_path.exists());
+
+        let redone = engine.redo().unwrap();
+        assert_eq!(redone.index, 0);
+        assert_eq!(redone.decision, Decision::Trash);
+        assert!(!file_path.exists());
+        assert_eq!(engine.history.len(), 1);
+
+        assert!(engine.redo().is_err());
+    }
+
+    #[test]
+    fn test_decision_engine_revert_single_decision() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let file1 = temp_dir.path().join("file1.txt");
+        let file2 = temp_dir.path().join("file2.txt");
+        fs::write(&file1, b"content1").unwrap();
+        fs::write(&file2, b"content2").unwrap();
+
+        let files = vec![
+            create_test_entry_with_path(file1.clone()),
+            create_test_entry_with_path(file2.clone()),
+        ];
+        let mut engine = DecisionEngine::new(files);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        engine.record_decision(1, Decision::Trash).unwrap();
+
+        // Revert the first decision only; the second stays in effect
+        let reverted = engine.revert(0).unwrap();
+        assert_eq!(reverted.index, 0);
+        assert!(file1.exists());
+        assert!(!file2.exists());
+        assert_eq!(engine.history.len(), 1);
+        assert_eq!(engine.history.applied()[0].index, 1);
+    }
+
+    #[test]
+    fn test_decision_engine_jump_to() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let file1 = temp_dir.path().join("file1.txt");
+        let file2 = temp_dir.path().join("file2.txt");
+        let file3 = temp_dir.path().join("file3.txt");
+        fs::write(&file1, b"content1").unwrap();
+        fs::write(&file2, b"content2").unwrap();
+        fs::write(&file3, b"content3").unwrap();
+
+        let files = vec![
+            create_test_entry_with_path(file1.clone()),
+            create_test_entry_with_path(file2.clone()),
+            create_test_entry_with_path(file3.clone()),
+        ];
+        let mut engine = DecisionEngine::new(files);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        engine.record_decision(1, Decision::Keep).unwrap();
+        engine.record_decision(2, Decision::Trash).unwrap();
+
+        // Jump back to just after the first decision
+        engine.jump_to(1).unwrap();
+        assert_eq!(engine.history.len(), 1);
+        assert!(!file1.exists());
+        assert!(file3.exists());
+
+        // And forward again
+        engine.jump_to(3).unwrap();
+        assert_eq!(engine.history.len(), 3);
+        assert!(!file3.exists());
     }
 
     #[test]
@@ -386,19 +1073,461 @@ mod tests {
     }
 
     #[test]
-    fn test_decision_engine_dry_run_trash() {
+    fn test_decision_engine_move_and_undo() {
         let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("test.txt");
+        let file_path = temp_dir.path().join("receipt.pdf");
+        let dest = temp_dir.path().join("Receipts");
         fs::write(&file_path, b"content").unwrap();
 
         let entry = create_test_entry_with_path(file_path.clone());
         let mut engine = DecisionEngine::new(vec![entry]);
-        engine.set_dry_run(true);
 
-        // In dry-run mode, trash should NOT move the file
+        engine
+            .record_decision(0, Decision::Move { dest: dest.clone() })
+            .unwrap();
+        assert!(!file_path.exists());
+        assert_eq!(engine.moved_path(0).unwrap(), dest.join("receipt.pdf"));
+        assert_eq!(fs::read(dest.join("receipt.pdf")).unwrap(), b"content");
+
+        engine.undo().unwrap();
+        assert_eq!(fs::read(&file_path).unwrap(), b"content");
+        assert!(!dest.join("receipt.pdf").exists());
+        assert!(engine.moved_path(0).is_none());
+    }
+
+    #[test]
+    fn test_decision_engine_move_name_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("photo.jpg");
+        let dest = temp_dir.path().join("Photos");
+        fs::write(&file_path, b"new").unwrap();
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(dest.join("photo.jpg"), b"existing").unwrap();
+        fs::write(dest.join("photo (1).jpg"), b"existing").unwrap();
+
+        let entry = create_test_entry_with_path(file_path);
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine
+            .record_decision(0, Decision::Move { dest: dest.clone() })
+            .unwrap();
+
+        assert_eq!(engine.moved_path(0).unwrap(), dest.join("photo (2).jpg"));
+        assert_eq!(fs::read(dest.join("photo.jpg")).unwrap(), b"existing");
+        assert_eq!(fs::read(dest.join("photo (2).jpg")).unwrap(), b"new");
+    }
+
+    #[test]
+    fn test_decision_engine_move_undo_refuses_occupied_original() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("notes.txt");
+        let dest = temp_dir.path().join("Archive");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine
+            .record_decision(0, Decision::Move { dest: dest.clone() })
+            .unwrap();
+        fs::write(&file_path, b"replacement").unwrap();
+
+        assert!(engine.undo().is_err());
+        assert_eq!(engine.history.len(), 1);
+        assert_eq!(fs::read(&file_path).unwrap(), b"replacement");
+        assert!(dest.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_decision_engine_statistics_per_destination() {
+        let temp_dir = TempDir::new().unwrap();
+        let receipts = temp_dir.path().join("Receipts");
+        let photos = temp_dir.path().join("Photos");
+
+        let mut files = Vec::new();
+        for name in ["a.pdf", "b.pdf", "c.jpg", "d.txt"] {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, b"content").unwrap();
+            files.push(create_test_entry_with_path(path));
+        }
+        let mut engine = DecisionEngine::new(files);
+
+        engine
+            .record_decision(
+                0,
+                Decision::Move {
+                    dest: receipts.clone(),
+                },
+            )
+            .unwrap();
+        engine
+            .record_decision(
+                1,
+                Decision::Move {
+                    dest: receipts.clone(),
+                },
+            )
+            .unwrap();
+        engine
+            .record_decision(
+                2,
+                Decision::Move {
+                    dest: photos.clone(),
+                },
+            )
+            .unwrap();
+        engine.record_decision(3, Decision::Keep).unwrap();
+
+        let stats = engine.get_statistics();
+        assert_eq!(stats.moved, 3);
+        assert_eq!(stats.kept, 1);
+        assert_eq!(stats.decided(), 4);
+        assert_eq!(stats.moved_by_destination[&receipts], 2);
+        assert_eq!(stats.moved_by_destination[&photos], 1);
+    }
+
+    #[test]
+    fn test_decision_engine_rename_and_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("IMG_2231.jpg");
+        let renamed = temp_dir.path().join("beach.jpg");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine
+            .record_decision(
+                0,
+                Decision::Rename {
+                    name: "beach.jpg".to_string(),
+                },
+            )
+            .unwrap();
+        assert!(!file_path.exists());
+        assert_eq!(fs::read(&renamed).unwrap(), b"content");
+        assert_eq!(engine.get_statistics().renamed, 1);
+
+        engine.undo().unwrap();
+        assert!(file_path.exists());
+        assert!(!renamed.exists());
+    }
+
+    #[test]
+    fn test_decision_engine_archive_and_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("export.csv");
+        fs::write(&file_path, b"a,b,c").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+        engine.set_archive(SessionArchive::in_dir(
+            &temp_dir.path().join("archive"),
+            ArchiveFormat::TarGz,
+        ));
+
+        engine.record_decision(0, Decision::Archive).unwrap();
+        assert!(!file_path.exists());
+        // The archive is only written when the session ends
+        assert!(!engine.archive().unwrap().path().exists());
+        assert_eq!(engine.get_statistics().archived, 1);
+
+        engine.undo().unwrap();
+        assert_eq!(fs::read(&file_path).unwrap(), b"a,b,c");
+
+        engine.redo().unwrap();
+        let report = engine.commit_archive_decisions();
+        assert_eq!(report.archived, vec![file_path.clone()]);
+        assert!(report.failed.is_empty());
+        assert!(!file_path.exists());
+        assert!(engine.staged_path(0).is_none());
+
+        let archive = SessionArchive::load(engine.archive().unwrap().path()).unwrap();
+        assert_eq!(archive.manifest().entries[0].name, "export.csv");
+        assert_eq!(archive.manifest().entries[0].original_path, file_path);
+    }
+
+    #[test]
+    fn test_decision_engine_archive_failure_restores_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("export.csv");
+        fs::write(&file_path, b"a,b,c").unwrap();
+        // The archive folder cannot be created where a file is in the way
+        let blocked = temp_dir.path().join("blocked");
+        fs::write(&blocked, b"").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+        engine.set_archive(SessionArchive::in_dir(
+            &blocked.join("archive"),
+            ArchiveFormat::Zip,
+        ));
+
+        engine.record_decision(0, Decision::Archive).unwrap();
+        let report = engine.commit_archive_decisions();
+
+        assert!(report.archived.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(fs::read(&file_path).unwrap(), b"a,b,c");
+    }
+
+    #[test]
+    fn test_decision_engine_archive_refuses_the_archive_itself() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = SessionArchive::in_dir(temp_dir.path(), ArchiveFormat::TarGz);
+        fs::write(archive.path(), b"left over from a crash").unwrap();
+
+        let entry = create_test_entry_with_path(archive.path().to_path_buf());
+        let mut engine = DecisionEngine::new(vec![entry]);
+        engine.set_archive(archive);
+
+        let result = engine.record_decision(0, Decision::Archive);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert!(engine.archive().unwrap().path().exists());
+    }
+
+    #[test]
+    fn test_decision_engine_archive_requires_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("export.csv");
+        fs::write(&file_path, b"a,b,c").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        assert!(engine.record_decision(0, Decision::Archive).is_err());
+        assert!(file_path.exists());
+    }
+
+    #[test]
+    fn test_decision_engine_directory_trash_undo_and_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("old-project");
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::write(project.join("src").join("main.rs"), b"fn main() {}").unwrap();
+
+        let entry = FileEntry::from_dir(&project).unwrap();
+        let mut engine = DecisionEngine::new(vec![entry]);
+        engine.set_archive(SessionArchive::in_dir(
+            &temp_dir.path().join("archive"),
+            ArchiveFormat::TarGz,
+        ));
+
+        let result = engine.record_decision(0, Decision::Archive);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert!(project.exists());
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        assert!(!project.exists());
+        engine.undo().unwrap();
+        assert_eq!(
+            fs::read(project.join("src").join("main.rs")).unwrap(),
+            b"fn main() {}"
+        );
+
+        engine.record_decision(0, Decision::Delete).unwrap();
+        let staged = engine.staged_path(0).unwrap().to_path_buf();
+        let report = engine.commit_delete_decisions(true);
+        assert!(report.failed.is_empty());
+        assert!(!staged.exists());
+        assert!(!project.exists());
+    }
+
+    #[test]
+    fn test_statistics_count_reclaimed_bytes() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("videos");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("take.mov"), vec![0u8; 400]).unwrap();
+        let mut files = vec![FileEntry::from_dir(&folder).unwrap()];
+        for (name, size) in [("a.iso", 300), ("b.iso", 100), ("c.iso", 200)] {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, vec![0u8; size]).unwrap();
+            files.push(FileEntry::from_path(&path).unwrap());
+        }
+
+        let mut engine = DecisionEngine::new(files);
+        engine.record_decision(0, Decision::Keep).unwrap();
+        engine.record_decision(1, Decision::Trash).unwrap();
+        engine.record_decision(2, Decision::Keep).unwrap();
+
+        let stats = engine.get_statistics();
+        assert_eq!(stats.freed_bytes, 300);
+        // The kept folder is left out: its contents are reviewed on their own
+        assert_eq!(stats.decided_bytes, 400);
+        assert_eq!(stats.pending_bytes, 200);
+        assert_eq!(stats.projected_bytes(), 300 + 150);
+
+        engine.undo().unwrap();
+        engine.undo().unwrap();
+        assert_eq!(engine.get_statistics().freed_bytes, 0);
+        assert_eq!(engine.get_statistics().projected_bytes(), 0);
+    }
+
+    #[test]
+    fn test_decision_engine_rename_never_overwrites() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("a.txt");
+        let taken = temp_dir.path().join("b.txt");
+        fs::write(&file_path, b"a").unwrap();
+        fs::write(&taken, b"b").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        let result = engine.record_decision(
+            0,
+            Decision::Rename {
+                name: "b.txt".to_string(),
+            },
+        );
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert!(engine.history.is_empty());
+        assert_eq!(fs::read(&taken).unwrap(), b"b");
+        assert!(file_path.exists());
+    }
+
+    #[test]
+    fn test_decision_engine_rename_never_replaces_empty_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("photos");
+        let taken = temp_dir.path().join("old");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("a.jpg"), b"a").unwrap();
+        fs::create_dir(&taken).unwrap();
+
+        let entry = FileEntry::from_dir(&folder).unwrap();
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        let result = engine.record_decision(
+            0,
+            Decision::Rename {
+                name: "old".to_string(),
+            },
+        );
+
+        let err = result.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(err.to_string().contains("already exists"));
+        assert!(folder.join("a.jpg").exists());
+        assert!(taken.exists());
+    }
+
+    #[test]
+    fn test_decision_engine_rename_rejects_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("a.txt");
+        fs::write(&file_path, b"a").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        let result = engine.record_decision(
+            0,
+            Decision::Rename {
+                name: "../escape.txt".to_string(),
+            },
+        );
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert!(file_path.exists());
+    }
+
+    #[test]
+    fn test_decision_engine_delete_is_staged_until_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("secret.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine.record_decision(0, Decision::Delete).unwrap();
+        assert!(!file_path.exists());
+        assert_eq!(engine.pending_deletes().len(), 1);
+        assert_eq!(engine.get_statistics().deleted, 1);
+
+        // Undo works like trash while the file is still staged
+        engine.undo().unwrap();
+        assert_eq!(fs::read(&file_path).unwrap(), b"content");
+        assert!(engine.pending_deletes().is_empty());
+    }
+
+    #[test]
+    fn test_decision_engine_commit_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let deleted = temp_dir.path().join("delete.txt");
+        let trashed = temp_dir.path().join("trash.txt");
+        fs::write(&deleted, b"content").unwrap();
+        fs::write(&trashed, b"content").unwrap();
+
+        let files = vec![
+            create_test_entry_with_path(deleted.clone()),
+            create_test_entry_with_path(trashed.clone()),
+        ];
+        let mut engine = DecisionEngine::new(files);
+
+        engine.record_decision(0, Decision::Delete).unwrap();
+        engine.record_decision(1, Decision::Trash).unwrap();
+        let staged = engine.staged_path(0).unwrap().to_path_buf();
+
+        let report = engine.commit_delete_decisions(true);
+
+        assert_eq!(report.deleted, vec![deleted.clone()]);
+        assert!(report.failed.is_empty());
+        assert!(!staged.exists());
+        assert!(!deleted.exists());
+        // Trashed files are left for commit_trash_decisions
+        assert!(engine.staged_path(1).is_some());
+        assert!(engine.pending_deletes().is_empty());
+    }
+
+    #[test]
+    fn test_delete_permanently_overwrites_before_unlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("secret.txt");
+        fs::write(&file_path, b"secret").unwrap();
+
+        // A second hard link shows what happened to the contents
+        let link = temp_dir.path().join("link.txt");
+        fs::hard_link(&file_path, &link).unwrap();
+
+        delete_permanently(&file_path, true).unwrap();
+
+        assert!(!file_path.exists());
+        assert_eq!(fs::read(&link).unwrap(), vec![0u8; 6]);
+    }
+
+    #[test]
+    fn test_decision_engine_drop_restores_uncommitted_deletes() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("keep-me.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine.record_decision(0, Decision::Delete).unwrap();
+        drop(engine);
+
+        assert_eq!(fs::read(&file_path).unwrap(), b"content");
+    }
+
+    #[test]
+    fn test_decision_engine_dry_run_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+        engine.set_dry_run(true);
+
+        // In dry-run mode, trash should NOT move the file
         let result = engine.record_decision(0, Decision::Trash);
         assert!(result.is_ok());
-        assert_eq!(engine.decisions.len(), 1);
+        assert_eq!(engine.history.len(), 1);
 
         // File should still exist (not moved)
         assert!(file_path.exists());
@@ -415,16 +1544,197 @@ mod tests {
         engine.set_dry_run(true);
 
         engine.record_decision(0, Decision::Trash).unwrap();
-        assert_eq!(engine.decisions.len(), 1);
+        assert_eq!(engine.history.len(), 1);
 
         let result = engine.undo();
         assert!(result.is_ok());
-        assert_eq!(engine.decisions.len(), 0);
+        assert_eq!(engine.history.len(), 0);
 
         // File should still exist
         assert!(file_path.exists());
     }
 
+    #[test]
+    fn test_decision_engine_commit_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        let staged_path = engine.staged_path(0).unwrap().to_path_buf();
+        let trashed = RefCell::new(Vec::new());
+        let report = engine.commit_trash_decisions_with(|path| {
+            // The file is back at its original path when the trash gets it
+            assert_eq!(fs::read(path)?, b"content");
+            trashed.borrow_mut().push(path.to_path_buf());
+            fs::remove_file(path)
+        });
+
+        assert_eq!(report.trashed, vec![file_path.clone()]);
+        assert!(report.failed.is_empty());
+        assert_eq!(trashed.into_inner(), vec![file_path.clone()]);
+        assert!(!staged_path.exists());
+        assert!(!file_path.exists());
+        assert!(engine.staged_path(0).is_none());
+    }
+
+    #[test]
+    fn test_decision_engine_commit_trash_failure_restores_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        let staged_path = engine.staged_path(0).unwrap().to_path_buf();
+        let report =
+            engine.commit_trash_decisions_with(|_| Err(io::Error::other("trash is unavailable")));
+
+        // A trash backend failure must leave the file restored, never deleted
+        assert!(report.trashed.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.failed[0].1.contains("trash is unavailable"));
+        assert!(!staged_path.exists());
+        assert_eq!(fs::read(&file_path).unwrap(), b"content");
+    }
+
+    #[test]
+    fn test_decision_engine_commit_skips_occupied_original_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        fs::write(&file_path, b"new file").unwrap();
+
+        let report = engine.commit_trash_decisions();
+
+        assert!(report.trashed.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(fs::read(&file_path).unwrap(), b"new file");
+        assert!(engine.staged_path(0).unwrap().exists());
+    }
+
+    #[test]
+    fn test_decision_engine_commit_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+        engine.set_dry_run(true);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        let report = engine.commit_trash_decisions();
+
+        assert!(report.trashed.is_empty());
+        assert!(report.failed.is_empty());
+        assert!(file_path.exists());
+    }
+
+    #[test]
+    fn test_decision_engine_drop_restores_uncommitted() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::new(vec![entry]);
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        assert!(!file_path.exists());
+
+        drop(engine);
+
+        assert!(file_path.exists());
+        assert_eq!(fs::read(&file_path).unwrap(), b"content");
+    }
+
+    #[test]
+    fn test_decision_engine_journals_staged_files() {
+        let sessions = TempDir::new().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, b"content").unwrap();
+
+        let entry = create_test_entry_with_path(file_path.clone());
+        let mut engine = DecisionEngine::with_sessions_dir(vec![entry], sessions.path());
+
+        engine.record_decision(0, Decision::Trash).unwrap();
+        let journal = Journal::load(&engine.staging_dir).unwrap();
+        assert_eq!(journal.entries.len(), 1);
+        assert_eq!(journal.entries[0].original_path, file_path);
+        assert_eq!(
+            journal.entries[0].staged_path,
+            engine.staged_path(0).unwrap()
+        );
+
+        // A live session is never reported as orphaned
+        assert!(journal::find_orphaned_sessions(sessions.path())
+            .unwrap()
+            .is_empty());
+
+        engine.undo().unwrap();
+        let journal = Journal::load(&engine.staging_dir).unwrap();
+        assert!(journal.entries.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths: Vec<_> = ["a.txt", "b.txt", "c.txt", "d.txt"]
+            .iter()
+            .map(|name| temp_dir.path().join(name))
+            .collect();
+        for path in &paths {
+            fs::write(path, b"same").unwrap();
+        }
+
+        let files = paths.iter().cloned().map(create_test_entry_with_path);
+        let mut engine = DecisionEngine::new(files.collect());
+        // Already kept by hand before resolving the group
+        engine.record_decision(3, Decision::Keep).unwrap();
+
+        engine.resolve_duplicates(0..4, 1, Decision::Trash).unwrap();
+
+        assert!(!paths[0].exists());
+        assert!(paths[1].exists());
+        assert!(!paths[2].exists());
+        assert!(paths[3].exists());
+        let stats = engine.get_statistics();
+        assert_eq!((stats.kept, stats.trashed), (2, 2));
+    }
+
+    #[test]
+    fn test_resolve_duplicates_rolls_back_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept = temp_dir.path().join("a.txt");
+        let gone = temp_dir.path().join("b.txt");
+        fs::write(&kept, b"same").unwrap();
+
+        let files = vec![
+            create_test_entry_with_path(kept.clone()),
+            create_test_entry_with_path(gone),
+        ];
+        let mut engine = DecisionEngine::new(files);
+
+        let err = engine
+            .resolve_duplicates(0..2, 0, Decision::Trash)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(engine.history.is_empty());
+        assert!(kept.exists());
+    }
+
     #[test]
     fn test_decision_engine_is_dry_run() {
         let engine = DecisionEngine::new(vec![]);
@@ -1,14 +1,40 @@
-use super::{FileEntry, FileType};
+use super::staging::STAGING_DIR_NAME;
+use super::{ExtensionTypes, FileEntry, FileType, KeepList};
+use chrono::{DateTime, Utc};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+/// Ignore files honoured in every directory, lowest precedence first
+pub const IGNORE_FILE_NAMES: [&str; 3] = [".gitignore", ".ignore", ".fswpignore"];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortBy {
     Date,
+    /// File name, with runs of digits compared as numbers
     Name,
     Size,
     Type,
+    /// Lowercased extension; files without one come first
+    Extension,
+    /// Path from the reviewed directory, compared like names
+    Path,
+    /// Last access, or last modification where that is unknown
+    Accessed,
+    /// Creation, or last modification where that is unknown
+    Created,
+    /// A shuffle that is the same every time for the same seed
+    Random {
+        seed: u64,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -17,8 +43,34 @@ pub struct DiscoveryOptions {
     pub show_hidden: bool,
     pub min_size: Option<u64>,
     pub max_size: Option<u64>,
-    pub sort_by: SortBy,
+    /// Only files last modified before this
+    pub modified_before: Option<DateTime<Utc>>,
+    /// Only files last modified after this
+    pub modified_after: Option<DateTime<Utc>>,
+    /// Only files last accessed before this, or modified before it when the
+    /// access time is unknown
+    pub accessed_before: Option<DateTime<Utc>>,
+    /// Sort keys, each breaking ties left by the ones before it
+    pub sort_by: Vec<SortBy>,
     pub reverse: bool,
+    /// Descend into subdirectories
+    pub recursive: bool,
+    /// How many levels below the reviewed directory to descend when recursive (unlimited if None)
+    pub max_depth: Option<usize>,
+    /// Yield subdirectories as entries of their own instead of descending into them
+    pub directories: bool,
+    /// Skip files and directories matched by .gitignore, .ignore and .fswpignore
+    pub respect_ignore_files: bool,
+    /// Only files matching one of these globs
+    pub include: Vec<String>,
+    /// Leave out files matching any of these globs
+    pub exclude: Vec<String>,
+    /// Only files whose name matches this regular expression
+    pub name_regex: Option<String>,
+    /// Files kept in earlier runs, left out unless they changed since
+    pub keep_list: Option<KeepList>,
+    /// Extension mappings from the user configuration
+    pub extension_types: ExtensionTypes,
 }
 
 impl Default for DiscoveryOptions {
@@ -28,9 +80,71 @@ impl Default for DiscoveryOptions {
             show_hidden: false,
             min_size: None,
             max_size: None,
-            sort_by: SortBy::Date,
+            modified_before: None,
+            modified_after: None,
+            accessed_before: None,
+            sort_by: vec![SortBy::Date],
             reverse: false,
+            recursive: false,
+            max_depth: None,
+            directories: false,
+            respect_ignore_files: true,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            name_regex: None,
+            keep_list: None,
+            extension_types: ExtensionTypes::default(),
+        }
+    }
+}
+
+impl DiscoveryOptions {
+    /// Whether `file_entry` passes the type, size, age and keep-list filters
+    pub fn admits(&self, file_entry: &FileEntry) -> bool {
+        if let Some(ref type_filters) = self.file_types {
+            if !type_filters.contains(&file_entry.file_type) {
+                return false;
+            }
+        }
+
+        if let Some(min_size) = self.min_size {
+            if file_entry.size < min_size {
+                return false;
+            }
+        }
+
+        if let Some(max_size) = self.max_size {
+            if file_entry.size > max_size {
+                return false;
+            }
+        }
+
+        if let Some(before) = self.modified_before {
+            if file_entry.modified_date >= before {
+                return false;
+            }
+        }
+
+        if let Some(after) = self.modified_after {
+            if file_entry.modified_date <= after {
+                return false;
+            }
+        }
+
+        if let Some(before) = self.accessed_before {
+            let accessed = file_entry.accessed_date.unwrap_or(file_entry.modified_date);
+            if accessed >= before {
+                return false;
+            }
+        }
+
+        if let Some(ref keep_list) = self.keep_list {
+            if keep_list.contains(file_entry) {
+                return false;
+            }
         }
+
+        true
     }
 }
 
@@ -42,72 +156,570 @@ pub fn discover_files_with_options(
     dir_path: &Path,
     options: &DiscoveryOptions,
 ) -> io::Result<Vec<FileEntry>> {
-    let entries = fs::read_dir(dir_path)?;
     let mut files = Vec::new();
+    let mut walk = Walk {
+        root: dir_path,
+        options,
+        names: NameFilter::new(options)?,
+        visited: HashSet::new(),
+        ignores: Vec::new(),
+        parent_ignores: ParentIgnores::load(dir_path, options.respect_ignore_files),
+        found: |file| {
+            files.push(file);
+            true
+        },
+        stopped: false,
+    };
+    walk.visit_dir(dir_path, 0)?;
+
+    files.sort_by(|a, b| compare_files(&options.sort_by, options.reverse, a, b));
+    Ok(files)
+}
+
+/// Orders two files by the keys in `sort_by`, or the other way round when `reverse`
+pub fn compare_files(sort_by: &[SortBy], reverse: bool, a: &FileEntry, b: &FileEntry) -> Ordering {
+    let (a, b) = if reverse { (b, a) } else { (a, b) };
+    sort_by
+        .iter()
+        .map(|&key| compare_by(key, a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
 
-    for entry_result in entries {
-        let entry = entry_result?;
-        let path = entry.path();
+fn compare_by(sort_by: SortBy, a: &FileEntry, b: &FileEntry) -> Ordering {
+    match sort_by {
+        SortBy::Date => a.modified_date.cmp(&b.modified_date),
+        SortBy::Name => natural_cmp(&a.name, &b.name),
+        SortBy::Size => a.size.cmp(&b.size),
+        SortBy::Type => a.file_type.cmp(&b.file_type),
+        SortBy::Extension => extension(a).cmp(&extension(b)),
+        SortBy::Path => compare_paths(&a.path, &b.path),
+        SortBy::Accessed => a
+            .accessed_date
+            .unwrap_or(a.modified_date)
+            .cmp(&b.accessed_date.unwrap_or(b.modified_date)),
+        SortBy::Created => a
+            .created_date
+            .unwrap_or(a.modified_date)
+            .cmp(&b.created_date.unwrap_or(b.modified_date)),
+        SortBy::Random { seed } => shuffle_key(seed, &a.path).cmp(&shuffle_key(seed, &b.path)),
+    }
+}
 
-        if path.is_dir() {
-            continue;
+/// Compares paths folder by folder, so a folder's files stay together
+fn compare_paths(a: &Path, b: &Path) -> Ordering {
+    let mut a_parts = a.components();
+    let mut b_parts = b.components();
+    loop {
+        let ordering = match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => natural_cmp(
+                &x.as_os_str().to_string_lossy(),
+                &y.as_os_str().to_string_lossy(),
+            ),
+        };
+        if ordering.is_ne() {
+            return ordering;
         }
+    }
+}
 
-        if !options.show_hidden {
-            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                if filename.starts_with('.') {
-                    continue;
-                }
+fn extension(file: &FileEntry) -> String {
+    file.path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Compares names the way people read them: runs of digits by their value, so
+/// `file9` comes before `file10`, and letters without regard to case. Names
+/// that only differ in case or leading zeros still get a fixed order.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_chars, mut b_chars) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let ordering = match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_digits(&mut a_chars);
+                let y = take_digits(&mut b_chars);
+                let (x_value, y_value) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                x_value
+                    .len()
+                    .cmp(&y_value.len())
+                    .then_with(|| x_value.cmp(y_value))
+            }
+            (Some(&x), Some(&y)) => {
+                a_chars.next();
+                b_chars.next();
+                x.to_lowercase().cmp(y.to_lowercase())
             }
+        };
+        if ordering.is_ne() {
+            return ordering;
         }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits
+}
+
+/// Where `path` lands in the shuffle for `seed`: FNV-1a over the path,
+/// mixed with splitmix64 so similar paths end up far apart
+fn shuffle_key(seed: u64, path: &Path) -> u64 {
+    let hash = path
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325 ^ seed, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    let mut z = hash.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// A discovery walking the directory on a background thread, handing over
+/// files as it finds them. Dropping it stops the walk.
+#[derive(Debug)]
+pub struct Discovery {
+    receiver: mpsc::Receiver<Found>,
+    sort_by: Vec<SortBy>,
+    reverse: bool,
+    /// Files handed over so far
+    found: usize,
+    finished: bool,
+    error: Option<io::Error>,
+}
+
+/// Messages from the discovery thread
+#[derive(Debug)]
+enum Found {
+    File(FileEntry),
+    Done(io::Result<()>),
+}
+
+impl Discovery {
+    /// Starts discovering files in `dir_path`. Invalid name filters are
+    /// reported right away; a directory that cannot be read, once finished.
+    pub fn start(dir_path: &Path, options: DiscoveryOptions) -> io::Result<Self> {
+        Self::start_all(&[dir_path.to_path_buf()], options)
+    }
+
+    /// Like `start`, walking each of `roots` in turn. A root that cannot be
+    /// read does not stop the others; the first such error is reported.
+    pub fn start_all(roots: &[PathBuf], options: DiscoveryOptions) -> io::Result<Self> {
+        let names = NameFilter::new(&options)?;
+        let (sender, receiver) = mpsc::channel();
+        let (sort_by, reverse) = (options.sort_by.clone(), options.reverse);
+        let roots = roots.to_vec();
 
-        if let Ok(file_entry) = FileEntry::from_path(&path) {
-            if let Some(ref type_filters) = options.file_types {
-                if !type_filters.contains(&file_entry.file_type) {
-                    continue;
+        thread::spawn(move || {
+            let mut result = Ok(());
+            for root in &roots {
+                let mut walk = Walk {
+                    root,
+                    options: &options,
+                    names: names.clone(),
+                    visited: HashSet::new(),
+                    ignores: Vec::new(),
+                    parent_ignores: ParentIgnores::load(root, options.respect_ignore_files),
+                    // Nobody is listening once the receiver is dropped
+                    found: |file| sender.send(Found::File(file)).is_ok(),
+                    stopped: false,
+                };
+                let walked = walk.visit_dir(root, 0);
+                if walk.stopped {
+                    return;
+                }
+                if let (Err(e), Ok(())) = (walked, &result) {
+                    result = Err(e);
                 }
             }
+            sender.send(Found::Done(result)).ok();
+        });
+
+        Ok(Self {
+            receiver,
+            sort_by,
+            reverse,
+            found: 0,
+            finished: false,
+            error: None,
+        })
+    }
+
+    /// Files found since the last call, sorted, without waiting for more
+    pub fn take(&mut self) -> Vec<FileEntry> {
+        let mut files = Vec::new();
+        while let Ok(found) = self.receiver.try_recv() {
+            self.receive(found, &mut files);
+        }
+        self.sort(files)
+    }
+
+    /// Like `take`, but waits for at least one file unless the walk is over
+    pub fn take_waiting(&mut self) -> Vec<FileEntry> {
+        let mut files = Vec::new();
+        while files.is_empty() && !self.finished {
+            match self.receiver.recv() {
+                Ok(found) => self.receive(found, &mut files),
+                Err(_) => self.finished = true,
+            }
+        }
+        files.extend(self.take());
+        self.sort(files)
+    }
+
+    /// Waits for the walk to finish and returns every file left, sorted.
+    /// `progress` is told how many files were found so far as they come in.
+    pub fn wait(mut self, mut progress: impl FnMut(usize)) -> io::Result<Vec<FileEntry>> {
+        let mut files = Vec::new();
+        while !self.finished {
+            files.extend(self.take_waiting());
+            progress(self.found);
+        }
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(self.sort(files)),
+        }
+    }
+
+    /// Number of files found so far
+    pub fn found(&self) -> usize {
+        self.found
+    }
+
+    /// Whether the walk is over and every file was handed over
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The error that ended the walk early, if any
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Orders two files the way the discovery was asked to sort them
+    pub fn compare(&self, a: &FileEntry, b: &FileEntry) -> Ordering {
+        compare_files(&self.sort_by, self.reverse, a, b)
+    }
+
+    fn receive(&mut self, found: Found, files: &mut Vec<FileEntry>) {
+        match found {
+            Found::File(file) => {
+                self.found += 1;
+                files.push(file);
+            }
+            Found::Done(result) => {
+                self.finished = true;
+                self.error = result.err();
+            }
+        }
+    }
+
+    fn sort(&self, mut files: Vec<FileEntry>) -> Vec<FileEntry> {
+        files.sort_by(|a, b| self.compare(a, b));
+        files
+    }
+}
+
+/// State carried through one discovery walk
+struct Walk<'a, F: FnMut(FileEntry) -> bool> {
+    root: &'a Path,
+    options: &'a DiscoveryOptions,
+    names: NameFilter,
+    /// Canonical paths of directories already read
+    visited: HashSet<PathBuf>,
+    /// Ignore rules of the directories above the one being read
+    ignores: Vec<Gitignore>,
+    /// Ignore rules of the repository directories above the reviewed one
+    parent_ignores: ParentIgnores,
+    /// Receives each matching file, and returns false to stop the walk
+    found: F,
+    stopped: bool,
+}
+
+impl<F: FnMut(FileEntry) -> bool> Walk<'_, F> {
+    /// Collects matching files in `dir`, descending into subdirectories when recursive.
+    /// `depth` is how many levels `dir` is below the reviewed directory.
+    fn visit_dir(&mut self, dir: &Path, depth: usize) -> io::Result<()> {
+        // Symlinks can lead back into a directory already read; read each one once
+        if let Ok(canonical) = dir.canonicalize() {
+            if !self.visited.insert(canonical) {
+                return Ok(());
+            }
+        }
+
+        let entries = fs::read_dir(dir)?;
+
+        let rules = if self.options.respect_ignore_files {
+            load_ignore_rules(dir)
+        } else {
+            None
+        };
+        let has_rules = rules.is_some();
+        self.ignores.extend(rules);
+
+        let result = self.visit_entries(entries, depth);
+
+        if has_rules {
+            self.ignores.pop();
+        }
+        result
+    }
+
+    /// Handles the entries of one directory for `visit_dir`
+    fn visit_entries(&mut self, entries: fs::ReadDir, depth: usize) -> io::Result<()> {
+        let options = self.options;
+
+        for entry_result in entries {
+            if self.stopped {
+                break;
+            }
+            let entry = entry_result?;
+            let path = entry.path();
+            let file_name = entry.file_name();
+            let is_dir = path.is_dir();
+
+            if !options.show_hidden && file_name.to_string_lossy().starts_with('.') {
+                continue;
+            }
+
+            let relative = path.strip_prefix(self.root).unwrap_or(&path);
+            let verdict = ignore_verdict(&self.ignores, &path, is_dir)
+                .or_else(|| self.parent_ignores.verdict(relative, is_dir));
+            if verdict == Some(true) {
+                continue;
+            }
 
-            if let Some(min_size) = options.min_size {
-                if file_entry.size < min_size {
-                    continue;
+            if is_dir && !options.directories {
+                let within_depth = options.max_depth.is_none_or(|max| depth < max);
+                if options.recursive && within_depth && file_name != STAGING_DIR_NAME {
+                    // An unreadable subdirectory should not end discovery of the rest
+                    self.visit_dir(&path, depth + 1).ok();
                 }
+                continue;
+            }
+            if is_dir && file_name == STAGING_DIR_NAME {
+                continue;
+            }
+
+            if !self.names.matches(relative) {
+                continue;
             }
 
-            if let Some(max_size) = options.max_size {
-                if file_entry.size > max_size {
-                    continue;
+            let file_entry = if is_dir {
+                FileEntry::from_dir(&path)
+            } else {
+                FileEntry::from_path_with_types(&path, &options.extension_types)
+            };
+            if let Ok(mut file_entry) = file_entry {
+                file_entry.root = Some(self.root.to_path_buf());
+                if options.admits(&file_entry) {
+                    self.stopped = !(self.found)(file_entry);
                 }
             }
+        }
+
+        Ok(())
+    }
+}
+
+/// The --include, --exclude and --regex filters, compiled
+#[derive(Clone)]
+pub(super) struct NameFilter {
+    include: Option<GlobFilter>,
+    exclude: Option<GlobFilter>,
+    regex: Option<Regex>,
+}
+
+impl NameFilter {
+    pub(super) fn new(options: &DiscoveryOptions) -> io::Result<Self> {
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+
+        Ok(Self {
+            include: GlobFilter::new(&options.include).map_err(invalid)?,
+            exclude: GlobFilter::new(&options.exclude).map_err(invalid)?,
+            regex: options
+                .name_regex
+                .as_deref()
+                .map(compile_regex)
+                .transpose()
+                .map_err(invalid)?,
+        })
+    }
+
+    /// Whether a file at `relative` (to the reviewed directory) passes every filter
+    pub(super) fn matches(&self, relative: &Path) -> bool {
+        let name = relative
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default();
+
+        self.include.as_ref().is_none_or(|g| g.matches(relative))
+            && !self.exclude.as_ref().is_some_and(|g| g.matches(relative))
+            && self.regex.as_ref().is_none_or(|r| r.is_match(&name))
+    }
+}
+
+/// Glob patterns split like gitignore does: patterns without a `/` match the
+/// file name at any depth, the others match the path from the reviewed directory
+#[derive(Clone)]
+struct GlobFilter {
+    names: GlobSet,
+    paths: GlobSet,
+}
 
-            files.push(file_entry);
+impl GlobFilter {
+    /// Compiles `patterns`, or returns None when there are none
+    fn new(patterns: &[String]) -> Result<Option<Self>, String> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = compile_glob(pattern)?;
+            if pattern.contains('/') {
+                paths.add(glob);
+            } else {
+                names.add(glob);
+            }
         }
+
+        Ok(Some(Self {
+            names: names.build().map_err(|e| e.to_string())?,
+            paths: paths.build().map_err(|e| e.to_string())?,
+        }))
     }
 
-    files.sort_by(|a, b| match options.sort_by {
-        SortBy::Date => a.modified_date.cmp(&b.modified_date),
-        SortBy::Name => a.name.cmp(&b.name),
-        SortBy::Size => a.size.cmp(&b.size),
-        SortBy::Type => {
-            let a_type = format!("{:?}", a.file_type);
-            let b_type = format!("{:?}", b.file_type);
-            a_type.cmp(&b_type)
+    fn matches(&self, relative: &Path) -> bool {
+        relative
+            .file_name()
+            .is_some_and(|name| self.names.is_match(name))
+            || self.paths.is_match(relative)
+    }
+}
+
+/// Compiles a --include/--exclude pattern, with an error message fit for the user
+pub fn compile_glob(pattern: &str) -> Result<Glob, String> {
+    GlobBuilder::new(pattern.trim_start_matches('/'))
+        .literal_separator(true)
+        .build()
+        .map_err(|e| format!("Invalid glob '{}': {}", pattern, e.kind()))
+}
+
+/// Compiles a --regex pattern, with an error message fit for the user
+pub fn compile_regex(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("Invalid regex '{}': {}", pattern, e))
+}
+
+/// Reads the ignore files in `dir` into one matcher, or None if it has none.
+/// Later files take precedence, so .fswpignore can re-include what .gitignore hides.
+fn load_ignore_rules(dir: &Path) -> Option<Gitignore> {
+    let mut builder = GitignoreBuilder::new(dir);
+    let mut found = false;
+
+    for name in IGNORE_FILE_NAMES {
+        let path = dir.join(name);
+        if path.is_file() {
+            // Invalid lines are skipped; the rest of the file still applies
+            builder.add(path);
+            found = true;
         }
-    });
+    }
 
-    if options.reverse {
-        files.reverse();
+    if !found {
+        return None;
     }
+    builder.build().ok()
+}
 
-    Ok(files)
+/// What the closest ignore rules that mention `path` say: Some(true) if they
+/// exclude it, Some(false) if they re-include it, None if none mention it
+fn ignore_verdict(ignores: &[Gitignore], path: &Path, is_dir: bool) -> Option<bool> {
+    ignores
+        .iter()
+        .rev()
+        .find_map(|rules| match rules.matched(path, is_dir) {
+            Match::Ignore(_) => Some(true),
+            Match::Whitelist(_) => Some(false),
+            Match::None => None,
+        })
+}
+
+/// Ignore rules from the directories between a reviewed directory and the
+/// root of the repository it sits in, so reviewing a subdirectory still
+/// honours the repository's .gitignore and .git/info/exclude
+#[derive(Debug, Default)]
+struct ParentIgnores {
+    /// Canonical reviewed directory, which the rules' paths are relative to
+    root: PathBuf,
+    /// Outermost directory first, so later rules take precedence
+    rules: Vec<Gitignore>,
+}
+
+impl ParentIgnores {
+    /// Loads the rules above `root`, or none outside a repository or when
+    /// ignore files are not respected
+    fn load(root: &Path, respect_ignore_files: bool) -> Self {
+        if !respect_ignore_files {
+            return Self::default();
+        }
+        let Ok(root) = root.canonicalize() else {
+            return Self::default();
+        };
+        let Some(repository) = root.ancestors().find(|dir| dir.join(".git").exists()) else {
+            return Self::default();
+        };
+
+        let mut rules = Vec::new();
+        let exclude = repository.join(".git").join("info").join("exclude");
+        if exclude.is_file() {
+            let mut builder = GitignoreBuilder::new(repository);
+            builder.add(exclude);
+            rules.extend(builder.build().ok());
+        }
+
+        // The reviewed directory's own files are read by the walk itself
+        let mut parents: Vec<&Path> = root
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(repository))
+            .collect();
+        parents.reverse();
+        rules.extend(parents.into_iter().filter_map(load_ignore_rules));
+
+        Self { root, rules }
+    }
+
+    /// The verdict on `relative`, a path relative to the reviewed directory
+    fn verdict(&self, relative: &Path, is_dir: bool) -> Option<bool> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        ignore_verdict(&self.rules, &self.root.join(relative), is_dir)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs::FileTimes;
     use std::path::Path;
     use std::thread;
-    use std::time::Duration;
+    use std::time::{Duration, SystemTime};
     use tempfile::TempDir;
 
     #[test]
@@ -198,7 +810,7 @@ mod tests {
         let txt_file = temp_dir.path().join("file.txt");
         let png_file = temp_dir.path().join("image.png");
         fs::write(&txt_file, "content").unwrap();
-        fs::write(&png_file, "content").unwrap();
+        fs::write(&png_file, b"\x89PNG\r\n\x1a\n").unwrap();
 
         let options = DiscoveryOptions {
             file_types: Some(vec![FileType::Text]),
@@ -230,6 +842,58 @@ mod tests {
         assert_eq!(files[0].name, "large.txt");
     }
 
+    /// Creates `name`, last accessed and modified the given numbers of days ago
+    fn write_aged(dir: &Path, name: &str, accessed_days: u64, modified_days: u64) {
+        let days_ago = |days: u64| SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+        let file = fs::File::create(dir.join(name)).unwrap();
+        file.set_times(
+            FileTimes::new()
+                .set_accessed(days_ago(accessed_days))
+                .set_modified(days_ago(modified_days)),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_discover_with_age_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        write_aged(temp_dir.path(), "ancient.txt", 400, 400);
+        write_aged(temp_dir.path(), "old.txt", 100, 100);
+        write_aged(temp_dir.path(), "recent.txt", 3, 3);
+        let days_ago = |days: i64| Utc::now() - chrono::Duration::days(days);
+
+        let options = DiscoveryOptions {
+            modified_before: Some(days_ago(90)),
+            ..Default::default()
+        };
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+        assert_eq!(sorted_names(&files), vec!["ancient.txt", "old.txt"]);
+
+        let options = DiscoveryOptions {
+            modified_before: Some(days_ago(90)),
+            modified_after: Some(days_ago(365)),
+            ..Default::default()
+        };
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+        assert_eq!(sorted_names(&files), vec!["old.txt"]);
+    }
+
+    #[test]
+    fn test_discover_not_accessed_since() {
+        let temp_dir = TempDir::new().unwrap();
+        // Old, but opened last week
+        write_aged(temp_dir.path(), "in-use.txt", 7, 400);
+        write_aged(temp_dir.path(), "forgotten.txt", 200, 400);
+
+        let options = DiscoveryOptions {
+            accessed_before: Some(Utc::now() - chrono::Duration::days(180)),
+            ..Default::default()
+        };
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        assert_eq!(sorted_names(&files), vec!["forgotten.txt"]);
+    }
+
     #[test]
     fn test_discover_with_show_hidden() {
         let temp_dir = TempDir::new().unwrap();
@@ -257,7 +921,7 @@ mod tests {
         fs::write(&file_a, "content").unwrap();
 
         let options = DiscoveryOptions {
-            sort_by: SortBy::Name,
+            sort_by: vec![SortBy::Name],
             ..Default::default()
         };
 
@@ -276,7 +940,7 @@ mod tests {
         fs::write(&large, "hello world").unwrap();
 
         let options = DiscoveryOptions {
-            sort_by: SortBy::Size,
+            sort_by: vec![SortBy::Size],
             ..Default::default()
         };
 
@@ -286,6 +950,231 @@ mod tests {
         assert_eq!(files[1].name, "large.txt");
     }
 
+    #[test]
+    fn test_discover_sorted_by_type() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.zip"), b"PK\x03\x04\x14\x00").unwrap();
+        fs::write(temp_dir.path().join("b.mp3"), b"ID3\x04\x00\x00").unwrap();
+        fs::write(temp_dir.path().join("c.md"), "# notes").unwrap();
+
+        let options = DiscoveryOptions {
+            sort_by: vec![SortBy::Type],
+            ..Default::default()
+        };
+
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        let types: Vec<_> = files.iter().map(|f| f.file_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![FileType::Text, FileType::Audio, FileType::Archive]
+        );
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        let mut names = vec![
+            "file10.txt",
+            "file9.txt",
+            "File2.txt",
+            "file1.txt",
+            "file01.txt",
+        ];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            vec![
+                "file01.txt",
+                "file1.txt",
+                "File2.txt",
+                "file9.txt",
+                "file10.txt"
+            ]
+        );
+        assert_eq!(natural_cmp("IMG_0099", "IMG_100"), Ordering::Less);
+        assert_eq!(natural_cmp("a", "a"), Ordering::Equal);
+    }
+
+    fn names_sorted_by(dir: &Path, sort_by: Vec<SortBy>) -> Vec<String> {
+        let options = DiscoveryOptions {
+            sort_by,
+            recursive: true,
+            ..Default::default()
+        };
+        discover_files_with_options(dir, &options)
+            .unwrap()
+            .into_iter()
+            .map(|f| {
+                f.path
+                    .strip_prefix(dir)
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_discover_sort_by_extension_and_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::create_dir_all(dir.join("b")).unwrap();
+        fs::create_dir_all(dir.join("a10")).unwrap();
+        fs::create_dir_all(dir.join("a9")).unwrap();
+        fs::write(dir.join("b").join("notes.TXT"), "x").unwrap();
+        fs::write(dir.join("a10").join("photo.jpg"), "x").unwrap();
+        fs::write(dir.join("a9").join("Makefile"), "x").unwrap();
+
+        assert_eq!(
+            names_sorted_by(dir, vec![SortBy::Path]),
+            vec!["a9/Makefile", "a10/photo.jpg", "b/notes.TXT"]
+        );
+        assert_eq!(
+            names_sorted_by(dir, vec![SortBy::Extension]),
+            vec!["a9/Makefile", "a10/photo.jpg", "b/notes.TXT"]
+        );
+    }
+
+    #[test]
+    fn test_discover_sort_by_several_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("big.md"), "# a longer set of notes").unwrap();
+        fs::write(dir.join("small.md"), "# notes").unwrap();
+        fs::write(dir.join("a.zip"), b"PK\x03\x04\x14\x00").unwrap();
+
+        assert_eq!(
+            names_sorted_by(dir, vec![SortBy::Type, SortBy::Size]),
+            vec!["small.md", "big.md", "a.zip"]
+        );
+    }
+
+    #[test]
+    fn test_discover_random_sort_follows_seed() {
+        let temp_dir = TempDir::new().unwrap();
+        for i in 0..20 {
+            fs::write(temp_dir.path().join(format!("file{}.txt", i)), "x").unwrap();
+        }
+        let shuffled = |seed| names_sorted_by(temp_dir.path(), vec![SortBy::Random { seed }]);
+
+        assert_eq!(shuffled(1), shuffled(1));
+        assert_ne!(shuffled(1), shuffled(2));
+        let mut sorted = shuffled(1);
+        sorted.sort_by(|a, b| natural_cmp(a, b));
+        assert_ne!(shuffled(1), sorted);
+    }
+
+    #[test]
+    fn test_discovery_hands_over_every_file() {
+        let temp_dir = nested_tree();
+        let options = DiscoveryOptions {
+            recursive: true,
+            sort_by: vec![SortBy::Name],
+            ..Default::default()
+        };
+        let expected = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        let mut discovery = Discovery::start(temp_dir.path(), options).unwrap();
+        let mut files = Vec::new();
+        while !discovery.is_finished() {
+            let batch = discovery.take_waiting();
+            assert!(batch.is_sorted_by(|a, b| discovery.compare(a, b).is_le()));
+            files.extend(batch);
+        }
+
+        assert!(discovery.take_error().is_none());
+        assert_eq!(discovery.found(), expected.len());
+        files.sort_by(|a, b| discovery.compare(a, b));
+        assert_eq!(paths(&files), paths(&expected));
+    }
+
+    #[test]
+    fn test_discovery_walks_every_root() {
+        let downloads = TempDir::new().unwrap();
+        let desktop = TempDir::new().unwrap();
+        fs::write(downloads.path().join("a.txt"), "a").unwrap();
+        fs::write(desktop.path().join("b.txt"), "b").unwrap();
+        let roots = vec![
+            downloads.path().to_path_buf(),
+            PathBuf::from("/nonexistent/directory"),
+            desktop.path().to_path_buf(),
+        ];
+
+        let mut discovery = Discovery::start_all(&roots, DiscoveryOptions::default()).unwrap();
+        let mut files = Vec::new();
+        while !discovery.is_finished() {
+            files.extend(discovery.take_waiting());
+        }
+
+        // The missing root is reported without losing the others
+        assert!(discovery.take_error().is_some());
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].root.as_deref(), Some(downloads.path()));
+        assert_eq!(files[1].root.as_deref(), Some(desktop.path()));
+    }
+
+    #[test]
+    fn test_discovery_wait() {
+        let temp_dir = nested_tree();
+        let options = DiscoveryOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let expected = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        let mut progress = Vec::new();
+        let files = Discovery::start(temp_dir.path(), options)
+            .unwrap()
+            .wait(|found| progress.push(found))
+            .unwrap();
+
+        assert_eq!(paths(&files), paths(&expected));
+        assert_eq!(progress.last(), Some(&expected.len()));
+    }
+
+    #[test]
+    fn test_discovery_reports_unreadable_directory() {
+        let discovery =
+            Discovery::start(Path::new("/nonexistent/dir"), DiscoveryOptions::default());
+        assert!(discovery.unwrap().wait(|_| {}).is_err());
+
+        let options = DiscoveryOptions {
+            include: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(Discovery::start(Path::new("."), options).is_err());
+    }
+
+    #[test]
+    fn test_discover_directories_as_entries() {
+        let temp_dir = nested_tree();
+        fs::create_dir(temp_dir.path().join("empty")).unwrap();
+        fs::create_dir(temp_dir.path().join(STAGING_DIR_NAME)).unwrap();
+        let options = DiscoveryOptions {
+            directories: true,
+            sort_by: vec![SortBy::Type],
+            ..Default::default()
+        };
+
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        assert_eq!(sorted_names(&files), vec!["a", "empty", "top.txt"]);
+        assert!(files[0].is_dir() && files[1].is_dir());
+        let a = files.iter().find(|f| f.name == "a").unwrap();
+        assert_eq!(a.file_count, Some(2));
+        assert_eq!(a.size, 14);
+
+        // Size filters look at the whole folder
+        let options = DiscoveryOptions {
+            directories: true,
+            min_size: Some(10),
+            ..Default::default()
+        };
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+        assert_eq!(sorted_names(&files), vec!["a"]);
+    }
+
     #[test]
     fn test_discover_reverse_sort() {
         let temp_dir = TempDir::new().unwrap();
@@ -295,7 +1184,7 @@ mod tests {
         fs::write(&file_b, "content").unwrap();
 
         let options = DiscoveryOptions {
-            sort_by: SortBy::Name,
+            sort_by: vec![SortBy::Name],
             reverse: true,
             ..Default::default()
         };
@@ -305,4 +1194,277 @@ mod tests {
         assert_eq!(files[0].name, "b.txt");
         assert_eq!(files[1].name, "a.txt");
     }
+
+    #[test]
+    fn test_discover_excludes_kept_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept = temp_dir.path().join("kept.txt");
+        let edited = temp_dir.path().join("edited.txt");
+        fs::write(&kept, "content").unwrap();
+        fs::write(&edited, "content").unwrap();
+
+        let mut keep_list = KeepList::new();
+        keep_list.mark(&FileEntry::from_path(&kept).unwrap());
+        keep_list.mark(&FileEntry::from_path(&edited).unwrap());
+        fs::write(&edited, "content that changed").unwrap();
+
+        let options = DiscoveryOptions {
+            keep_list: Some(keep_list),
+            ..Default::default()
+        };
+
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "edited.txt");
+    }
+
+    fn nested_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let deep = temp_dir.path().join("a").join("b");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(temp_dir.path().join("top.txt"), "content").unwrap();
+        fs::write(temp_dir.path().join("a").join("middle.txt"), "content").unwrap();
+        fs::write(deep.join("bottom.txt"), "content").unwrap();
+        temp_dir
+    }
+
+    fn paths(files: &[FileEntry]) -> Vec<&Path> {
+        files.iter().map(|f| f.path.as_path()).collect()
+    }
+
+    fn sorted_names(files: &[FileEntry]) -> Vec<&str> {
+        let mut names: Vec<_> = files.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_discover_recursive() {
+        let temp_dir = nested_tree();
+        let options = DiscoveryOptions {
+            recursive: true,
+            ..Default::default()
+        };
+
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        assert_eq!(
+            sorted_names(&files),
+            vec!["bottom.txt", "middle.txt", "top.txt"]
+        );
+        assert!(files
+            .iter()
+            .any(|f| f.path == temp_dir.path().join("a").join("b").join("bottom.txt")));
+    }
+
+    #[test]
+    fn test_discover_recursive_max_depth() {
+        let temp_dir = nested_tree();
+        let options = DiscoveryOptions {
+            recursive: true,
+            max_depth: Some(1),
+            ..Default::default()
+        };
+
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        assert_eq!(sorted_names(&files), vec!["middle.txt", "top.txt"]);
+    }
+
+    #[test]
+    fn test_discover_recursive_skips_hidden_and_staging_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        for dir in [".git", STAGING_DIR_NAME] {
+            fs::create_dir(temp_dir.path().join(dir)).unwrap();
+            fs::write(temp_dir.path().join(dir).join("inside.txt"), "content").unwrap();
+        }
+        fs::write(temp_dir.path().join("visible.txt"), "content").unwrap();
+
+        let options = DiscoveryOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+        assert_eq!(sorted_names(&files), vec!["visible.txt"]);
+
+        // Staged files are never offered for review, even with hidden files shown
+        let options = DiscoveryOptions {
+            recursive: true,
+            show_hidden: true,
+            ..Default::default()
+        };
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+        assert_eq!(sorted_names(&files), vec!["inside.txt", "visible.txt"]);
+        assert!(files
+            .iter()
+            .all(|f| !f.path.starts_with(temp_dir.path().join(STAGING_DIR_NAME))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover_recursive_survives_symlink_loop() {
+        let temp_dir = nested_tree();
+        std::os::unix::fs::symlink(temp_dir.path(), temp_dir.path().join("a").join("loop"))
+            .unwrap();
+
+        let options = DiscoveryOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let files = discover_files_with_options(temp_dir.path(), &options).unwrap();
+
+        assert_eq!(
+            sorted_names(&files),
+            vec!["bottom.txt", "middle.txt", "top.txt"]
+        );
+    }
+
+    #[test]
+    fn test_discover_respects_ignore_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("target").join("debug")).unwrap();
+        fs::create_dir_all(root.join("logs")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+        fs::write(root.join("logs").join(".ignore"), "!keep.log\n").unwrap();
+        fs::write(root.join(".fswpignore"), "*.bak\n").unwrap();
+        fs::write(root.join("target").join("debug").join("app"), "binary").unwrap();
+        fs::write(root.join("build.log"), "log").unwrap();
+        fs::write(root.join("notes.bak"), "old").unwrap();
+        fs::write(root.join("notes.txt"), "content").unwrap();
+        fs::write(root.join("logs").join("keep.log"), "log").unwrap();
+        fs::write(root.join("logs").join("drop.log"), "log").unwrap();
+
+        let options = DiscoveryOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let files = discover_files_with_options(root, &options).unwrap();
+        assert_eq!(sorted_names(&files), vec!["keep.log", "notes.txt"]);
+
+        let options = DiscoveryOptions {
+            recursive: true,
+            respect_ignore_files: false,
+            ..Default::default()
+        };
+        let files = discover_files_with_options(root, &options).unwrap();
+        assert_eq!(files.len(), 6);
+    }
+
+    #[test]
+    fn test_discover_subdirectory_respects_repository_ignore_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = temp_dir.path();
+        let project = repository.join("crates").join("app");
+        fs::create_dir_all(repository.join(".git").join("info")).unwrap();
+        fs::create_dir_all(project.join("target")).unwrap();
+        fs::write(
+            repository.join(".gitignore"),
+            "target/\n/crates/app/notes.bak\n",
+        )
+        .unwrap();
+        fs::write(
+            repository.join(".git").join("info").join("exclude"),
+            "*.log\n",
+        )
+        .unwrap();
+        fs::write(repository.join("crates").join(".ignore"), "!keep.log\n").unwrap();
+        fs::write(project.join("target").join("app"), "binary").unwrap();
+        fs::write(project.join("notes.bak"), "old").unwrap();
+        fs::write(project.join("build.log"), "log").unwrap();
+        fs::write(project.join("keep.log"), "log").unwrap();
+        fs::write(project.join("main.rs"), "fn main() {}").unwrap();
+
+        let options = DiscoveryOptions {
+            recursive: true,
+            ..Default::default()
+        };
+        let files = discover_files_with_options(&project, &options).unwrap();
+        assert_eq!(sorted_names(&files), vec!["keep.log", "main.rs"]);
+
+        let options = DiscoveryOptions {
+            recursive: true,
+            respect_ignore_files: false,
+            ..Default::default()
+        };
+        let files = discover_files_with_options(&project, &options).unwrap();
+        assert_eq!(files.len(), 5);
+    }
+
+    fn names_with(options: DiscoveryOptions) -> Vec<String> {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("old")).unwrap();
+        for name in [
+            "setup.dmg",
+            "ubuntu.iso",
+            "main.rs",
+            "IMG_0001.jpg",
+            "old/lib.rs",
+            "old/legacy.iso",
+        ] {
+            fs::write(root.join(name), "content").unwrap();
+        }
+
+        let options = DiscoveryOptions {
+            recursive: true,
+            ..options
+        };
+        let files = discover_files_with_options(root, &options).unwrap();
+        sorted_names(&files).into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_discover_include_globs() {
+        let names = names_with(DiscoveryOptions {
+            include: vec!["*.dmg".to_string(), "*.iso".to_string()],
+            ..Default::default()
+        });
+
+        // Patterns without a slash match at any depth
+        assert_eq!(names, vec!["legacy.iso", "setup.dmg", "ubuntu.iso"]);
+    }
+
+    #[test]
+    fn test_discover_exclude_globs() {
+        let names = names_with(DiscoveryOptions {
+            exclude: vec!["*.rs".to_string(), "old/*".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(names, vec!["IMG_0001.jpg", "setup.dmg", "ubuntu.iso"]);
+    }
+
+    #[test]
+    fn test_discover_include_path_glob() {
+        let names = names_with(DiscoveryOptions {
+            include: vec!["old/*.iso".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(names, vec!["legacy.iso"]);
+    }
+
+    #[test]
+    fn test_discover_regex_filter() {
+        let names = names_with(DiscoveryOptions {
+            name_regex: Some(r"^IMG_\d{4}\.jpe?g$".to_string()),
+            ..Default::default()
+        });
+
+        assert_eq!(names, vec!["IMG_0001.jpg"]);
+    }
+
+    #[test]
+    fn test_discover_rejects_invalid_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = DiscoveryOptions {
+            name_regex: Some("(unclosed".to_string()),
+            ..Default::default()
+        };
+
+        let err = discover_files_with_options(temp_dir.path(), &options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
 }
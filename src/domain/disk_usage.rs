@@ -0,0 +1,246 @@
+//! Cumulative sizes for reviewing the biggest space consumers first
+//!
+//! Disk-hog review walks the whole tree once and adds every file's size to
+//! each folder above it. The review starts with the biggest files and folders
+//! at the top; keeping a folder queues what is inside it, so the review works
+//! its way down to wherever the space actually goes.
+
+use super::{DiscoveryOptions, FileEntry, FileType};
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct DiskUsage {
+    roots: Vec<PathBuf>,
+    /// Files and folders offered for review inside each directory, biggest first
+    children: HashMap<PathBuf, Vec<FileEntry>>,
+    total_size: u64,
+}
+
+/// Running totals for one folder
+struct Totals {
+    size: u64,
+    file_count: u64,
+    modified: Option<DateTime<Utc>>,
+    accessed: Option<DateTime<Utc>>,
+    /// Whether any file inside passes the filters
+    offered: bool,
+}
+
+impl DiskUsage {
+    /// Options for the walk that feeds `new`: every file in the tree, so
+    /// folder sizes count everything that goes with them. Only hidden files,
+    /// ignore files and name filters still decide what is walked.
+    pub fn walk_options(options: &DiscoveryOptions) -> DiscoveryOptions {
+        DiscoveryOptions {
+            file_types: None,
+            min_size: None,
+            max_size: None,
+            modified_before: None,
+            modified_after: None,
+            accessed_before: None,
+            recursive: true,
+            max_depth: None,
+            directories: false,
+            keep_list: None,
+            ..options.clone()
+        }
+    }
+
+    /// Adds up `files`, found under `roots` with `walk_options`. Files are
+    /// offered when they pass the filters in `options`; folders when they
+    /// hold at least one file that does.
+    pub fn new(roots: &[PathBuf], files: Vec<FileEntry>, options: &DiscoveryOptions) -> Self {
+        let mut totals: HashMap<PathBuf, Totals> = HashMap::new();
+        let mut children: HashMap<PathBuf, Vec<FileEntry>> = HashMap::new();
+        let mut total_size = 0;
+
+        for file in files {
+            let offered = options.admits(&file);
+            total_size += file.size;
+
+            let Some(root) = roots.iter().find(|root| file.path.starts_with(root)) else {
+                continue;
+            };
+            let folders = file.path.ancestors().skip(1).take_while(|dir| dir != root);
+            for dir in folders {
+                let dir_totals = totals.entry(dir.to_path_buf()).or_insert(Totals {
+                    size: 0,
+                    file_count: 0,
+                    modified: None,
+                    accessed: None,
+                    offered: false,
+                });
+                dir_totals.size += file.size;
+                dir_totals.file_count += 1;
+                dir_totals.modified = dir_totals.modified.max(Some(file.modified_date));
+                dir_totals.accessed = dir_totals.accessed.max(file.accessed_date);
+                dir_totals.offered |= offered;
+            }
+
+            if offered {
+                if let Some(parent) = file.path.parent() {
+                    children.entry(parent.to_path_buf()).or_default().push(file);
+                }
+            }
+        }
+
+        for (dir, dir_totals) in totals {
+            if !dir_totals.offered {
+                continue;
+            }
+            let Some(parent) = dir.parent() else {
+                continue;
+            };
+            let mut entry = folder_entry(&dir, dir_totals);
+            entry.root = roots.iter().find(|root| dir.starts_with(root)).cloned();
+            children
+                .entry(parent.to_path_buf())
+                .or_default()
+                .push(entry);
+        }
+
+        for entries in children.values_mut() {
+            entries.sort_by(biggest_first);
+        }
+
+        Self {
+            roots: roots.to_vec(),
+            children,
+            total_size,
+        }
+    }
+
+    /// The files and folders directly inside the reviewed directories, biggest first
+    pub fn top_level(&self) -> Vec<FileEntry> {
+        let mut entries: Vec<FileEntry> = self
+            .roots
+            .iter()
+            .flat_map(|root| self.children(root))
+            .collect();
+        entries.sort_by(biggest_first);
+        entries
+    }
+
+    /// The files and folders directly inside `dir`, biggest first
+    pub fn children(&self, dir: &Path) -> Vec<FileEntry> {
+        self.children.get(dir).cloned().unwrap_or_default()
+    }
+
+    /// Size of every file in the tree
+    pub fn total_size(&self) -> u64 {
+        self.total_size
+    }
+}
+
+/// Orders entries biggest first, then by path so ties stay put
+pub fn biggest_first(a: &FileEntry, b: &FileEntry) -> Ordering {
+    b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path))
+}
+
+/// An entry for a whole folder from the totals of the files in it
+fn folder_entry(dir: &Path, totals: Totals) -> FileEntry {
+    let metadata = fs::metadata(dir).ok();
+    let own_modified = metadata
+        .as_ref()
+        .and_then(|m| m.modified().ok())
+        .map(DateTime::<Utc>::from);
+
+    FileEntry {
+        path: dir.to_path_buf(),
+        name: dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        size: totals.size,
+        modified_date: totals
+            .modified
+            .max(own_modified)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC),
+        file_type: FileType::Directory,
+        accessed_date: totals.accessed,
+        created_date: metadata
+            .and_then(|m| m.created().ok())
+            .map(DateTime::<Utc>::from),
+        file_count: Some(totals.file_count),
+        root: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::discover_files_with_options;
+    use tempfile::TempDir;
+
+    fn tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("videos/raw")).unwrap();
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::write(root.join("videos/raw/take1.mov"), vec![0u8; 500]).unwrap();
+        fs::write(root.join("videos/raw/take2.mov"), vec![0u8; 300]).unwrap();
+        fs::write(root.join("videos/final.mp4"), vec![0u8; 200]).unwrap();
+        fs::write(root.join("notes/todo.txt"), b"buy milk").unwrap();
+        fs::write(root.join("installer.iso"), vec![0u8; 600]).unwrap();
+        temp_dir
+    }
+
+    fn usage(root: &Path, options: &DiscoveryOptions) -> DiskUsage {
+        let files = discover_files_with_options(root, &DiskUsage::walk_options(options)).unwrap();
+        DiskUsage::new(&[root.to_path_buf()], files, options)
+    }
+
+    fn names(entries: &[FileEntry]) -> Vec<(&str, u64)> {
+        entries.iter().map(|e| (e.name.as_str(), e.size)).collect()
+    }
+
+    #[test]
+    fn test_disk_usage_adds_up_folders() {
+        let temp_dir = tree();
+        let root = temp_dir.path();
+        let usage = usage(root, &DiscoveryOptions::default());
+
+        assert_eq!(usage.total_size(), 1608);
+        assert_eq!(
+            names(&usage.top_level()),
+            vec![("videos", 1000), ("installer.iso", 600), ("notes", 8)]
+        );
+        assert_eq!(usage.top_level()[0].file_count, Some(3));
+        assert!(usage.top_level()[0].is_dir());
+
+        assert_eq!(
+            names(&usage.children(&root.join("videos"))),
+            vec![("raw", 800), ("final.mp4", 200)]
+        );
+        assert_eq!(
+            names(&usage.children(&root.join("videos/raw"))),
+            vec![("take1.mov", 500), ("take2.mov", 300)]
+        );
+    }
+
+    #[test]
+    fn test_disk_usage_offers_folders_holding_matching_files() {
+        let temp_dir = tree();
+        let root = temp_dir.path();
+        let options = DiscoveryOptions {
+            min_size: Some(250),
+            ..Default::default()
+        };
+        let usage = usage(root, &options);
+
+        // Folders keep their full size, but only those with a match are offered
+        assert_eq!(
+            names(&usage.top_level()),
+            vec![("videos", 1000), ("installer.iso", 600)]
+        );
+        assert_eq!(
+            names(&usage.children(&root.join("videos"))),
+            vec![("raw", 800)]
+        );
+    }
+}
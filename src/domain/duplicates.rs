@@ -0,0 +1,200 @@
+//! Finding files with identical contents
+//!
+//! Candidates are narrowed in three passes so most files are never read in
+//! full: files are grouped by size, then by a hash of their first block, and
+//! only the files still sharing a group are hashed completely.
+
+use super::FileEntry;
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, Read};
+
+/// How much of each file the partial hash covers
+const PARTIAL_HASH_LEN: u64 = 16 * 1024;
+
+/// Which copy of a duplicate group to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepRule {
+    /// The most recently modified copy
+    Newest,
+    /// The least recently modified copy
+    Oldest,
+    /// The copy with the shortest path, usually the one closest to the top
+    ShortestPath,
+    /// The biggest copy, usually the original of resized or re-encoded images
+    Largest,
+}
+
+impl KeepRule {
+    /// Position in `group` of the copy to keep. Ties go to the shorter path,
+    /// then to the path that sorts first.
+    pub fn pick(self, group: &[FileEntry]) -> usize {
+        let path_order = |a: &FileEntry, b: &FileEntry| {
+            let a_len = a.path.as_os_str().len();
+            let b_len = b.path.as_os_str().len();
+            a_len.cmp(&b_len).then_with(|| a.path.cmp(&b.path))
+        };
+
+        group
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                match self {
+                    KeepRule::Newest => b.modified_date.cmp(&a.modified_date),
+                    KeepRule::Oldest => a.modified_date.cmp(&b.modified_date),
+                    KeepRule::ShortestPath => std::cmp::Ordering::Equal,
+                    KeepRule::Largest => b.size.cmp(&a.size),
+                }
+                .then_with(|| path_order(a, b))
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+}
+
+/// Groups `files` whose contents are identical. Empty files and files that
+/// cannot be read are left out. Groups come biggest waste first, and the
+/// copies within a group by path.
+pub fn find_duplicates(files: Vec<FileEntry>) -> Vec<Vec<FileEntry>> {
+    let by_size = group_by(files.into_iter().filter(|f| f.size > 0), |f| Some(f.size));
+
+    let mut groups: Vec<Vec<FileEntry>> = by_size
+        .into_iter()
+        .flat_map(|group| group_by(group, |f| hash_file(f, Some(PARTIAL_HASH_LEN)).ok()))
+        .flat_map(|group| {
+            // Files no longer than the partial hash were already hashed in full
+            if group[0].size <= PARTIAL_HASH_LEN {
+                vec![group]
+            } else {
+                group_by(group, |f| hash_file(f, None).ok())
+            }
+        })
+        .collect();
+
+    sort_groups(&mut groups);
+    groups
+}
+
+/// Orders copies within each group by path, and groups biggest waste first
+pub(crate) fn sort_groups(groups: &mut [Vec<FileEntry>]) {
+    for group in groups.iter_mut() {
+        group.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    groups.sort_by(|a, b| {
+        wasted_bytes(b)
+            .cmp(&wasted_bytes(a))
+            .then_with(|| a[0].path.cmp(&b[0].path))
+    });
+}
+
+/// Space freed by keeping only the largest copy in `group`
+pub fn wasted_bytes(group: &[FileEntry]) -> u64 {
+    let total: u64 = group.iter().map(|f| f.size).sum();
+    let largest = group.iter().map(|f| f.size).max().unwrap_or(0);
+    total - largest
+}
+
+/// Splits `files` by `key`, dropping files without one and groups of one
+fn group_by<K: Hash + Eq>(
+    files: impl IntoIterator<Item = FileEntry>,
+    key: impl Fn(&FileEntry) -> Option<K>,
+) -> Vec<Vec<FileEntry>> {
+    let mut groups: HashMap<K, Vec<FileEntry>> = HashMap::new();
+    for file in files {
+        if let Some(k) = key(&file) {
+            groups.entry(k).or_default().push(file);
+        }
+    }
+    groups.into_values().filter(|g| g.len() > 1).collect()
+}
+
+/// Hashes the first `limit` bytes of `file`, or all of it
+fn hash_file(file: &FileEntry, limit: Option<u64>) -> io::Result<blake3::Hash> {
+    let reader = File::open(&file.path)?;
+    let mut hasher = blake3::Hasher::new();
+    io::copy(&mut reader.take(limit.unwrap_or(u64::MAX)), &mut hasher)?;
+    Ok(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, contents: &[u8]) -> FileEntry {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        FileEntry::from_path(&path).unwrap()
+    }
+
+    fn names(group: &[FileEntry]) -> Vec<&str> {
+        group.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_identical_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let files = vec![
+            write(dir, "setup.dmg", b"installer"),
+            write(dir, "setup (1).dmg", b"installer"),
+            write(dir, "photo.jpg", b"pixels"),
+            write(dir, "photo copy.jpg", b"pixels"),
+            write(dir, "photo edit.jpg", b"PIXELS"),
+            write(dir, "empty.txt", b""),
+            write(dir, "empty copy.txt", b""),
+        ];
+
+        let groups = find_duplicates(files);
+
+        assert_eq!(groups.len(), 2);
+        // The installer wastes more space, so it comes first
+        assert_eq!(names(&groups[0]), vec!["setup (1).dmg", "setup.dmg"]);
+        assert_eq!(names(&groups[1]), vec!["photo copy.jpg", "photo.jpg"]);
+    }
+
+    #[test]
+    fn test_find_duplicates_compares_past_the_first_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let mut contents = vec![0u8; PARTIAL_HASH_LEN as usize + 10];
+        let a = write(dir, "a.bin", &contents);
+        let b = write(dir, "b.bin", &contents);
+        *contents.last_mut().unwrap() = 1;
+        let c = write(dir, "c.bin", &contents);
+
+        let groups = find_duplicates(vec![a, b, c]);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(names(&groups[0]), vec!["a.bin", "b.bin"]);
+    }
+
+    #[test]
+    fn test_keep_rule_pick() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::create_dir(dir.join("backup")).unwrap();
+        let mut old = write(dir, "backup/report.pdf", b"report");
+        let mut new = write(dir, "report.pdf", b"report");
+        let mut newer_copy = write(dir, "report copy.pdf", b"report");
+        old.modified_date = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+        new.modified_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        newer_copy.modified_date = new.modified_date;
+        let group = vec![old, new, newer_copy];
+
+        assert_eq!(KeepRule::Oldest.pick(&group), 0);
+        // Equally new: the shorter path wins
+        assert_eq!(KeepRule::Newest.pick(&group), 1);
+        assert_eq!(KeepRule::ShortestPath.pick(&group), 1);
+        // Equally large: the shorter path wins
+        assert_eq!(KeepRule::Largest.pick(&group), 1);
+
+        let mut group = group;
+        group[2].size = 1000;
+        assert_eq!(KeepRule::Largest.pick(&group), 2);
+    }
+}
@@ -1,8 +1,10 @@
-use super::FileType;
+use super::file_type::SNIFF_LEN;
+use super::{ExtensionTypes, FileType};
 use chrono::{DateTime, Utc};
-use std::fs;
-use std::io;
+use std::fs::{self, File};
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 #[derive(Debug, Clone)]
 pub struct FileEntry {
@@ -11,10 +13,24 @@ pub struct FileEntry {
     pub size: u64,
     pub modified_date: DateTime<Utc>,
     pub file_type: FileType,
+    /// Last access time, when the platform and filesystem record one
+    pub accessed_date: Option<DateTime<Utc>>,
+    /// Creation time, when the platform and filesystem record one
+    pub created_date: Option<DateTime<Utc>>,
+    /// Number of files inside, for directories reviewed as a whole
+    pub file_count: Option<u64>,
+    /// The reviewed directory the file was found in
+    pub root: Option<PathBuf>,
 }
 
 impl FileEntry {
     pub fn from_path(path: &Path) -> io::Result<Self> {
+        Self::from_path_with_types(path, &ExtensionTypes::default())
+    }
+
+    /// Like `from_path`, with the user's extension mappings deciding the type
+    /// of files they cover
+    pub fn from_path_with_types(path: &Path, types: &ExtensionTypes) -> io::Result<Self> {
         let metadata = fs::metadata(path)?;
         let modified = metadata.modified()?;
         let modified_date: DateTime<Utc> = modified.into();
@@ -27,7 +43,15 @@ impl FileEntry {
 
         let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
-        let file_type = FileType::from_extension(extension);
+        let file_type = types.get(extension).unwrap_or_else(|| {
+            // Only regular files are read: opening a FIFO would block
+            let head = if metadata.is_file() {
+                read_head(path).unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            FileType::detect(&head, extension)
+        });
 
         Ok(FileEntry {
             path: path.to_path_buf(),
@@ -35,17 +59,103 @@ impl FileEntry {
             size: metadata.len(),
             modified_date,
             file_type,
+            accessed_date: metadata.accessed().ok().map(Into::into),
+            created_date: metadata.created().ok().map(Into::into),
+            file_count: None,
+            root: None,
+        })
+    }
+
+    /// Creates an entry for a whole directory. Its size and file count add up
+    /// everything inside, and its modified and accessed dates are the latest
+    /// of any file inside, so a folder counts as old only if all of it is.
+    /// Symlinks inside are counted but not followed.
+    pub fn from_dir(path: &Path) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let mut totals = DirTotals {
+            size: 0,
+            file_count: 0,
+            modified: metadata.modified()?,
+            accessed: metadata.accessed().ok(),
+        };
+        totals.add_dir(path);
+
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        Ok(FileEntry {
+            path: path.to_path_buf(),
+            name,
+            size: totals.size,
+            modified_date: totals.modified.into(),
+            file_type: FileType::Directory,
+            accessed_date: totals.accessed.map(Into::into),
+            created_date: metadata.created().ok().map(Into::into),
+            file_count: Some(totals.file_count),
+            root: None,
         })
     }
+
+    pub fn is_dir(&self) -> bool {
+        self.file_type == FileType::Directory
+    }
+}
+
+/// Running totals while `FileEntry::from_dir` walks a directory
+struct DirTotals {
+    size: u64,
+    file_count: u64,
+    modified: SystemTime,
+    accessed: Option<SystemTime>,
+}
+
+impl DirTotals {
+    /// Adds everything under `dir`, skipping what cannot be read
+    fn add_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if let Ok(modified) = metadata.modified() {
+                self.modified = self.modified.max(modified);
+            }
+            if let Ok(accessed) = metadata.accessed() {
+                self.accessed = Some(self.accessed.map_or(accessed, |a| a.max(accessed)));
+            }
+
+            if metadata.is_dir() {
+                self.add_dir(&entry.path());
+            } else {
+                self.size += metadata.len();
+                self.file_count += 1;
+            }
+        }
+    }
+}
+
+/// The first `SNIFF_LEN` bytes of the file at `path`
+fn read_head(path: &Path) -> io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    File::open(path)?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut head)?;
+    Ok(head)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
-    use tempfile::NamedTempFile;
+    use tempfile::{NamedTempFile, TempDir};
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_file_entry_from_path() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path();
@@ -56,7 +166,9 @@ mod tests {
         assert_eq!(entry.path, path);
         assert!(entry.name.len() > 0);
         assert_eq!(entry.size, 12);
-        assert_eq!(entry.file_type, FileType::Binary);
+        // No extension, but the contents are plainly text
+        assert_eq!(entry.file_type, FileType::Text);
+        assert!(entry.accessed_date.is_some());
     }
 
     #[test]
@@ -74,6 +186,95 @@ mod tests {
         fs::remove_file(&txt_path).ok();
     }
 
+    #[test]
+    fn test_file_entry_detects_mislabelled_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let cases: [(&str, &[u8], FileType); 6] = [
+            (
+                "README",
+                b"# fswp\n\nDeclutter directories.\n",
+                FileType::Text,
+            ),
+            ("Makefile", b"build:\n\tcargo build\n", FileType::Text),
+            ("photo.jpg", b"this was never a photo", FileType::Text),
+            (
+                "notes.txt",
+                b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR",
+                FileType::Image,
+            ),
+            ("scan.bin", b"%PDF-1.4\n%\xc7\xec\x8f\xa2\n", FileType::Pdf),
+            (
+                "export.txt",
+                b"PK\x03\x04\x14\x00\x00\x00\x08\x00",
+                FileType::Archive,
+            ),
+        ];
+
+        for (name, contents, expected) in cases {
+            let path = dir.join(name);
+            fs::write(&path, contents).unwrap();
+            let entry = FileEntry::from_path(&path).unwrap();
+            assert_eq!(entry.file_type, expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_file_entry_empty_file_uses_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("blank.png");
+        fs::write(&path, b"").unwrap();
+
+        let entry = FileEntry::from_path(&path).unwrap();
+        assert_eq!(entry.file_type, FileType::Image);
+    }
+
+    #[test]
+    fn test_file_entry_extension_types_override_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("drawing.kra");
+        fs::write(
+            &path,
+            b"PK\x03\x04\x14\x00\x00\x00mimetypeapplication/x-krita",
+        )
+        .unwrap();
+        let types = ExtensionTypes::parse([("kra", "image")]).unwrap();
+
+        assert_eq!(
+            FileEntry::from_path(&path).unwrap().file_type,
+            FileType::Archive
+        );
+        assert_eq!(
+            FileEntry::from_path_with_types(&path, &types)
+                .unwrap()
+                .file_type,
+            FileType::Image
+        );
+    }
+
+    #[test]
+    fn test_file_entry_from_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("old-project");
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        fs::write(dir.join("README"), b"hello").unwrap();
+        fs::write(dir.join("src/main.rs"), b"fn main() {}").unwrap();
+        fs::write(dir.join("src/nested/data.bin"), [0u8; 100]).unwrap();
+
+        let entry = FileEntry::from_dir(&dir).unwrap();
+
+        assert_eq!(entry.name, "old-project");
+        assert!(entry.is_dir());
+        assert_eq!(entry.size, 5 + 12 + 100);
+        assert_eq!(entry.file_count, Some(3));
+        let newest: DateTime<Utc> = fs::metadata(dir.join("src/nested/data.bin"))
+            .unwrap()
+            .modified()
+            .unwrap()
+            .into();
+        assert!(entry.modified_date >= newest);
+    }
+
     #[test]
     fn test_file_entry_nonexistent_file() {
         let result = FileEntry::from_path(Path::new("/nonexistent/file.txt"));
@@ -0,0 +1,236 @@
+//! Files to review named in a list instead of found by a walk
+//!
+//! `fd -e log | fswp --stdin` and `fswp --from-list candidates.txt` hand over
+//! paths one per line, or separated by NUL bytes the way `fd -0` and
+//! `find -print0` write them. Entries are built straight from the paths; the
+//! type, size, age, name and keep-list filters still apply, and the files keep
+//! the list's order unless sort keys are given.
+
+use super::discovery::NameFilter;
+use super::session::absolute;
+use super::{compare_files, DiscoveryOptions, FileEntry};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Files read from a list, and the paths that could not be read
+#[derive(Debug, Default)]
+pub struct ListedFiles {
+    pub files: Vec<FileEntry>,
+    pub unreadable: Vec<(PathBuf, io::Error)>,
+}
+
+/// Splits a file list into paths: at NUL bytes when it has any, otherwise at
+/// line ends. Blank entries are skipped.
+pub fn parse_file_list(contents: &[u8]) -> Vec<PathBuf> {
+    if contents.contains(&0) {
+        contents
+            .split(|&byte| byte == 0)
+            .filter(|entry| !entry.is_empty())
+            .map(path_from_bytes)
+            .collect()
+    } else {
+        contents
+            .split(|&byte| byte == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .filter(|line| !line.is_empty())
+            .map(path_from_bytes)
+            .collect()
+    }
+}
+
+/// Builds entries for the files at `paths`, filtered and sorted as discovery
+/// would with `options`. With no sort keys the list's order is kept, or
+/// turned around when `options.reverse`. Directories are skipped unless
+/// `options.directories`; hidden and ignored files are not: the list decides.
+/// A path listed twice is reviewed once. Name patterns see paths relative to
+/// the current directory, the way discovery shows them paths relative to the
+/// reviewed one.
+pub fn load_listed_files(paths: &[PathBuf], options: &DiscoveryOptions) -> io::Result<ListedFiles> {
+    let names = NameFilter::new(options)?;
+    let cwd = std::env::current_dir().ok();
+    let mut seen = HashSet::new();
+    let mut listed = ListedFiles::default();
+
+    for path in paths {
+        let relative = relative_to(path, cwd.as_deref());
+        if !names.matches(&relative) || !seen.insert(absolute(path)) {
+            continue;
+        }
+
+        let file_entry = match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() => {
+                if !options.directories {
+                    continue;
+                }
+                FileEntry::from_dir(path)
+            }
+            Ok(_) => FileEntry::from_path_with_types(path, &options.extension_types),
+            Err(e) => Err(e),
+        };
+        match file_entry {
+            Ok(file_entry) if options.admits(&file_entry) => listed.files.push(file_entry),
+            Ok(_) => {}
+            Err(e) => listed.unreadable.push((path.clone(), e)),
+        }
+    }
+
+    if !options.sort_by.is_empty() {
+        listed
+            .files
+            .sort_by(|a, b| compare_files(&options.sort_by, options.reverse, a, b));
+    } else if options.reverse {
+        listed.files.reverse();
+    }
+    Ok(listed)
+}
+
+/// `path` without the current directory in front: `./build/x` and
+/// `$PWD/build/x` both become `build/x`
+fn relative_to(path: &Path, cwd: Option<&Path>) -> PathBuf {
+    let path = cwd
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+    path.components()
+        .skip_while(|component| *component == Component::CurDir)
+        .collect()
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SortBy;
+    use tempfile::TempDir;
+
+    fn unsorted() -> DiscoveryOptions {
+        DiscoveryOptions {
+            sort_by: Vec::new(),
+            ..Default::default()
+        }
+    }
+
+    fn names(listed: &ListedFiles) -> Vec<&str> {
+        listed.files.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_file_list() {
+        assert_eq!(
+            parse_file_list(b"a.log\nlogs/b.log\r\n\n/var/log/c.log\n"),
+            vec![
+                PathBuf::from("a.log"),
+                PathBuf::from("logs/b.log"),
+                PathBuf::from("/var/log/c.log")
+            ]
+        );
+
+        // With NUL separators, line breaks belong to the names
+        assert_eq!(
+            parse_file_list(b"two\nlines.txt\0plain.txt\0"),
+            vec![PathBuf::from("two\nlines.txt"), PathBuf::from("plain.txt")]
+        );
+
+        assert!(parse_file_list(b"").is_empty());
+    }
+
+    #[test]
+    fn test_load_listed_files_keeps_list_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("b.txt"), b"bb").unwrap();
+        fs::write(dir.join("a.txt"), b"a").unwrap();
+        fs::write(dir.join(".hidden"), b"hidden").unwrap();
+        fs::create_dir(dir.join("folder")).unwrap();
+        let paths = vec![
+            dir.join("b.txt"),
+            dir.join("gone.txt"),
+            dir.join(".hidden"),
+            dir.join("folder"),
+            dir.join("a.txt"),
+            dir.join("b.txt"),
+        ];
+
+        let listed = load_listed_files(&paths, &unsorted()).unwrap();
+
+        assert_eq!(names(&listed), vec!["b.txt", ".hidden", "a.txt"]);
+        assert_eq!(listed.unreadable.len(), 1);
+        assert_eq!(listed.unreadable[0].0, dir.join("gone.txt"));
+        assert_eq!(listed.unreadable[0].1.kind(), io::ErrorKind::NotFound);
+
+        let options = DiscoveryOptions {
+            reverse: true,
+            directories: true,
+            ..unsorted()
+        };
+        let listed = load_listed_files(&paths, &options).unwrap();
+        assert_eq!(names(&listed), vec!["a.txt", "folder", ".hidden", "b.txt"]);
+    }
+
+    #[test]
+    fn test_load_listed_files_matches_names_relative_to_cwd() {
+        let cwd = std::env::current_dir().unwrap();
+        let paths = vec![
+            PathBuf::from("./build/missing-a.o"),
+            cwd.join("build/missing-b.o"),
+            PathBuf::from("build/missing-c.o"),
+            PathBuf::from("src/build/missing-d.o"),
+        ];
+        let options = DiscoveryOptions {
+            include: vec!["build/**".to_string()],
+            ..unsorted()
+        };
+
+        // Files passing the name filter are looked up, and reported missing
+        let listed = load_listed_files(&paths, &options).unwrap();
+        let looked_up: Vec<&PathBuf> = listed.unreadable.iter().map(|(path, _)| path).collect();
+        assert_eq!(looked_up, paths[..3].iter().collect::<Vec<_>>());
+
+        assert_eq!(
+            relative_to(Path::new("./././x/y"), None),
+            PathBuf::from("x/y")
+        );
+        assert_eq!(
+            relative_to(Path::new("/elsewhere/x"), Some(&cwd)),
+            PathBuf::from("/elsewhere/x")
+        );
+    }
+
+    #[test]
+    fn test_load_listed_files_filters_and_sorts() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("big.log"), [0u8; 100]).unwrap();
+        fs::write(dir.join("small.log"), [0u8; 10]).unwrap();
+        fs::write(dir.join("huge.iso"), [0u8; 500]).unwrap();
+        fs::write(dir.join("medium.log"), [0u8; 50]).unwrap();
+        let paths: Vec<PathBuf> = ["big.log", "small.log", "huge.iso", "medium.log"]
+            .iter()
+            .map(|name| dir.join(name))
+            .collect();
+
+        let options = DiscoveryOptions {
+            sort_by: vec![SortBy::Size],
+            min_size: Some(20),
+            include: vec!["*.log".to_string()],
+            ..Default::default()
+        };
+        let listed = load_listed_files(&paths, &options).unwrap();
+
+        assert_eq!(names(&listed), vec!["medium.log", "big.log"]);
+        assert!(listed.unreadable.is_empty());
+        assert!(listed.files.iter().all(|f| f.root.is_none()));
+        assert_eq!(listed.files[1].path, dir.join("big.log"));
+    }
+}
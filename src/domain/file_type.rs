@@ -1,32 +1,406 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use std::collections::HashMap;
+
+/// Kinds of file, declared in the order `SortBy::Type` sorts them
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FileType {
+    /// A folder reviewed as a whole
+    Directory,
     Text,
-    Image,
+    Document,
+    Spreadsheet,
     Pdf,
+    Image,
+    Audio,
+    Video,
+    Archive,
+    Font,
+    Executable,
     Binary,
 }
 
+/// Extension mappings from the user configuration. They take precedence over
+/// both content detection and the built-in extensions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtensionTypes(HashMap<String, FileType>);
+
+impl ExtensionTypes {
+    /// Builds the mappings from extension and type name pairs, as written in
+    /// the configuration. Fails on the first empty extension or unknown type.
+    pub fn parse<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<Self, String> {
+        let mut types = HashMap::new();
+        for (extension, name) in pairs {
+            let extension = extension.trim().trim_start_matches('.').to_lowercase();
+            if extension.is_empty() || extension.contains(['/', '\\']) {
+                return Err(format!("\"{}\" is not a file extension", extension));
+            }
+            // Only folders are directories, whatever their name
+            let mappable = |t: &FileType| *t != FileType::Directory;
+            let file_type = FileType::from_name(name).filter(mappable).ok_or_else(|| {
+                let names: Vec<_> = FileType::ALL
+                    .iter()
+                    .filter(|t| mappable(t))
+                    .map(FileType::name)
+                    .collect();
+                format!(
+                    "unknown file type \"{}\" for .{} (expected one of: {})",
+                    name,
+                    extension,
+                    names.join(", ")
+                )
+            })?;
+            types.insert(extension, file_type);
+        }
+        Ok(ExtensionTypes(types))
+    }
+
+    /// The type mapped to `extension`, compared case-insensitively
+    pub fn get(&self, extension: &str) -> Option<FileType> {
+        self.0.get(&extension.to_lowercase()).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// How much of a file is read to detect its type
+pub const SNIFF_LEN: usize = 8 * 1024;
+
+/// Signatures of common formats: offset, bytes, and the type they mean
+const MAGIC_NUMBERS: &[(usize, &[u8], FileType)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", FileType::Image),
+    (0, b"\xff\xd8\xff", FileType::Image),
+    (0, b"GIF87a", FileType::Image),
+    (0, b"GIF89a", FileType::Image),
+    (0, b"%PDF-", FileType::Pdf),
+    (0, b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1", FileType::Document),
+    (0, b"{\\rtf", FileType::Document),
+    (0, b"\x1f\x8b", FileType::Archive),
+    (0, b"7z\xbc\xaf\x27\x1c", FileType::Archive),
+    (0, b"\xfd7zXZ\x00", FileType::Archive),
+    (0, b"Rar!\x1a\x07", FileType::Archive),
+    (0, b"\x28\xb5\x2f\xfd", FileType::Archive),
+    (0, b"MSCF\x00\x00\x00\x00", FileType::Archive),
+    (257, b"ustar", FileType::Archive),
+    (0, b"ID3", FileType::Audio),
+    (0, b"fLaC", FileType::Audio),
+    (0, b"OggS", FileType::Audio),
+    (0, b"MThd\x00\x00\x00\x06", FileType::Audio),
+    (0, b"\x1a\x45\xdf\xa3", FileType::Video),
+    (0, b"FLV\x01", FileType::Video),
+    (0, b"wOFF", FileType::Font),
+    (0, b"wOF2", FileType::Font),
+    (0, b"OTTO", FileType::Font),
+    (0, b"ttcf", FileType::Font),
+    (0, b"\x7fELF", FileType::Executable),
+    (0, b"\xfe\xed\xfa\xce", FileType::Executable),
+    (0, b"\xfe\xed\xfa\xcf", FileType::Executable),
+    (0, b"\xce\xfa\xed\xfe", FileType::Executable),
+    (0, b"\xcf\xfa\xed\xfe", FileType::Executable),
+    (0, b"\xca\xfe\xba\xbe", FileType::Executable),
+    (0, b"SQLite format 3\x00", FileType::Binary),
+];
+
+/// Container formats shared by several kinds of file, and those kinds. When
+/// the extension names one of them, it is more specific than the signature.
+const CONTAINERS: &[(&[u8], &[FileType])] = &[
+    // docx, xlsx, odt, epub, apk, jar
+    (
+        b"PK\x03\x04",
+        &[
+            FileType::Archive,
+            FileType::Document,
+            FileType::Spreadsheet,
+            FileType::Executable,
+        ],
+    ),
+    // doc, xls, ppt, msi
+    (
+        b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1",
+        &[
+            FileType::Document,
+            FileType::Spreadsheet,
+            FileType::Executable,
+        ],
+    ),
+    // ogg, ogv
+    (b"OggS", &[FileType::Audio, FileType::Video]),
+];
+
 impl FileType {
+    /// Every type, in declaration order
+    pub const ALL: [FileType; 12] = [
+        FileType::Directory,
+        FileType::Text,
+        FileType::Document,
+        FileType::Spreadsheet,
+        FileType::Pdf,
+        FileType::Image,
+        FileType::Audio,
+        FileType::Video,
+        FileType::Archive,
+        FileType::Font,
+        FileType::Executable,
+        FileType::Binary,
+    ];
+
+    /// Name used in the configuration and on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            FileType::Directory => "directory",
+            FileType::Text => "text",
+            FileType::Image => "image",
+            FileType::Pdf => "pdf",
+            FileType::Document => "document",
+            FileType::Spreadsheet => "spreadsheet",
+            FileType::Archive => "archive",
+            FileType::Audio => "audio",
+            FileType::Video => "video",
+            FileType::Font => "font",
+            FileType::Executable => "executable",
+            FileType::Binary => "binary",
+        }
+    }
+
+    /// The type called `name`, compared case-insensitively
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|t| t.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Name shown in the interface
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileType::Directory => "Directory",
+            FileType::Text => "Text",
+            FileType::Image => "Image",
+            FileType::Pdf => "PDF",
+            FileType::Document => "Document",
+            FileType::Spreadsheet => "Spreadsheet",
+            FileType::Archive => "Archive",
+            FileType::Audio => "Audio",
+            FileType::Video => "Video",
+            FileType::Font => "Font",
+            FileType::Executable => "Executable",
+            FileType::Binary => "Binary",
+        }
+    }
+
+    /// Detects the type from the first bytes of a file (up to `SNIFF_LEN`),
+    /// falling back to the extension when the contents are inconclusive
+    pub fn detect(head: &[u8], extension: &str) -> Self {
+        let by_extension = Self::from_extension(extension);
+
+        if let Some(file_type) = Self::from_magic(head) {
+            let shared = CONTAINERS
+                .iter()
+                .find(|(magic, _)| head.starts_with(magic))
+                .is_some_and(|(_, kinds)| kinds.contains(&by_extension));
+            return if shared { by_extension } else { file_type };
+        }
+        if head.is_empty() {
+            return by_extension;
+        }
+
+        if looks_like_text(head) {
+            if is_svg(head, extension) {
+                FileType::Image
+            } else {
+                FileType::Text
+            }
+        } else if head.contains(&0) && by_extension == FileType::Text {
+            // Text never contains NUL bytes, whatever the extension claims
+            FileType::Binary
+        } else {
+            by_extension
+        }
+    }
+
+    /// The type whose signature `head` starts with, if any
+    pub fn from_magic(head: &[u8]) -> Option<Self> {
+        let at =
+            |offset: usize, magic: &[u8]| head.get(offset..).is_some_and(|h| h.starts_with(magic));
+
+        if at(0, b"PK\x03\x04") {
+            return Some(zip_contents(head));
+        }
+        if at(0, b"RIFF") || at(0, b"FORM") {
+            match head.get(8..12) {
+                Some(b"WEBP") => return Some(FileType::Image),
+                Some(b"WAVE" | b"AIFF" | b"AIFC") => return Some(FileType::Audio),
+                Some(b"AVI ") => return Some(FileType::Video),
+                _ => {}
+            }
+        }
+        if at(4, b"ftyp") {
+            // The brand tells MPEG-4 audio and HEIF photos from video
+            return Some(match head.get(8..12) {
+                Some(b"M4A " | b"M4B " | b"M4P ") => FileType::Audio,
+                Some(b"heic" | b"heix" | b"mif1" | b"msf1" | b"avif") => FileType::Image,
+                _ => FileType::Video,
+            });
+        }
+        if at(0, b"BM") && head.get(6..10) == Some(&[0; 4]) {
+            return Some(FileType::Image);
+        }
+        if at(0, b"BZh") && head.get(3).is_some_and(|b| (b'1'..=b'9').contains(b)) {
+            return Some(FileType::Archive);
+        }
+        if at(0, b"MZ") && head.contains(&0) {
+            return Some(FileType::Executable);
+        }
+        // Short, mostly-zero signatures: trusted only when the header behind them checks out
+        if at(0, b"\x00\x00\x01\x00") && is_icon(head) {
+            return Some(FileType::Image);
+        }
+        if at(0, b"\x00\x01\x00\x00") && is_truetype(head) {
+            return Some(FileType::Font);
+        }
+
+        MAGIC_NUMBERS
+            .iter()
+            .find(|(offset, magic, _)| at(*offset, magic))
+            .map(|(_, _, file_type)| file_type.clone())
+    }
+
     pub fn from_extension(ext: &str) -> Self {
         let ext = ext.to_lowercase();
         match ext.as_str() {
             // Text files
             "txt" | "md" | "rs" | "py" | "js" | "ts" | "jsx" | "tsx" | "json" | "yaml" | "yml"
             | "toml" | "xml" | "html" | "css" | "sh" | "bash" | "c" | "cpp" | "h" | "hpp"
-            | "java" | "go" | "rb" | "php" | "swift" | "kt" | "cs" | "sql" => FileType::Text,
+            | "java" | "go" | "rb" | "php" | "swift" | "kt" | "cs" | "sql" | "csv" | "tsv"
+            | "log" => FileType::Text,
 
             // Image files
-            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico" => FileType::Image,
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico" | "heic" | "avif" => {
+                FileType::Image
+            }
 
             // PDF files
             "pdf" => FileType::Pdf,
 
+            // Word processing, presentations and e-books
+            "doc" | "docx" | "odt" | "rtf" | "pages" | "epub" | "ppt" | "pptx" | "odp" | "key" => {
+                FileType::Document
+            }
+
+            // Spreadsheets
+            "xls" | "xlsx" | "ods" | "numbers" => FileType::Spreadsheet,
+
+            // Archives, compressed files and disk images
+            "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "7z" | "rar" | "cab" | "jar"
+            | "dmg" | "iso" | "deb" | "rpm" => FileType::Archive,
+
+            // Audio files
+            "mp3" | "wav" | "flac" | "ogg" | "oga" | "opus" | "m4a" | "aac" | "wma" | "aif"
+            | "aiff" | "mid" | "midi" => FileType::Audio,
+
+            // Video files
+            "mp4" | "m4v" | "mkv" | "webm" | "mov" | "avi" | "wmv" | "flv" | "mpg" | "mpeg"
+            | "ogv" | "3gp" => FileType::Video,
+
+            // Fonts
+            "ttf" | "otf" | "ttc" | "woff" | "woff2" | "eot" => FileType::Font,
+
+            // Programs, libraries and installers
+            "exe" | "msi" | "dll" | "so" | "dylib" | "appimage" | "apk" | "com" => {
+                FileType::Executable
+            }
+
             // Everything else is binary
             _ => FileType::Binary,
         }
     }
 }
 
+/// What a zip file holds, judged by the names of its first entries
+fn zip_contents(head: &[u8]) -> FileType {
+    let has = |needle: &[u8]| head.windows(needle.len()).any(|w| w == needle);
+
+    if has(b"application/vnd.oasis.opendocument.spreadsheet") || has(b"xl/") {
+        FileType::Spreadsheet
+    } else if has(b"application/vnd.oasis.opendocument")
+        || has(b"application/epub+zip")
+        || has(b"word/")
+        || has(b"ppt/")
+    {
+        FileType::Document
+    } else if has(b"AndroidManifest.xml") {
+        FileType::Executable
+    } else {
+        FileType::Archive
+    }
+}
+
+/// Whether `head`, which starts like an ICO file, has a sane icon directory:
+/// at least one image, whose first entry has a valid colour depth, a size and
+/// data that starts after the directory
+fn is_icon(head: &[u8]) -> bool {
+    let u16_at = |i: usize| head.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let u32_at = |i: usize| {
+        head.get(i..i + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+
+    let (Some(count), Some(planes), Some(depth), Some(size), Some(offset)) =
+        (u16_at(4), u16_at(10), u16_at(12), u32_at(14), u32_at(18))
+    else {
+        return false;
+    };
+    count > 0
+        && head[9] == 0
+        && planes <= 1
+        && matches!(depth, 0 | 1 | 4 | 8 | 16 | 24 | 32)
+        && size > 0
+        && offset >= 6 + 16 * u32::from(count)
+}
+
+/// Whether `head`, which starts like a TrueType font, has a table directory
+/// whose binary search fields agree with its table count
+fn is_truetype(head: &[u8]) -> bool {
+    let u16_at = |i: usize| head.get(i..i + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+
+    let (Some(tables), Some(search_range), Some(entry_selector), Some(range_shift)) =
+        (u16_at(4), u16_at(6), u16_at(8), u16_at(10))
+    else {
+        return false;
+    };
+    if tables == 0 {
+        return false;
+    }
+    let log2 = 15 - tables.leading_zeros() as u16;
+    entry_selector == log2
+        && u32::from(search_range) == 16 << log2
+        && u32::from(range_shift) == u32::from(tables) * 16 - u32::from(search_range)
+}
+
+/// UTF-8 without NUL bytes and with hardly any control characters
+fn looks_like_text(head: &[u8]) -> bool {
+    let utf8 = match std::str::from_utf8(head) {
+        Ok(_) => true,
+        // A character cut in half where the sniffed bytes end
+        Err(e) => e.error_len().is_none() && head.len() >= SNIFF_LEN,
+    };
+    let controls = head
+        .iter()
+        .filter(|&&b| (b < 0x20 && !b"\t\n\r\x0c\x1b".contains(&b)) || b == 0x7f)
+        .count();
+
+    utf8 && !head.contains(&0) && controls * 100 <= head.len()
+}
+
+/// Whether text content is an SVG drawing rather than other XML or HTML
+fn is_svg(head: &[u8], extension: &str) -> bool {
+    let text = String::from_utf8_lossy(head);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    extension.eq_ignore_ascii_case("svg")
+        || text.starts_with("<svg")
+        || (text.starts_with("<?xml") && text.contains("<svg"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,12 +430,194 @@ mod tests {
 
     #[test]
     fn test_file_type_from_extension_binary() {
-        assert_eq!(FileType::from_extension("exe"), FileType::Binary);
         assert_eq!(FileType::from_extension("bin"), FileType::Binary);
+        assert_eq!(FileType::from_extension("dat"), FileType::Binary);
         assert_eq!(FileType::from_extension("unknown"), FileType::Binary);
         assert_eq!(FileType::from_extension(""), FileType::Binary);
     }
 
+    #[test]
+    fn test_file_type_from_extension_categories() {
+        assert_eq!(FileType::from_extension("docx"), FileType::Document);
+        assert_eq!(FileType::from_extension("epub"), FileType::Document);
+        assert_eq!(FileType::from_extension("xlsx"), FileType::Spreadsheet);
+        assert_eq!(FileType::from_extension("zip"), FileType::Archive);
+        assert_eq!(FileType::from_extension("dmg"), FileType::Archive);
+        assert_eq!(FileType::from_extension("mp3"), FileType::Audio);
+        assert_eq!(FileType::from_extension("mp4"), FileType::Video);
+        assert_eq!(FileType::from_extension("ttf"), FileType::Font);
+        assert_eq!(FileType::from_extension("exe"), FileType::Executable);
+        assert_eq!(FileType::from_extension("AppImage"), FileType::Executable);
+    }
+
+    #[test]
+    fn test_detect_categories_by_magic_number() {
+        let cases: [(&[u8], FileType); 10] = [
+            (b"ID3\x04\x00\x00\x00\x00\x00\x00", FileType::Audio),
+            (b"RIFF\x24\x00\x00\x00WAVEfmt ", FileType::Audio),
+            (b"\x00\x00\x00\x20ftypM4A \x00\x00\x00\x00", FileType::Audio),
+            (b"\x00\x00\x00\x18ftypmp42\x00\x00\x00\x00", FileType::Video),
+            (b"\x00\x00\x00\x18ftypheic\x00\x00\x00\x00", FileType::Image),
+            (b"\x1a\x45\xdf\xa3\x01\x00\x00\x00", FileType::Video),
+            (b"wOF2\x00\x01\x00\x00", FileType::Font),
+            (b"\x7fELF\x02\x01\x01\x00", FileType::Executable),
+            (b"MZ\x90\x00\x03\x00\x00\x00", FileType::Executable),
+            (b"BZh91AY&SY", FileType::Archive),
+        ];
+        for (head, expected) in cases {
+            assert_eq!(FileType::detect(head, ""), expected, "{:?}", head);
+        }
+    }
+
+    #[test]
+    fn test_detect_icons_and_truetype_fonts_by_header() {
+        // One 16x16 32-bit icon, its data right after the directory
+        let icon: &[u8] = b"\x00\x00\x01\x00\x01\x00\x10\x10\x00\x00\x01\x00\x20\x00\x68\x04\x00\x00\x16\x00\x00\x00";
+        // Twelve tables: searchRange 128, entrySelector 3, rangeShift 64
+        let font: &[u8] = b"\x00\x01\x00\x00\x00\x0c\x00\x80\x00\x03\x00\x40OS/2";
+        assert_eq!(FileType::detect(icon, ""), FileType::Image);
+        assert_eq!(FileType::detect(font, ""), FileType::Font);
+
+        // Data files that merely start with the same few bytes
+        let zeros: &[u8] = &[
+            0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let counter: &[u8] = b"\x00\x01\x00\x00\x00\x02\x00\x00\x00\x03\x00\x00\x00\x04";
+        assert_eq!(FileType::from_magic(zeros), None);
+        assert_eq!(FileType::from_magic(counter), None);
+        assert_eq!(FileType::detect(zeros, "dat"), FileType::Binary);
+        assert_eq!(FileType::detect(counter, ""), FileType::Binary);
+    }
+
+    #[test]
+    fn test_detect_zip_based_formats() {
+        let docx = b"PK\x03\x04\x14\x00\x06\x00[Content_Types].xml...word/document.xml";
+        let xlsx = b"PK\x03\x04\x14\x00\x06\x00[Content_Types].xml...xl/workbook.xml";
+        let odt = b"PK\x03\x04\x0a\x00\x00\x00mimetypeapplication/vnd.oasis.opendocument.text";
+        let plain = b"PK\x03\x04\x14\x00\x08\x00notes.txt";
+
+        assert_eq!(FileType::detect(docx, ""), FileType::Document);
+        assert_eq!(FileType::detect(xlsx, ""), FileType::Spreadsheet);
+        assert_eq!(FileType::detect(odt, ""), FileType::Document);
+        assert_eq!(FileType::detect(plain, ""), FileType::Archive);
+        // A compatible extension is more specific than the zip signature
+        assert_eq!(FileType::detect(plain, "xlsx"), FileType::Spreadsheet);
+        assert_eq!(FileType::detect(plain, "apk"), FileType::Executable);
+        // An incompatible one is not
+        assert_eq!(FileType::detect(plain, "mp3"), FileType::Archive);
+        assert_eq!(FileType::detect(b"OggS\x00\x02", "ogv"), FileType::Video);
+    }
+
+    #[test]
+    fn test_detect_by_magic_number() {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+        assert_eq!(FileType::detect(png, "jpg"), FileType::Image);
+        assert_eq!(FileType::detect(png, "txt"), FileType::Image);
+        assert_eq!(FileType::detect(b"\xff\xd8\xff\xe0", ""), FileType::Image);
+        assert_eq!(
+            FileType::detect(b"RIFF\x24\x00\x00\x00WEBPVP8 ", "bin"),
+            FileType::Image
+        );
+        assert_eq!(
+            FileType::detect(b"BM\x36\x00\x0c\x00\x00\x00\x00\x00\x36", ""),
+            FileType::Image
+        );
+        assert_eq!(
+            FileType::detect(b"%PDF-1.7\n%\xe2\xe3", "txt"),
+            FileType::Pdf
+        );
+        assert_eq!(
+            FileType::detect(b"PK\x03\x04\x14\x00", "txt"),
+            FileType::Archive
+        );
+        assert_eq!(
+            FileType::detect(b"\x1f\x8b\x08\x00", "md"),
+            FileType::Archive
+        );
+    }
+
+    #[test]
+    fn test_detect_text_without_extension() {
+        assert_eq!(
+            FileType::detect(b"# Project\n\nUsage...\n", ""),
+            FileType::Text
+        );
+        assert_eq!(
+            FileType::detect(b"all:\n\tcargo build\n", ""),
+            FileType::Text
+        );
+        assert_eq!(
+            FileType::detect(b"#!/bin/sh\necho hi\n", ""),
+            FileType::Text
+        );
+        assert_eq!(
+            FileType::detect(b"date,amount\n2024-01-01,12.50\n", "csv"),
+            FileType::Text
+        );
+        assert_eq!(
+            FileType::detect("caf\u{e9} \u{1b}[1mbold\u{1b}[0m\n".as_bytes(), "log"),
+            FileType::Text
+        );
+        // Text in an image's clothing
+        assert_eq!(
+            FileType::detect(b"not really a photo", "jpg"),
+            FileType::Text
+        );
+    }
+
+    #[test]
+    fn test_detect_svg() {
+        let svg = b"<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+        assert_eq!(FileType::detect(svg, ""), FileType::Image);
+        assert_eq!(
+            FileType::detect(b"<!-- logo -->\n<svg/>", "svg"),
+            FileType::Image
+        );
+        // An HTML page with an inline drawing is still text
+        assert_eq!(
+            FileType::detect(b"<html><body><svg/></body></html>", "html"),
+            FileType::Text
+        );
+    }
+
+    #[test]
+    fn test_detect_falls_back_to_extension() {
+        // Nothing to look at
+        assert_eq!(FileType::detect(b"", "png"), FileType::Image);
+        assert_eq!(FileType::detect(b"", "rs"), FileType::Text);
+        // Not UTF-8, but no NUL bytes either
+        assert_eq!(FileType::detect(b"caf\xe9 cr\xe8me", "txt"), FileType::Text);
+        assert_eq!(FileType::detect(b"\xde\xad\xbe\xef", "pdf"), FileType::Pdf);
+        // NUL bytes rule out text
+        assert_eq!(
+            FileType::detect(b"\x01\x00\x02\x00", "txt"),
+            FileType::Binary
+        );
+    }
+
+    #[test]
+    fn test_extension_types_parse() {
+        let types =
+            ExtensionTypes::parse([("vue", "text"), (".KRA", "Image"), ("dat", "binary")]).unwrap();
+
+        assert_eq!(types.get("vue"), Some(FileType::Text));
+        assert_eq!(types.get("kra"), Some(FileType::Image));
+        assert_eq!(types.get("Dat"), Some(FileType::Binary));
+        assert_eq!(types.get("rs"), None);
+    }
+
+    #[test]
+    fn test_extension_types_reject_unknown_names() {
+        let err = ExtensionTypes::parse([("vue", "txet")]).unwrap_err();
+        assert!(err.contains("\"txet\""));
+        assert!(err.contains(".vue"));
+        assert!(err.contains("text, document, spreadsheet"));
+
+        assert!(ExtensionTypes::parse([("", "text")]).is_err());
+        assert!(ExtensionTypes::parse([("d", "directory")]).is_err());
+        assert!(ExtensionTypes::parse([("a/b", "text")]).is_err());
+    }
+
     #[test]
     fn test_file_type_case_insensitive() {
         assert_eq!(FileType::from_extension("PNG"), FileType::Image);
@@ -0,0 +1,170 @@
+use super::Decision;
+
+/// A decision made on the file at `index`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub index: usize,
+    pub decision: Decision,
+}
+
+/// Linear undo/redo history of decisions.
+///
+/// Entries before `applied` are in effect; the ones after it were undone and
+/// can be redone until a new decision is recorded.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+    applied: usize,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a new decision, discarding anything that could have been redone
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.truncate(self.applied);
+        self.entries.push(entry);
+        self.applied += 1;
+    }
+
+    /// The most recent decision still in effect
+    pub fn last_applied(&self) -> Option<&HistoryEntry> {
+        self.applied.checked_sub(1).map(|i| &self.entries[i])
+    }
+
+    /// The next decision that `redo` would re-apply
+    pub fn next_redo(&self) -> Option<&HistoryEntry> {
+        self.entries.get(self.applied)
+    }
+
+    /// Marks the most recent decision as undone
+    pub fn undo(&mut self) -> Option<HistoryEntry> {
+        let entry = self.last_applied()?.clone();
+        self.applied -= 1;
+        Some(entry)
+    }
+
+    /// Marks the next undone decision as applied again
+    pub fn redo(&mut self) -> Option<HistoryEntry> {
+        let entry = self.next_redo()?.clone();
+        self.applied += 1;
+        Some(entry)
+    }
+
+    /// Drops a single applied decision, leaving the ones after it in effect
+    pub fn remove(&mut self, position: usize) -> Option<HistoryEntry> {
+        if position >= self.applied {
+            return None;
+        }
+        self.applied -= 1;
+        Some(self.entries.remove(position))
+    }
+
+    /// Decisions currently in effect, oldest first
+    pub fn applied(&self) -> &[HistoryEntry] {
+        &self.entries[..self.applied]
+    }
+
+    /// Every decision in the timeline, including undone ones that can be redone
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Number of decisions in effect
+    pub fn len(&self) -> usize {
+        self.applied
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.applied == 0
+    }
+
+    /// Whether the entry at `position` is currently in effect
+    pub fn is_applied(&self, position: usize) -> bool {
+        position < self.applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(index: usize, decision: Decision) -> HistoryEntry {
+        HistoryEntry { index, decision }
+    }
+
+    #[test]
+    fn test_history_push_and_undo() {
+        let mut history = History::new();
+        history.push(entry(0, Decision::Keep));
+        history.push(entry(1, Decision::Trash));
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.undo(), Some(entry(1, Decision::Trash)));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.entries().len(), 2);
+    }
+
+    #[test]
+    fn test_history_undo_empty() {
+        let mut history = History::new();
+        assert!(history.undo().is_none());
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_history_redo() {
+        let mut history = History::new();
+        history.push(entry(0, Decision::Keep));
+        history.push(entry(1, Decision::Trash));
+        history.undo();
+        history.undo();
+
+        assert_eq!(history.redo(), Some(entry(0, Decision::Keep)));
+        assert_eq!(history.redo(), Some(entry(1, Decision::Trash)));
+        assert!(history.redo().is_none());
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn test_history_push_discards_redo() {
+        let mut history = History::new();
+        history.push(entry(0, Decision::Keep));
+        history.push(entry(1, Decision::Trash));
+        history.undo();
+
+        history.push(entry(2, Decision::Keep));
+
+        assert!(history.next_redo().is_none());
+        assert_eq!(
+            history.applied(),
+            &[entry(0, Decision::Keep), entry(2, Decision::Keep)]
+        );
+    }
+
+    #[test]
+    fn test_history_remove_keeps_later_entries() {
+        let mut history = History::new();
+        history.push(entry(0, Decision::Trash));
+        history.push(entry(1, Decision::Keep));
+        history.push(entry(2, Decision::Trash));
+
+        assert_eq!(history.remove(0), Some(entry(0, Decision::Trash)));
+        assert_eq!(
+            history.applied(),
+            &[entry(1, Decision::Keep), entry(2, Decision::Trash)]
+        );
+    }
+
+    #[test]
+    fn test_history_remove_rejects_undone_entries() {
+        let mut history = History::new();
+        history.push(entry(0, Decision::Trash));
+        history.undo();
+
+        assert!(history.remove(0).is_none());
+        assert_eq!(history.entries().len(), 1);
+    }
+}
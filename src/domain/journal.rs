@@ -0,0 +1,414 @@
+//! Crash-safe record of files that have been moved into staging
+//!
+//! Every staged file is written to `journal.json` inside the session's staging
+//! directory before the file is moved, so a panic, SIGKILL or power loss never
+//! leaves a file without a record of where it came from. A running session
+//! holds an exclusive lock on `session.lock`; a journal whose lock can be taken
+//! belongs to a session that is no longer running.
+
+use super::decision_engine::{move_back, send_to_trash, system_trash};
+use super::staging;
+use super::Decision;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE: &str = "journal.json";
+const LOCK_FILE: &str = "session.lock";
+
+/// Directory that holds one staging directory per session (~/.local/share/fswp/sessions)
+pub fn sessions_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("fswp").join("sessions"))
+}
+
+/// A single staged file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub original_path: PathBuf,
+    pub staged_path: PathBuf,
+    pub size: u64,
+    pub modified_date: DateTime<Utc>,
+    pub decision: Decision,
+}
+
+/// On-disk list of the files a session currently has in staging
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Journal {
+    pub created: DateTime<Utc>,
+    pub entries: Vec<JournalEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Journal {
+    /// Creates an empty journal for the given staging directory without writing it
+    pub fn new(staging_dir: &Path) -> Self {
+        Self {
+            created: Utc::now(),
+            entries: Vec::new(),
+            path: staging_dir.join(JOURNAL_FILE),
+        }
+    }
+
+    /// Loads the journal stored in a staging directory
+    pub fn load(staging_dir: &Path) -> io::Result<Self> {
+        let path = staging_dir.join(JOURNAL_FILE);
+        let contents = fs::read_to_string(&path)?;
+        let mut journal: Journal = serde_json::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        journal.path = path;
+        Ok(journal)
+    }
+
+    /// Atomically replaces the journal on disk and syncs it
+    pub fn save(&self) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        let tmp_path = self.path.with_extension("json.tmp");
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        // Persist the rename itself
+        if let Some(parent) = self.path.parent() {
+            if let Ok(dir) = File::open(parent) {
+                dir.sync_all().ok();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the journal file from disk
+    pub fn remove(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a staged file and persists the journal
+    pub fn push(&mut self, entry: JournalEntry) -> io::Result<()> {
+        self.entries.push(entry);
+        self.save()
+    }
+
+    /// Drops the entry for a staged path and persists the journal
+    pub fn remove_entry(&mut self, staged_path: &Path) -> io::Result<()> {
+        self.entries.retain(|e| e.staged_path != staged_path);
+        self.save()
+    }
+}
+
+/// Takes the session lock for a staging directory, failing if another process holds it
+pub fn lock_session(staging_dir: &Path) -> io::Result<File> {
+    let file = File::create(staging_dir.join(LOCK_FILE))?;
+    file.try_lock().map_err(|e| match e {
+        fs::TryLockError::WouldBlock => io::Error::new(
+            io::ErrorKind::WouldBlock,
+            format!("Session is in use: {:?}", staging_dir),
+        ),
+        fs::TryLockError::Error(e) => e,
+    })?;
+    Ok(file)
+}
+
+/// Removes a staging directory once it holds nothing but session bookkeeping
+pub fn remove_session_dir(staging_dir: &Path) {
+    fs::remove_file(staging_dir.join(LOCK_FILE)).ok();
+    fs::remove_dir(staging_dir).ok();
+}
+
+/// Outcome of restoring or committing an orphaned session
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+    /// Original paths of files that were restored or trashed
+    pub completed: Vec<PathBuf>,
+    /// Files that could not be processed, with the reason. These stay in the journal.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// A staging directory left behind by a session that is no longer running
+#[derive(Debug)]
+pub struct OrphanedSession {
+    pub staging_dir: PathBuf,
+    pub journal: Journal,
+    /// Keeps a second fswp from resolving the same session concurrently
+    lock: Option<File>,
+}
+
+impl OrphanedSession {
+    /// Moves every staged file back to its original path
+    pub fn restore(&mut self) -> RecoveryReport {
+        self.process(|entry| move_back(&entry.staged_path, &entry.original_path))
+    }
+
+    /// Carries out the recorded decision for every staged file. Files marked
+    /// for permanent deletion go to the trash too: the session ended before
+    /// their deletion was confirmed. Files waiting for the session archive
+    /// are put back, as the archive was never written.
+    pub fn commit(&mut self) -> RecoveryReport {
+        self.commit_with(system_trash)
+    }
+
+    /// Like `commit`, with `trash` standing in for the system trash
+    pub fn commit_with(&mut self, trash: impl Fn(&Path) -> io::Result<()>) -> RecoveryReport {
+        self.process(|entry| match entry.decision {
+            Decision::Trash | Decision::Delete => {
+                send_to_trash(&entry.staged_path, &entry.original_path, &trash)
+            }
+            Decision::Archive => move_back(&entry.staged_path, &entry.original_path),
+            Decision::Keep | Decision::Move { .. } | Decision::Rename { .. } => Ok(()),
+        })
+    }
+
+    fn process<F>(&mut self, mut action: F) -> RecoveryReport
+    where
+        F: FnMut(&JournalEntry) -> io::Result<()>,
+    {
+        let mut report = RecoveryReport::default();
+        let mut remaining = Vec::new();
+
+        for entry in self.journal.entries.drain(..) {
+            // A missing staged file means the session crashed between writing the
+            // journal and moving the file (or the reverse), so the file never left
+            if !entry.staged_path.exists() {
+                if !entry.original_path.exists() {
+                    report.failed.push((
+                        entry.original_path.clone(),
+                        format!("Staged file is missing: {:?}", entry.staged_path),
+                    ));
+                    remaining.push(entry);
+                }
+                continue;
+            }
+
+            match action(&entry) {
+                Ok(()) => {
+                    report.completed.push(entry.original_path.clone());
+                    if let Some(dir) = entry.staged_path.parent() {
+                        if dir != self.staging_dir {
+                            staging::remove_empty_staging_dir(dir);
+                        }
+                    }
+                }
+                Err(e) => {
+                    report
+                        .failed
+                        .push((entry.original_path.clone(), e.to_string()));
+                    remaining.push(entry);
+                }
+            }
+        }
+
+        self.journal.entries = remaining;
+        if self.journal.entries.is_empty() {
+            self.lock.take();
+            self.journal.remove().ok();
+            remove_session_dir(&self.staging_dir);
+        } else {
+            self.journal.save().ok();
+        }
+
+        report
+    }
+
+    /// Total size of the files still staged by this session
+    pub fn staged_size(&self) -> u64 {
+        self.journal.entries.iter().map(|e| e.size).sum()
+    }
+}
+
+/// Finds staging directories under `sessions_dir` whose session is no longer running
+pub fn find_orphaned_sessions(sessions_dir: &Path) -> io::Result<Vec<OrphanedSession>> {
+    let mut sessions = Vec::new();
+
+    if !sessions_dir.exists() {
+        return Ok(sessions);
+    }
+
+    for entry in fs::read_dir(sessions_dir)? {
+        let staging_dir = entry?.path();
+        if !staging_dir.join(JOURNAL_FILE).exists() {
+            continue;
+        }
+
+        // A live session holds the lock; taking it here proves the owner is gone
+        let Ok(lock) = lock_session(&staging_dir) else {
+            continue;
+        };
+
+        if let Ok(journal) = Journal::load(&staging_dir) {
+            sessions.push(OrphanedSession {
+                staging_dir,
+                journal,
+                lock: Some(lock),
+            });
+        }
+    }
+
+    sessions.sort_by_key(|s| s.journal.created);
+    Ok(sessions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn stage_file(staging_dir: &Path, original: &Path, journal: &mut Journal) -> PathBuf {
+        let staged = staging_dir.join(format!("file_{}", journal.entries.len()));
+        journal
+            .push(JournalEntry {
+                original_path: original.to_path_buf(),
+                staged_path: staged.clone(),
+                size: fs::metadata(original).unwrap().len(),
+                modified_date: Utc::now(),
+                decision: Decision::Trash,
+            })
+            .unwrap();
+        fs::rename(original, &staged).unwrap();
+        staged
+    }
+
+    #[test]
+    fn test_journal_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let original = temp_dir.path().join("a.txt");
+        fs::write(&original, b"content").unwrap();
+
+        let mut journal = Journal::new(temp_dir.path());
+        stage_file(temp_dir.path(), &original, &mut journal);
+
+        let loaded = Journal::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded.entries, journal.entries);
+        assert_eq!(loaded.entries[0].size, 7);
+    }
+
+    #[test]
+    fn test_find_orphaned_sessions_skips_locked() {
+        let sessions = TempDir::new().unwrap();
+        let live = sessions.path().join("live");
+        let dead = sessions.path().join("dead");
+        fs::create_dir_all(&live).unwrap();
+        fs::create_dir_all(&dead).unwrap();
+        Journal::new(&live).save().unwrap();
+        Journal::new(&dead).save().unwrap();
+
+        let _lock = lock_session(&live).unwrap();
+
+        let orphans = find_orphaned_sessions(sessions.path()).unwrap();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].staging_dir, dead);
+    }
+
+    #[test]
+    fn test_orphaned_session_restore() {
+        let sessions = TempDir::new().unwrap();
+        let files = TempDir::new().unwrap();
+        let staging_dir = sessions.path().join("session");
+        fs::create_dir_all(&staging_dir).unwrap();
+
+        let original = files.path().join("a.txt");
+        fs::write(&original, b"content").unwrap();
+        let mut journal = Journal::new(&staging_dir);
+        stage_file(&staging_dir, &original, &mut journal);
+        assert!(!original.exists());
+
+        let mut orphans = find_orphaned_sessions(sessions.path()).unwrap();
+        let report = orphans[0].restore();
+
+        assert_eq!(report.completed, vec![original.clone()]);
+        assert!(report.failed.is_empty());
+        assert_eq!(fs::read(&original).unwrap(), b"content");
+        assert!(!staging_dir.exists());
+    }
+
+    #[test]
+    fn test_orphaned_session_restore_keeps_conflicts() {
+        let sessions = TempDir::new().unwrap();
+        let files = TempDir::new().unwrap();
+        let staging_dir = sessions.path().join("session");
+        fs::create_dir_all(&staging_dir).unwrap();
+
+        let original = files.path().join("a.txt");
+        fs::write(&original, b"content").unwrap();
+        let mut journal = Journal::new(&staging_dir);
+        let staged = stage_file(&staging_dir, &original, &mut journal);
+        fs::write(&original, b"replacement").unwrap();
+
+        let mut orphans = find_orphaned_sessions(sessions.path()).unwrap();
+        let report = orphans[0].restore();
+
+        assert_eq!(report.failed.len(), 1);
+        assert!(staged.exists());
+        assert_eq!(Journal::load(&staging_dir).unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_orphaned_session_commit_trashes_unconfirmed_deletes() {
+        let sessions = TempDir::new().unwrap();
+        let files = TempDir::new().unwrap();
+        let staging_dir = sessions.path().join("session");
+        fs::create_dir_all(&staging_dir).unwrap();
+
+        let original = files.path().join("secret.txt");
+        let staged = staging_dir.join("file_0");
+        fs::write(&staged, b"content").unwrap();
+        let mut journal = Journal::new(&staging_dir);
+        journal
+            .push(JournalEntry {
+                original_path: original.clone(),
+                staged_path: staged.clone(),
+                size: 7,
+                modified_date: Utc::now(),
+                decision: Decision::Delete,
+            })
+            .unwrap();
+
+        let trash_dir = TempDir::new().unwrap();
+        let mut orphans = find_orphaned_sessions(sessions.path()).unwrap();
+        let report = orphans[0]
+            .commit_with(|path| fs::rename(path, trash_dir.path().join(path.file_name().unwrap())));
+
+        assert_eq!(report.completed, vec![original.clone()]);
+        assert!(!original.exists());
+        assert_eq!(
+            fs::read(trash_dir.path().join("secret.txt")).unwrap(),
+            b"content"
+        );
+    }
+
+    #[test]
+    fn test_orphaned_session_drops_stale_entries() {
+        let sessions = TempDir::new().unwrap();
+        let files = TempDir::new().unwrap();
+        let staging_dir = sessions.path().join("session");
+        fs::create_dir_all(&staging_dir).unwrap();
+
+        // Crash after the journal write but before the file was moved
+        let original = files.path().join("a.txt");
+        fs::write(&original, b"content").unwrap();
+        let mut journal = Journal::new(&staging_dir);
+        journal
+            .push(JournalEntry {
+                original_path: original.clone(),
+                staged_path: staging_dir.join("file_0"),
+                size: 7,
+                modified_date: Utc::now(),
+                decision: Decision::Trash,
+            })
+            .unwrap();
+
+        let mut orphans = find_orphaned_sessions(sessions.path()).unwrap();
+        let report = orphans[0].commit();
+
+        assert!(report.completed.is_empty());
+        assert!(report.failed.is_empty());
+        assert!(original.exists());
+        assert!(!staging_dir.exists());
+    }
+}
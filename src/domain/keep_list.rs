@@ -0,0 +1,229 @@
+//! Files the user explicitly kept, remembered across runs
+//!
+//! Kept files are stored by absolute path together with the size and modified
+//! date they had (~/.local/share/fswp/kept.json). Discovery leaves them out
+//! until they change, so a directory reviewed last week only shows what is new.
+
+use super::session::absolute;
+use super::{Decision, DecisionEngine, FileEntry};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Where the keep-list is stored (~/.local/share/fswp/kept.json)
+pub fn keep_list_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("fswp").join("kept.json"))
+}
+
+/// What a kept file looked like when it was kept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeptMark {
+    pub size: u64,
+    pub modified_date: DateTime<Utc>,
+    pub kept: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeepList {
+    files: BTreeMap<PathBuf, KeptMark>,
+    /// Paths the user asked to forget this session, which stay unmarked even
+    /// if they were kept earlier in it
+    #[serde(skip)]
+    forgotten: BTreeSet<PathBuf>,
+}
+
+impl KeepList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the keep-list stored at `path`, or an empty one if there is none yet
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Atomically writes the keep-list to `path`
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Remembers `file` as kept in its current state
+    pub fn mark(&mut self, file: &FileEntry) {
+        self.files.insert(
+            absolute(&file.path),
+            KeptMark {
+                size: file.size,
+                modified_date: file.modified_date,
+                kept: Utc::now(),
+            },
+        );
+    }
+
+    /// Forgets the kept mark on `path`. Returns whether there was one.
+    pub fn unmark(&mut self, path: &Path) -> bool {
+        self.files.remove(&absolute(path)).is_some()
+    }
+
+    /// Forgets the kept mark on `path` at the user's request, including a Keep
+    /// decided for it this session. Returns whether there was a mark.
+    pub fn forget(&mut self, path: &Path) -> bool {
+        self.forgotten.insert(absolute(path));
+        self.unmark(path)
+    }
+
+    /// Whether `file` was kept and has not changed since
+    pub fn contains(&self, file: &FileEntry) -> bool {
+        self.files
+            .get(&absolute(&file.path))
+            .is_some_and(|mark| mark.size == file.size && mark.modified_date == file.modified_date)
+    }
+
+    /// Marks every file kept in `engine` and forgets marks on files that got
+    /// any other decision, since they are no longer where they were kept.
+    /// Files forgotten with `forget` are not marked again.
+    pub fn record_decisions(&mut self, engine: &DecisionEngine) {
+        for entry in engine.history.applied() {
+            let file = &engine.files[entry.index];
+            match entry.decision {
+                Decision::Keep if self.forgotten.contains(&absolute(&file.path)) => {}
+                Decision::Keep => self.mark(file),
+                _ => {
+                    self.unmark(&file.path);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::FileType;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, size: u64) -> FileEntry {
+        FileEntry {
+            path: PathBuf::from(path),
+            name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned(),
+            size,
+            modified_date: Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap(),
+            file_type: FileType::Text,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
+        }
+    }
+
+    #[test]
+    fn test_keep_list_contains_until_changed() {
+        let file = entry("/home/me/notes.txt", 10);
+        let mut keep_list = KeepList::new();
+        keep_list.mark(&file);
+
+        assert!(keep_list.contains(&file));
+
+        let mut edited = file.clone();
+        edited.modified_date = Utc.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap();
+        assert!(!keep_list.contains(&edited));
+
+        let mut grown = file.clone();
+        grown.size = 11;
+        assert!(!keep_list.contains(&grown));
+    }
+
+    #[test]
+    fn test_keep_list_unmark() {
+        let file = entry("/home/me/notes.txt", 10);
+        let mut keep_list = KeepList::new();
+        keep_list.mark(&file);
+
+        assert!(keep_list.unmark(&file.path));
+        assert!(!keep_list.contains(&file));
+        assert!(!keep_list.unmark(&file.path));
+    }
+
+    #[test]
+    fn test_keep_list_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("fswp").join("kept.json");
+        assert!(KeepList::load(&path).unwrap().is_empty());
+
+        let mut keep_list = KeepList::new();
+        keep_list.mark(&entry("/home/me/a.txt", 1));
+        keep_list.save(&path).unwrap();
+
+        assert_eq!(KeepList::load(&path).unwrap(), keep_list);
+    }
+
+    #[test]
+    fn test_keep_list_record_decisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let kept_path = temp_dir.path().join("kept.txt");
+        let trashed_path = temp_dir.path().join("trashed.txt");
+        fs::write(&kept_path, b"keep").unwrap();
+        fs::write(&trashed_path, b"trash").unwrap();
+        let kept = FileEntry::from_path(&kept_path).unwrap();
+        let trashed = FileEntry::from_path(&trashed_path).unwrap();
+
+        let mut keep_list = KeepList::new();
+        keep_list.mark(&trashed);
+
+        let mut engine = DecisionEngine::new(vec![kept.clone(), trashed.clone()]);
+        engine.set_dry_run(true);
+        engine.record_decision(0, Decision::Keep).unwrap();
+        engine.record_decision(1, Decision::Trash).unwrap();
+        keep_list.record_decisions(&engine);
+
+        assert!(keep_list.contains(&kept));
+        assert!(!keep_list.contains(&trashed));
+    }
+
+    #[test]
+    fn test_keep_list_forget_outlasts_keep_this_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("draft.txt");
+        fs::write(&path, b"draft").unwrap();
+        let file = FileEntry::from_path(&path).unwrap();
+
+        let mut keep_list = KeepList::new();
+        let mut engine = DecisionEngine::new(vec![file.clone()]);
+        engine.set_dry_run(true);
+        engine.record_decision(0, Decision::Keep).unwrap();
+
+        assert!(!keep_list.forget(&file.path));
+        keep_list.record_decisions(&engine);
+
+        assert!(!keep_list.contains(&file));
+    }
+}
@@ -1,15 +1,39 @@
 // Module declarations
 pub mod app_state;
+pub mod archive;
 pub mod decision;
 pub mod decision_engine;
 pub mod discovery;
+pub mod disk_usage;
+pub mod duplicates;
 pub mod file_entry;
+pub mod file_list;
 pub mod file_type;
+pub mod history;
+pub mod journal;
+pub mod keep_list;
+pub mod rename;
+pub mod session;
+pub mod similar_images;
+pub mod staging;
 
 // Re-exports for convenience
 pub use app_state::AppState;
-pub use decision::{Decision, DecisionStatistics};
+pub use archive::{ArchiveFormat, SessionArchive};
+pub use decision::{CommitReport, Decision, DecisionStatistics};
 pub use decision_engine::DecisionEngine;
-pub use discovery::{discover_files, discover_files_with_options, DiscoveryOptions, SortBy};
+pub use discovery::{
+    compare_files, compile_glob, compile_regex, discover_files, discover_files_with_options,
+    natural_cmp, Discovery, DiscoveryOptions, SortBy,
+};
+pub use disk_usage::{biggest_first, DiskUsage};
+pub use duplicates::{find_duplicates, wasted_bytes, KeepRule};
 pub use file_entry::FileEntry;
-pub use file_type::FileType;
+pub use file_list::{load_listed_files, parse_file_list, ListedFiles};
+pub use file_type::{ExtensionTypes, FileType};
+pub use history::{History, HistoryEntry};
+pub use journal::{find_orphaned_sessions, sessions_dir, OrphanedSession, RecoveryReport};
+pub use keep_list::{keep_list_path, KeepList};
+pub use rename::{suggest_names, validate_file_name};
+pub use session::{saved_sessions_dir, DecidedFile, SavedSession};
+pub use similar_images::{find_similar_images, hash_distance, ImageHash, DEFAULT_MAX_DISTANCE};
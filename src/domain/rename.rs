@@ -0,0 +1,239 @@
+//! File name validation and suggested names for the rename decision
+
+use super::{FileEntry, FileType};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+/// Checks that `name` is a plain file name that can replace the current one
+pub fn validate_file_name(name: &str) -> io::Result<()> {
+    let invalid = |reason: &str| Err(io::Error::new(io::ErrorKind::InvalidInput, reason));
+
+    if name.trim().is_empty() {
+        return invalid("File name cannot be empty");
+    }
+    if name == "." || name == ".." {
+        return invalid("File name cannot be '.' or '..'");
+    }
+    if name.contains('/') || name.contains(std::path::MAIN_SEPARATOR) || name.contains('\0') {
+        return invalid("File name cannot contain path separators");
+    }
+    Ok(())
+}
+
+/// Suggested new names for a file, best first, never including its current name.
+///
+/// Images with an EXIF capture date get date-based names from it; every file
+/// gets one from its modified date and one with copy suffixes like " (3)" removed.
+pub fn suggest_names(entry: &FileEntry) -> Vec<String> {
+    let name = Path::new(&entry.name);
+    let stem = name
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| entry.name.clone());
+    let extension = name
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let base = strip_copy_suffix(&stem);
+
+    let mut suggestions = Vec::new();
+
+    if entry.file_type == FileType::Image {
+        if let Some(taken) = exif_date(&entry.path) {
+            suggestions.push(format!("{}{}", taken.format("%Y-%m-%d_%H%M%S"), extension));
+            suggestions.push(format!(
+                "{} {}{}",
+                taken.format("%Y-%m-%d"),
+                base,
+                extension
+            ));
+        }
+    }
+
+    let modified = entry.modified_date.with_timezone(&Local);
+    suggestions.push(format!(
+        "{} {}{}",
+        modified.format("%Y-%m-%d"),
+        base,
+        extension
+    ));
+    suggestions.push(format!("{}{}", base, extension));
+
+    let mut unique = Vec::new();
+    for suggestion in suggestions {
+        if suggestion != entry.name && !unique.contains(&suggestion) {
+            unique.push(suggestion);
+        }
+    }
+    unique
+}
+
+/// `name` with " (n)" inserted before the extension, for avoiding collisions
+pub(crate) fn numbered_name(name: &str, n: usize) -> String {
+    let path = Path::new(name);
+    match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(extension)) => format!(
+            "{} ({}).{}",
+            stem.to_string_lossy(),
+            n,
+            extension.to_string_lossy()
+        ),
+        _ => format!("{} ({})", name, n),
+    }
+}
+
+/// Removes the suffix browsers and file managers add to duplicates:
+/// "document(3)", "IMG_2231 (1)", "report - Copy", "notes copy"
+fn strip_copy_suffix(stem: &str) -> &str {
+    let trimmed = stem.trim_end();
+
+    if let Some(inner) = trimmed.strip_suffix(')') {
+        if let Some(open) = inner.rfind('(') {
+            let digits = &inner[open + 1..];
+            let base = inner[..open].trim_end();
+            if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) && !base.is_empty()
+            {
+                return base;
+            }
+        }
+    }
+
+    for suffix in [" - Copy", " copy"] {
+        if let Some(base) = trimmed.strip_suffix(suffix) {
+            if !base.is_empty() {
+                return base;
+            }
+        }
+    }
+
+    trimmed
+}
+
+/// When the photo was taken, from its EXIF DateTimeOriginal (or DateTime) tag
+fn exif_date(path: &Path) -> Option<NaiveDateTime> {
+    let file = File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+    let field = exif
+        .get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)
+        .or_else(|| exif.get_field(exif::Tag::DateTime, exif::In::PRIMARY))?;
+
+    let exif::Value::Ascii(ref values) = field.value else {
+        return None;
+    };
+    let date = exif::DateTime::from_ascii(values.first()?).ok()?;
+
+    NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())?.and_hms_opt(
+        date.hour.into(),
+        date.minute.into(),
+        date.second.into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn entry(path: PathBuf, file_type: FileType) -> FileEntry {
+        FileEntry {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            path,
+            size: 0,
+            modified_date: Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap(),
+            file_type,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
+        }
+    }
+
+    /// A JPEG holding nothing but an EXIF segment with DateTimeOriginal
+    fn jpeg_with_exif_date(date: &str) -> Vec<u8> {
+        let field = exif::Field {
+            tag: exif::Tag::DateTimeOriginal,
+            ifd_num: exif::In::PRIMARY,
+            value: exif::Value::Ascii(vec![date.as_bytes().to_vec()]),
+        };
+        let mut writer = exif::experimental::Writer::new();
+        writer.push_field(&field);
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn test_validate_file_name() {
+        assert!(validate_file_name("report.pdf").is_ok());
+        assert!(validate_file_name("").is_err());
+        assert!(validate_file_name("   ").is_err());
+        assert!(validate_file_name("..").is_err());
+        assert!(validate_file_name("a/b.txt").is_err());
+    }
+
+    #[test]
+    fn test_numbered_name() {
+        assert_eq!(numbered_name("photo.jpg", 2), "photo (2).jpg");
+        assert_eq!(numbered_name("Makefile", 1), "Makefile (1)");
+        assert_eq!(numbered_name("archive.tar.gz", 1), "archive.tar (1).gz");
+    }
+
+    #[test]
+    fn test_strip_copy_suffix() {
+        assert_eq!(strip_copy_suffix("document(3)"), "document");
+        assert_eq!(strip_copy_suffix("IMG_2231 (1)"), "IMG_2231");
+        assert_eq!(strip_copy_suffix("report - Copy"), "report");
+        assert_eq!(strip_copy_suffix("(1)"), "(1)");
+        assert_eq!(strip_copy_suffix("notes (draft)"), "notes (draft)");
+    }
+
+    #[test]
+    fn test_suggest_names_from_modified_date() {
+        let file = entry(PathBuf::from("/tmp/document(3).pdf"), FileType::Pdf);
+        let modified = file.modified_date.with_timezone(&Local).format("%Y-%m-%d");
+
+        let suggestions = suggest_names(&file);
+
+        assert_eq!(
+            suggestions,
+            vec![
+                format!("{} document.pdf", modified),
+                "document.pdf".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggest_names_skips_current_name() {
+        let file = entry(PathBuf::from("/tmp/notes.txt"), FileType::Text);
+
+        assert!(!suggest_names(&file).contains(&"notes.txt".to_string()));
+    }
+
+    #[test]
+    fn test_suggest_names_from_exif_date() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("IMG_2231.jpg");
+        fs::write(&path, jpeg_with_exif_date("2023:07:04 18:30:05")).unwrap();
+
+        let suggestions = suggest_names(&entry(path, FileType::Image));
+
+        assert_eq!(suggestions[0], "2023-07-04_183005.jpg");
+        assert_eq!(suggestions[1], "2023-07-04 IMG_2231.jpg");
+    }
+}
@@ -0,0 +1,315 @@
+//! Review progress saved between runs
+//!
+//! Large directories are rarely reviewed in one sitting, so when fswp quits
+//! before every file has a decision it writes one JSON file per reviewed
+//! directory under the data dir (~/.local/share/fswp/reviews). It records each
+//! decided file with the size and modified date it had, and the file the cursor
+//! was on, so the next run can skip what was already kept and pick up there.
+
+use super::{Decision, DecisionEngine, FileEntry};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Directory that holds one saved session per reviewed directory (~/.local/share/fswp/reviews)
+pub fn saved_sessions_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("fswp").join("reviews"))
+}
+
+/// A file that had a decision when the session was saved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecidedFile {
+    /// Where the file is now: its new path when it was moved or renamed
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_date: DateTime<Utc>,
+    pub decision: Decision,
+}
+
+impl DecidedFile {
+    /// Whether the file was left on disk: kept, moved or renamed
+    pub fn was_kept(&self) -> bool {
+        matches!(
+            self.decision,
+            Decision::Keep | Decision::Move { .. } | Decision::Rename { .. }
+        )
+    }
+
+    /// Whether `file` is this file, unchanged since it was decided
+    fn is_unchanged(&self, file: &FileEntry) -> bool {
+        absolute(&file.path) == self.path
+            && file.size == self.size
+            && file.modified_date == self.modified_date
+    }
+}
+
+/// Progress through one reviewed directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSession {
+    /// The reviewed directory, canonicalized
+    pub directory: PathBuf,
+    pub saved: DateTime<Utc>,
+    /// File the cursor was on
+    pub cursor: Option<PathBuf>,
+    pub decided: Vec<DecidedFile>,
+}
+
+impl SavedSession {
+    pub fn new(directory: &Path) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+            saved: Utc::now(),
+            cursor: None,
+            decided: Vec::new(),
+        }
+    }
+
+    /// Where the session for `directory` is stored inside `sessions_dir`
+    pub fn file_path(sessions_dir: &Path, directory: &Path) -> PathBuf {
+        sessions_dir.join(format!("{:016x}.json", directory_key(directory)))
+    }
+
+    /// Loads the saved session for `directory`, if there is one
+    pub fn load(sessions_dir: &Path, directory: &Path) -> io::Result<Option<Self>> {
+        let contents = match fs::read_to_string(Self::file_path(sessions_dir, directory)) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let session: SavedSession = serde_json::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        // Two directories whose keys collide must not share progress
+        if session.directory != directory {
+            return Ok(None);
+        }
+        Ok(Some(session))
+    }
+
+    /// Atomically writes the session to `sessions_dir`
+    pub fn save(&self, sessions_dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(sessions_dir)?;
+        let path = Self::file_path(sessions_dir, &self.directory);
+        let tmp_path = path.with_extension("json.tmp");
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)
+    }
+
+    /// Deletes the saved session for `directory`, if there is one
+    pub fn remove(sessions_dir: &Path, directory: &Path) -> io::Result<()> {
+        match fs::remove_file(Self::file_path(sessions_dir, directory)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a decided file, replacing any earlier record for the same path
+    pub fn record(&mut self, decided: DecidedFile) {
+        self.decided.retain(|d| d.path != decided.path);
+        self.decided.push(decided);
+    }
+
+    /// Records every decision currently in effect in `engine`
+    pub fn record_decisions(&mut self, engine: &DecisionEngine) {
+        for entry in engine.history.applied() {
+            let file = &engine.files[entry.index];
+            let path = engine
+                .moved_path(entry.index)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| file.path.clone());
+
+            self.record(DecidedFile {
+                path: absolute(&path),
+                size: file.size,
+                modified_date: file.modified_date,
+                decision: entry.decision.clone(),
+            });
+        }
+    }
+
+    /// Remembers the file the cursor is on
+    pub fn record_cursor(&mut self, file: Option<&FileEntry>) {
+        self.cursor = file.map(|f| absolute(&f.path));
+    }
+
+    /// Number of recorded files that are still on disk
+    pub fn kept_count(&self) -> usize {
+        self.decided.iter().filter(|d| d.was_kept()).count()
+    }
+
+    /// Drops files that were kept, moved or renamed and have not changed since
+    pub fn skip_decided(&self, files: Vec<FileEntry>) -> Vec<FileEntry> {
+        files
+            .into_iter()
+            .filter(|file| {
+                !self
+                    .decided
+                    .iter()
+                    .any(|d| d.was_kept() && d.is_unchanged(file))
+            })
+            .collect()
+    }
+
+    /// Index of the file the cursor was on, if it is among `files`
+    pub fn cursor_position(&self, files: &[FileEntry]) -> Option<usize> {
+        self.cursor
+            .as_ref()
+            .and_then(|cursor| files.iter().position(|f| &absolute(&f.path) == cursor))
+    }
+}
+
+/// `path` made absolute without touching the filesystem, so runs started as
+/// `fswp .` and `fswp ~/Downloads` record the same files the same way
+pub(crate) fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Stable FNV-1a hash of a directory path, used as its session file name
+fn directory_key(directory: &Path) -> u64 {
+    directory
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::FileType;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, size: u64) -> FileEntry {
+        FileEntry {
+            path: PathBuf::from(path),
+            name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned(),
+            size,
+            modified_date: Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap(),
+            file_type: FileType::Text,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
+        }
+    }
+
+    fn decided(file: &FileEntry, decision: Decision) -> DecidedFile {
+        DecidedFile {
+            path: file.path.clone(),
+            size: file.size,
+            modified_date: file.modified_date,
+            decision,
+        }
+    }
+
+    #[test]
+    fn test_saved_session_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let directory = Path::new("/home/me/Downloads");
+        let mut session = SavedSession::new(directory);
+        session.cursor = Some(PathBuf::from("/home/me/Downloads/b.txt"));
+        session.record(decided(
+            &entry("/home/me/Downloads/a.txt", 1),
+            Decision::Keep,
+        ));
+
+        session.save(temp_dir.path()).unwrap();
+        let loaded = SavedSession::load(temp_dir.path(), directory)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(loaded.cursor, session.cursor);
+        assert_eq!(loaded.decided, session.decided);
+
+        SavedSession::remove(temp_dir.path(), directory).unwrap();
+        assert!(SavedSession::load(temp_dir.path(), directory)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_saved_session_is_keyed_by_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        SavedSession::new(Path::new("/a"))
+            .save(temp_dir.path())
+            .unwrap();
+
+        assert!(SavedSession::load(temp_dir.path(), Path::new("/b"))
+            .unwrap()
+            .is_none());
+        assert_ne!(
+            SavedSession::file_path(temp_dir.path(), Path::new("/a")),
+            SavedSession::file_path(temp_dir.path(), Path::new("/b"))
+        );
+    }
+
+    #[test]
+    fn test_skip_decided_keeps_changed_files() {
+        let kept = entry("/d/kept.txt", 10);
+        let changed = entry("/d/changed.txt", 10);
+        let trashed = entry("/d/restored.txt", 10);
+        let fresh = entry("/d/new.txt", 10);
+
+        let mut session = SavedSession::new(Path::new("/d"));
+        session.record(decided(&kept, Decision::Keep));
+        session.record(decided(&changed, Decision::Keep));
+        session.record(decided(&trashed, Decision::Trash));
+
+        let mut grown = changed.clone();
+        grown.size = 20;
+        let remaining = session.skip_decided(vec![kept, grown, trashed, fresh]);
+
+        let names: Vec<_> = remaining.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["changed.txt", "restored.txt", "new.txt"]);
+    }
+
+    #[test]
+    fn test_record_decisions_uses_new_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("IMG_1.jpg");
+        fs::write(&path, b"photo").unwrap();
+        let file = FileEntry::from_path(&path).unwrap();
+
+        let mut engine = DecisionEngine::new(vec![file]);
+        engine
+            .record_decision(
+                0,
+                Decision::Rename {
+                    name: "beach.jpg".to_string(),
+                },
+            )
+            .unwrap();
+
+        let mut session = SavedSession::new(temp_dir.path());
+        session.record_decisions(&engine);
+
+        assert_eq!(session.decided[0].path, temp_dir.path().join("beach.jpg"));
+        assert_eq!(session.kept_count(), 1);
+    }
+
+    #[test]
+    fn test_cursor_position() {
+        let files = vec![entry("/d/a.txt", 1), entry("/d/b.txt", 1)];
+        let mut session = SavedSession::new(Path::new("/d"));
+        assert_eq!(session.cursor_position(&files), None);
+
+        session.cursor = Some(PathBuf::from("/d/b.txt"));
+        assert_eq!(session.cursor_position(&files), Some(1));
+
+        session.cursor = Some(PathBuf::from("/d/gone.txt"));
+        assert_eq!(session.cursor_position(&files), None);
+    }
+}
@@ -0,0 +1,319 @@
+//! Finding images that look alike
+//!
+//! Resized, re-encoded and burst-shot copies of a photo differ byte for byte,
+//! so they are compared by perceptual hash instead: a 64-bit fingerprint of a
+//! tiny grayscale version of the image, in which similar images differ in only
+//! a few bits. Images whose hashes are within a Hamming distance of each other
+//! end up in the same group.
+
+use super::duplicates::sort_groups;
+use super::{FileEntry, FileType};
+use image::imageops::FilterType;
+use image::{DynamicImage, GrayImage, ImageReader};
+use std::f64::consts::PI;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::thread;
+
+/// Default number of hash bits (out of 64) two similar images may differ in
+pub const DEFAULT_MAX_DISTANCE: u32 = 10;
+
+/// Perceptual hash algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageHash {
+    /// aHash: which pixels of an 8x8 thumbnail are brighter than average.
+    /// Fastest, but thrown off by brightness and contrast changes.
+    Average,
+    /// dHash: whether each pixel of a 9x8 thumbnail is brighter than its
+    /// right neighbour. Fast and robust to resizing and re-encoding.
+    #[default]
+    Difference,
+    /// pHash: the lowest frequencies of a 32x32 thumbnail's cosine transform.
+    /// Slowest, and the most robust to edits.
+    Perceptual,
+}
+
+impl ImageHash {
+    /// Hashes `image` into 64 bits
+    pub fn hash(self, image: &DynamicImage) -> u64 {
+        match self {
+            ImageHash::Average => {
+                let pixels = thumbnail(image, 8, 8).into_raw();
+                let mean = pixels.iter().map(|&p| u32::from(p)).sum::<u32>() / 64;
+                bits(pixels.iter().map(|&p| u32::from(p) > mean))
+            }
+            ImageHash::Difference => {
+                let thumb = thumbnail(image, 9, 8);
+                bits((0..8).flat_map(|y| {
+                    let thumb = &thumb;
+                    (0..8).map(move |x| thumb.get_pixel(x, y)[0] < thumb.get_pixel(x + 1, y)[0])
+                }))
+            }
+            ImageHash::Perceptual => {
+                let coefficients = low_frequencies(&thumbnail(image, 32, 32));
+                // The first coefficient is the overall brightness, not structure
+                let mean = coefficients[1..].iter().sum::<f64>() / 63.0;
+                bits(coefficients.iter().map(|&c| c > mean))
+            }
+        }
+    }
+}
+
+/// Number of bits in which two hashes differ
+pub fn hash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Groups the images among `files` whose hashes are at most `max_distance`
+/// bits apart. Similarity chains: if A is close to B and B to C, all three
+/// share a group. Images that cannot be decoded are left out.
+pub fn find_similar_images(
+    files: Vec<FileEntry>,
+    algorithm: ImageHash,
+    max_distance: u32,
+) -> Vec<Vec<FileEntry>> {
+    let images: Vec<FileEntry> = files
+        .into_iter()
+        .filter(|f| f.file_type == FileType::Image)
+        .collect();
+    let hashes = hash_images(&images, algorithm);
+
+    // Union-find over every pair of hashed images
+    let mut parent: Vec<usize> = (0..images.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for (i, a) in hashes.iter().enumerate() {
+        let Some(a) = a else { continue };
+        for (j, b) in hashes.iter().enumerate().skip(i + 1) {
+            if b.is_some_and(|b| hash_distance(*a, b) <= max_distance) {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                parent[rj] = ri;
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<FileEntry>> = (0..images.len()).map(|_| Vec::new()).collect();
+    for (i, image) in images.into_iter().enumerate() {
+        let r = root(&mut parent, i);
+        clusters[r].push(image);
+    }
+
+    let mut groups: Vec<Vec<FileEntry>> = clusters.into_iter().filter(|g| g.len() > 1).collect();
+    sort_groups(&mut groups);
+    groups
+}
+
+/// Hashes every image, decoding them on all available cores
+fn hash_images(images: &[FileEntry], algorithm: ImageHash) -> Vec<Option<u64>> {
+    hash_each(images, |file| {
+        load(&file.path).map(|img| algorithm.hash(&img))
+    })
+}
+
+/// Runs `hash` on every file across all available cores. The result lines up
+/// with `files`: a file whose hash fails or panics gets None in its place.
+fn hash_each<F>(files: &[FileEntry], hash: F) -> Vec<Option<u64>>
+where
+    F: Fn(&FileEntry) -> Option<u64> + Sync,
+{
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = files.len().div_ceil(threads).max(1);
+    let hash = &hash;
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let worker = scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|file| {
+                            // A decoder bug on one file must not cost the rest
+                            panic::catch_unwind(AssertUnwindSafe(|| hash(file)))
+                                .ok()
+                                .flatten()
+                        })
+                        .collect::<Vec<_>>()
+                });
+                (worker, chunk.len())
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|(worker, len)| worker.join().unwrap_or_else(|_| vec![None; len]))
+            .collect()
+    })
+}
+
+/// Decodes the image at `path` by its contents rather than its extension
+fn load(path: &Path) -> Option<DynamicImage> {
+    ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .decode()
+        .ok()
+}
+
+/// `image` shrunk to `width` x `height` grayscale pixels
+fn thumbnail(image: &DynamicImage, width: u32, height: u32) -> GrayImage {
+    image
+        .resize_exact(width, height, FilterType::Triangle)
+        .to_luma8()
+}
+
+/// Packs up to 64 booleans into a hash, first one in the highest bit
+fn bits(values: impl Iterator<Item = bool>) -> u64 {
+    values.fold(0, |hash, bit| (hash << 1) | u64::from(bit))
+}
+
+/// The top-left 8x8 coefficients of the 2D DCT-II of a 32x32 image
+fn low_frequencies(image: &GrayImage) -> [f64; 64] {
+    const N: usize = 32;
+    let cosines: Vec<[f64; N]> = (0..8)
+        .map(|k| std::array::from_fn(|n| (PI / N as f64 * (n as f64 + 0.5) * k as f64).cos()))
+        .collect();
+
+    // Rows first, keeping 8 frequencies per row, then columns
+    let rows: Vec<[f64; 8]> = (0..N)
+        .map(|y| {
+            std::array::from_fn(|k| {
+                (0..N)
+                    .map(|x| f64::from(image.get_pixel(x as u32, y as u32)[0]) * cosines[k][x])
+                    .sum()
+            })
+        })
+        .collect();
+
+    std::array::from_fn(|i| {
+        let (v, u) = (i / 8, i % 8);
+        (0..N).map(|y| rows[y][u] * cosines[v][y]).sum()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Soft diagonal bands, like a photo with large shapes
+    fn photo(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let (fx, fy) = (x as f64 / width as f64, y as f64 / height as f64);
+            let v = 128.0 + 100.0 * (fx * 7.0).sin() * (fy * 5.0).cos();
+            Rgb([v as u8, v as u8, (v / 2.0) as u8])
+        }))
+    }
+
+    /// Large checkerboard, nothing like `photo`
+    fn other_photo(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let v = if (x * 4 / width + y * 4 / height).is_multiple_of(2) {
+                30
+            } else {
+                220
+            };
+            Rgb([v, v, v])
+        }))
+    }
+
+    fn save(dir: &Path, name: &str, image: &DynamicImage) -> FileEntry {
+        let path = dir.join(name);
+        image.save(&path).unwrap();
+        FileEntry::from_path(&path).unwrap()
+    }
+
+    #[test]
+    fn test_hash_each_keeps_failed_images_in_place() {
+        let temp_dir = TempDir::new().unwrap();
+        let files: Vec<FileEntry> = (0..40)
+            .map(|i| save(temp_dir.path(), &format!("{:02}.png", i), &photo(8, 8)))
+            .collect();
+
+        let hashes = hash_each(&files, |file| {
+            let n: u64 = file.name[..2].parse().unwrap();
+            match n {
+                3 => None,
+                7 => panic!("decoder bug"),
+                n => Some(n),
+            }
+        });
+
+        assert_eq!(hashes.len(), files.len());
+        for (i, hash) in hashes.iter().enumerate() {
+            let expected = (i != 3 && i != 7).then_some(i as u64);
+            assert_eq!(*hash, expected, "{}", files[i].name);
+        }
+    }
+
+    #[test]
+    fn test_hashes_survive_resizing() {
+        let original = photo(400, 300);
+        let resized = original.resize_exact(120, 90, FilterType::Lanczos3);
+        let different = other_photo(400, 300);
+
+        for algorithm in [
+            ImageHash::Average,
+            ImageHash::Difference,
+            ImageHash::Perceptual,
+        ] {
+            let hash = algorithm.hash(&original);
+            assert!(
+                hash_distance(hash, algorithm.hash(&resized)) <= DEFAULT_MAX_DISTANCE,
+                "{:?} should match a resized copy",
+                algorithm
+            );
+            assert!(
+                hash_distance(hash, algorithm.hash(&different)) > DEFAULT_MAX_DISTANCE,
+                "{:?} should tell different images apart",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_similar_images() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let files = vec![
+            save(dir, "beach.png", &photo(400, 300)),
+            save(dir, "beach-small.jpg", &photo(200, 150)),
+            save(dir, "chess.png", &other_photo(400, 300)),
+        ];
+        fs::write(dir.join("broken.png"), b"not an image").unwrap();
+        let broken = FileEntry::from_path(&dir.join("broken.png")).unwrap();
+        let notes = dir.join("notes.txt");
+        fs::write(&notes, b"text").unwrap();
+
+        let mut all = files;
+        all.push(broken);
+        all.push(FileEntry::from_path(&notes).unwrap());
+        let groups = find_similar_images(all, ImageHash::Difference, DEFAULT_MAX_DISTANCE);
+
+        assert_eq!(groups.len(), 1);
+        let names: Vec<_> = groups[0].iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["beach-small.jpg", "beach.png"]);
+    }
+
+    #[test]
+    fn test_find_similar_images_threshold() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let files = vec![
+            save(dir, "a.png", &photo(400, 300)),
+            save(dir, "b.png", &other_photo(400, 300)),
+        ];
+
+        // Every pair of 64-bit hashes is within 64 bits of each other
+        let groups = find_similar_images(files, ImageHash::Difference, 64);
+        assert_eq!(groups.len(), 1);
+    }
+}
@@ -0,0 +1,326 @@
+//! Choosing where staged files live and moving them there safely
+//!
+//! A rename is only atomic within one filesystem, so each file is staged on the
+//! same device it lives on: in the session directory when that shares the
+//! device, otherwise in a hidden `.fswp-staging` directory at the reviewed root
+//! or next to the file. When a rename still crosses devices, the file is copied,
+//! synced and its contents checked against the original's hash before the
+//! original is unlinked. Directories are copied the same way, file by file,
+//! before the original tree is removed. Moves never replace whatever is
+//! already at the destination.
+
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the hidden per-root directory used when the session directory is on another device
+pub const STAGING_DIR_NAME: &str = ".fswp-staging";
+
+/// Picks the staging directory for `path` so that staging it is a same-device rename
+pub fn staging_dir_for(path: &Path, session_dir: &Path, review_roots: &[PathBuf]) -> PathBuf {
+    let session_name = session_dir.file_name().unwrap_or_default();
+
+    if same_device(path, session_dir) {
+        return session_dir.to_path_buf();
+    }
+
+    if let Some(root) = review_roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())
+    {
+        if same_device(path, root) {
+            return root.join(STAGING_DIR_NAME).join(session_name);
+        }
+    }
+
+    match path.parent() {
+        Some(parent) => parent.join(STAGING_DIR_NAME).join(session_name),
+        None => session_dir.to_path_buf(),
+    }
+}
+
+/// Moves a file or directory, falling back to copy + fsync + unlink when the
+/// move would cross filesystems. An existing `to` is never replaced: files are
+/// moved with a hard link + unlink, which fails if `to` exists, and
+/// directories only after checking `to` is free.
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(from)?.is_dir() {
+        return move_dir(from, to);
+    }
+
+    match fs::hard_link(from, to) {
+        Ok(()) => {
+            if let Err(e) = fs::remove_file(from) {
+                fs::remove_file(to).ok();
+                return Err(e);
+            }
+            Ok(())
+        }
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => copy_then_unlink(from, to),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Err(occupied(to)),
+        // Filesystems without hard links (FAT, some network mounts) get a checked rename
+        Err(_) => {
+            if fs::symlink_metadata(to).is_ok() {
+                return Err(occupied(to));
+            }
+            fs::rename(from, to)
+        }
+    }
+}
+
+/// Renames a directory unless `to` exists, copying it when that crosses filesystems
+fn move_dir(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(to).is_ok() {
+        return Err(occupied(to));
+    }
+
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => copy_dir_then_remove(from, to),
+        result => result,
+    }
+}
+
+fn occupied(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("Destination already exists: {:?}", path),
+    )
+}
+
+/// Copies the tree at `from` to `to` and only removes `from` once every file
+/// has been copied and verified. A partial copy is cleaned up on failure.
+fn copy_dir_then_remove(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir(to)?;
+    if let Err(e) = copy_dir_contents(from, to) {
+        fs::remove_dir_all(to).ok();
+        return Err(e);
+    }
+    fs::remove_dir_all(from)
+}
+
+fn copy_dir_contents(from: &Path, to: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let source = entry.path();
+        let dest = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            fs::create_dir(&dest)?;
+            copy_dir_contents(&source, &dest)?;
+        } else if file_type.is_symlink() {
+            copy_symlink(&source, &dest)?;
+        } else {
+            copy_verified(&source, &dest)?;
+        }
+    }
+
+    if let Ok(permissions) = fs::metadata(from).map(|m| m.permissions()) {
+        fs::set_permissions(to, permissions).ok();
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    copy_verified(from, to)
+}
+
+/// Copies `from` to `to`, verifies the copy on disk and only then removes `from`
+fn copy_then_unlink(from: &Path, to: &Path) -> io::Result<()> {
+    copy_verified(from, to)?;
+    fs::remove_file(from)
+}
+
+/// Copies `from` to `to` and verifies the copy on disk by size and content
+/// hash, leaving `from` in place
+fn copy_verified(from: &Path, to: &Path) -> io::Result<()> {
+    let metadata = fs::metadata(from)?;
+    let expected = metadata.len();
+
+    let copied = {
+        let mut source = File::open(from)?;
+        let mut dest = File::create_new(to)?;
+        let copied = io::copy(&mut source, &mut dest)?;
+        dest.set_permissions(metadata.permissions()).ok();
+        if let Ok(modified) = metadata.modified() {
+            dest.set_modified(modified).ok();
+        }
+        dest.sync_all()?;
+        copied
+    };
+
+    let written = fs::metadata(to).map(|m| m.len()).unwrap_or(0);
+    if copied != expected || written != expected {
+        fs::remove_file(to).ok();
+        return Err(io::Error::other(format!(
+            "Copy of {:?} is incomplete ({} of {} bytes), original kept",
+            from, written, expected
+        )));
+    }
+
+    // Read both back so a copy that only looks complete is caught too
+    if hash_contents(from)? != hash_contents(to)? {
+        fs::remove_file(to).ok();
+        return Err(io::Error::other(format!(
+            "Copy of {:?} does not match the original, original kept",
+            from
+        )));
+    }
+
+    Ok(())
+}
+
+fn hash_contents(path: &Path) -> io::Result<blake3::Hash> {
+    let mut hasher = blake3::Hasher::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize())
+}
+
+/// Removes a staging directory if it is empty, along with its `.fswp-staging` parent
+pub fn remove_empty_staging_dir(dir: &Path) {
+    if fs::remove_dir(dir).is_err() {
+        return;
+    }
+
+    if let Some(parent) = dir.parent() {
+        if parent.file_name().is_some_and(|n| n == STAGING_DIR_NAME) {
+            fs::remove_dir(parent).ok();
+        }
+    }
+}
+
+/// Whether two paths are on the same device. Unknown on non-Unix platforms,
+/// where `move_file` falls back to copying if the rename fails.
+fn same_device(a: &Path, b: &Path) -> bool {
+    match (device_id(a), device_id(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+#[cfg(unix)]
+fn device_id(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    // Staging directories may not exist yet, so fall back to the nearest ancestor
+    path.ancestors()
+        .find_map(|p| fs::metadata(p).ok())
+        .map(|m| m.dev())
+}
+
+#[cfg(not(unix))]
+fn device_id(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_staging_dir_for_same_device_uses_session_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_dir = temp_dir.path().join("fswp-1-1");
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, b"content").unwrap();
+
+        let staging = staging_dir_for(&file, &session_dir, &[temp_dir.path().to_path_buf()]);
+
+        assert_eq!(staging, session_dir);
+    }
+
+    #[test]
+    fn test_copy_then_unlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("from.txt");
+        let to = temp_dir.path().join("to.txt");
+        fs::write(&from, b"content").unwrap();
+
+        copy_then_unlink(&from, &to).unwrap();
+
+        assert!(!from.exists());
+        assert_eq!(fs::read(&to).unwrap(), b"content");
+    }
+
+    #[test]
+    fn test_copy_then_unlink_never_overwrites() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("from.txt");
+        let to = temp_dir.path().join("to.txt");
+        fs::write(&from, b"content").unwrap();
+        fs::write(&to, b"existing").unwrap();
+
+        assert!(copy_then_unlink(&from, &to).is_err());
+
+        assert_eq!(fs::read(&from).unwrap(), b"content");
+        assert_eq!(fs::read(&to).unwrap(), b"existing");
+    }
+
+    #[test]
+    fn test_move_file_never_overwrites() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("from.txt");
+        let to = temp_dir.path().join("to.txt");
+        fs::write(&from, b"content").unwrap();
+        fs::write(&to, b"existing").unwrap();
+
+        let err = move_file(&from, &to).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read(&from).unwrap(), b"content");
+        assert_eq!(fs::read(&to).unwrap(), b"existing");
+    }
+
+    #[test]
+    fn test_move_file_never_replaces_empty_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("from");
+        let to = temp_dir.path().join("to");
+        fs::create_dir(&from).unwrap();
+        fs::write(from.join("file.txt"), b"content").unwrap();
+        fs::create_dir(&to).unwrap();
+
+        assert!(move_file(&from, &to).is_err());
+
+        assert!(from.join("file.txt").exists());
+        assert!(to.exists());
+    }
+
+    #[test]
+    fn test_copy_dir_then_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("project");
+        let to = temp_dir.path().join("staged");
+        fs::create_dir_all(from.join("src")).unwrap();
+        fs::write(from.join("README"), b"readme").unwrap();
+        fs::write(from.join("src").join("main.rs"), b"fn main() {}").unwrap();
+
+        copy_dir_then_remove(&from, &to).unwrap();
+
+        assert!(!from.exists());
+        assert_eq!(fs::read(to.join("README")).unwrap(), b"readme");
+        assert_eq!(
+            fs::read(to.join("src").join("main.rs")).unwrap(),
+            b"fn main() {}"
+        );
+    }
+
+    #[test]
+    fn test_remove_empty_staging_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let staging = temp_dir.path().join(STAGING_DIR_NAME).join("fswp-1-1");
+        fs::create_dir_all(&staging).unwrap();
+
+        remove_empty_staging_dir(&staging);
+
+        assert!(!temp_dir.path().join(STAGING_DIR_NAME).exists());
+    }
+}
@@ -15,7 +15,7 @@ pub mod tui;
 // Re-export primary types for convenience
 pub use config::UserConfig;
 pub use domain::{
-    discover_files, discover_files_with_options, AppState, Decision, DecisionEngine,
+    discover_files, discover_files_with_options, AppState, CommitReport, Decision, DecisionEngine,
     DecisionStatistics, DiscoveryOptions, FileEntry, FileType, SortBy,
 };
 pub use error::{FileTinderError, Result};
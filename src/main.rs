@@ -1,13 +1,19 @@
 use fswp::async_preview::SyncPreviewManager;
-use fswp::cli::{AppConfig, Args, SortOrder};
+use fswp::cli::{AppConfig, Args, Command, FileList};
 use fswp::config::UserConfig;
 use fswp::domain::{
-    discover_files_with_options, AppState, Decision, DecisionEngine, DiscoveryOptions, SortBy,
+    biggest_first, find_duplicates, find_orphaned_sessions, find_similar_images, keep_list_path,
+    load_listed_files, parse_file_list, saved_sessions_dir, sessions_dir, AppState, Decision,
+    DecisionEngine, Discovery, DiscoveryOptions, DiskUsage, FileEntry, KeepList, OrphanedSession,
+    SavedSession, SessionArchive,
 };
 use fswp::open_file;
 use fswp::tui::{
-    handle_confirm_input, handle_key_event, render_confirm_trash_overlay, render_help_overlay,
-    render_summary, render_welcome_overlay, render_with_preview, KeyAction, ViewState,
+    format_file_size, handle_confirm_input, handle_delete_confirm_input, handle_key_event,
+    handle_rename_input, handle_timeline_input, render_confirm_bulk_delete_overlay,
+    render_confirm_delete_overlay, render_confirm_duplicates_overlay, render_confirm_trash_overlay,
+    render_help_overlay, render_rename_overlay, render_summary, render_timeline_overlay,
+    render_welcome_overlay, render_with_preview, KeyAction, RenameDialog, TextInput, ViewState,
 };
 
 use crossterm::{
@@ -16,12 +22,26 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::{io, time::Duration};
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, BufRead, IsTerminal, Read, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 fn main() -> io::Result<()> {
     // Parse command line arguments
     let args = Args::parse_args();
 
+    if let Some(Command::Recover { restore, commit }) = args.command {
+        return run_recover(restore, commit);
+    }
+
     // Validate arguments
     if let Err(e) = args.validate() {
         eprintln!("Error: {}", e);
@@ -35,57 +55,207 @@ fn main() -> io::Result<()> {
     run_app_with_config(&config)
 }
 
+/// Set once a file list has been read from stdin, so prompts go to the terminal
+static STDIN_USED: AtomicBool = AtomicBool::new(false);
+
 /// Runs the TUI application with configuration
 pub fn run_app_with_config(config: &AppConfig) -> io::Result<()> {
+    // A list piped in must be read before anything prompts on stdin
+    let listed_paths = config.file_list.as_ref().map(|list| {
+        read_file_list(list).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    });
+
+    // Files left in staging by a crashed session must be dealt with first
+    offer_recovery()?;
+
+    // Files kept in earlier runs stay hidden unless asked for
+    let (keep_list, keep_list_file) = load_keep_list();
+
+    // Load user configuration
+    let mut user_config = UserConfig::load().unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to load user config: {}", e);
+        UserConfig::default()
+    });
+    let extension_types = user_config.extension_types().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
     // Convert config to discovery options
+    let now = chrono::Utc::now();
     let discovery_options = DiscoveryOptions {
         file_types: config.file_type_filters.clone(),
         show_hidden: config.show_hidden,
         min_size: config.min_size,
         max_size: config.max_size,
-        sort_by: match config.sort_by {
-            SortOrder::Date => SortBy::Date,
-            SortOrder::Name => SortBy::Name,
-            SortOrder::Size => SortBy::Size,
-            SortOrder::Type => SortBy::Type,
-        },
+        modified_before: config.older_than.map(|age| now - age),
+        modified_after: config.newer_than.map(|age| now - age),
+        accessed_before: config.not_accessed_since.map(|age| now - age),
+        sort_by: config.sort_keys(),
         reverse: config.reverse,
+        recursive: config.recursive,
+        max_depth: config.max_depth,
+        directories: config.directories,
+        respect_ignore_files: config.respect_ignore_files,
+        include: config.include.clone(),
+        exclude: config.exclude.clone(),
+        name_regex: config.name_regex.clone(),
+        // A kept copy still counts when looking for duplicates
+        keep_list: (!config.review_kept && !config.reviews_groups()).then(|| keep_list.clone()),
+        extension_types,
     };
 
-    // Discover files with options
-    let files = discover_files_with_options(&config.directory, &discovery_options)?;
+    // Disk-hog review walks every file and applies the filters itself afterwards
+    let hog_options = config.disk_hogs.then(|| discovery_options.clone());
+    let walk_options = match &hog_options {
+        Some(options) => DiskUsage::walk_options(options),
+        None => discovery_options,
+    };
 
-    if files.is_empty() {
-        println!(
-            "No files found in directory: {}",
-            config.directory.display()
-        );
-        if config.file_type_filters.is_some() {
-            println!("(File type filters are active - try without filters)");
+    // Listed files are built up front, skipping the walk
+    let listed = match listed_paths {
+        Some(paths) => Some(load_file_list(&paths, &walk_options)?),
+        None => None,
+    };
+
+    // Discover files in the background, so the review can start with the first ones
+    let discover = || Discovery::start_all(&config.roots, walk_options);
+
+    let (mut app_state, mut incoming, saved_session) = if config.reviews_groups() {
+        // Groups can only be formed once every file is known
+        let files = match listed {
+            Some(files) => files,
+            None => {
+                let files = discover()?.wait(scan_progress())?;
+                println!("\rScanning... {} files found", files.len());
+                files
+            }
+        };
+        if files.is_empty() {
+            print_no_files_found(config, &keep_list);
+            return Ok(());
         }
-        return Ok(());
-    }
+
+        // Duplicate review goes group by group and does not save progress
+        println!("Comparing {} files...", files.len());
+        let (groups, what) = if config.similar_images {
+            let groups = find_similar_images(files, config.image_hash, config.similarity);
+            (groups, "similar images")
+        } else {
+            (find_duplicates(files), "duplicate files")
+        };
+        if groups.is_empty() {
+            println!("No {} found in {}", what, describe_roots(config));
+            return Ok(());
+        }
+        (AppState::from_duplicate_groups(groups), None, None)
+    } else if let Some(options) = hog_options {
+        // Folder sizes are only known once every file is
+        let files = discover()?.wait(scan_progress())?;
+        println!("\rScanning... {} files found", files.len());
+        let usage = DiskUsage::new(&config.roots, files, &options);
+        println!("{} in total", format_file_size(usage.total_size()));
+        let files = usage.top_level();
+        if files.is_empty() {
+            print_no_files_found(config, &keep_list);
+            return Ok(());
+        }
+
+        // Like duplicate review, hog review does not save progress
+        let mut app_state = AppState::new(files);
+        app_state.disk_hogs = true;
+        let hogs = HogReview {
+            usage,
+            expanded: HashSet::new(),
+            furthest: 0,
+        };
+        (app_state, Some(Incoming::Hogs(hogs)), None)
+    } else if let Some(files) = listed {
+        if files.is_empty() {
+            print_no_files_found(config, &keep_list);
+            return Ok(());
+        }
+
+        // A list has no directory to save progress for
+        (AppState::new(files), None, None)
+    } else {
+        // Pick up where a previous run on this directory left off. Progress is
+        // saved per directory, so a review of several starts afresh each time.
+        let (saved_session, resumed) = if config.has_several_roots() {
+            (None, None)
+        } else {
+            let (saved_session, resumed) = resume_saved_session(config)?;
+            (Some(saved_session), resumed)
+        };
+        let mut scan = Scan {
+            discovery: discover()?,
+            return_to_cursor: resumed.is_some(),
+            resumed,
+            furthest: 0,
+        };
+
+        let mut files = Vec::new();
+        while files.is_empty() && !scan.discovery.is_finished() {
+            files = scan.take(true);
+        }
+        if files.is_empty() {
+            if let Some(e) = scan.discovery.take_error() {
+                return Err(e);
+            }
+            if scan.discovery.found() == 0 {
+                print_no_files_found(config, &keep_list);
+            } else {
+                println!(
+                    "Every file in {} was already reviewed",
+                    describe_roots(config)
+                );
+            }
+            return Ok(());
+        }
+
+        let mut app_state = AppState::new(files);
+        app_state.scanning = !scan.discovery.is_finished();
+        scan.return_to_saved_cursor(&mut app_state, true);
+        (app_state, Some(Incoming::Scan(scan)), saved_session)
+    };
 
     // Print dry-run notice
     if config.dry_run {
         println!("[DRY RUN] No files will be moved to trash");
-        println!("   Found {} files to review", files.len());
+        if app_state.scanning {
+            println!(
+                "   Found {} files to review so far, still scanning",
+                app_state.files.len()
+            );
+        } else {
+            println!("   Found {} files to review", app_state.files.len());
+        }
         println!("   Press Enter to continue...");
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
+        read_answer()?;
     }
 
     // Initialize state
-    let mut app_state = AppState::new(files.clone());
-    let mut decision_engine = DecisionEngine::new(files);
+    app_state.delete_mode = config.delete_mode;
+    app_state.root = config.directory().to_path_buf();
+    app_state.several_roots = config.has_several_roots();
+    app_state.keep_list = keep_list;
+    let mut decision_engine =
+        DecisionEngine::with_sessions_dir(app_state.files.clone(), &staging_sessions_dir());
     decision_engine.set_dry_run(config.dry_run);
-    let mut preview_manager = SyncPreviewManager::new();
+    for root in &config.roots {
+        decision_engine.add_review_root(root);
+    }
 
-    // Load user configuration
-    let mut user_config = UserConfig::load().unwrap_or_else(|e| {
-        eprintln!("Warning: Failed to load user config: {}", e);
-        UserConfig::default()
-    });
+    decision_engine.set_archive(SessionArchive::in_dir(
+        &config.archive_location(user_config.archive_dir.as_deref()),
+        config.archive_format,
+    ));
+
+    // Termination signals end the session through the normal commit path
+    let shutdown = register_shutdown_signals()?;
 
     // Setup terminal
     enable_raw_mode()?;
@@ -99,9 +269,10 @@ pub fn run_app_with_config(config: &AppConfig) -> io::Result<()> {
         &mut terminal,
         &mut app_state,
         &mut decision_engine,
-        &mut preview_manager,
+        &mut incoming,
         config,
         &mut user_config,
+        &shutdown,
     );
 
     // Restore terminal
@@ -109,15 +280,539 @@ pub fn run_app_with_config(config: &AppConfig) -> io::Result<()> {
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
+    // Permanent deletes only happen after the typed confirmation on quit;
+    // anything else (a signal, an error) leaves them to be restored
+    if matches!(result, Ok(true)) {
+        let report = decision_engine.commit_delete_decisions(config.shred);
+        if !report.deleted.is_empty() {
+            println!("Permanently deleted {} files", report.deleted.len());
+        }
+        for (path, reason) in &report.failed {
+            eprintln!(
+                "Warning: Could not delete {}: {} (file was restored)",
+                path.display(),
+                reason
+            );
+        }
+    } else if !config.dry_run {
+        let pending = decision_engine.pending_deletes().len();
+        if pending > 0 {
+            println!(
+                "Restored {} files marked for permanent deletion (not confirmed)",
+                pending
+            );
+        }
+    }
+
+    // Write the session archive in one go
+    let report = decision_engine.commit_archive_decisions();
+    if let (false, Some(archive)) = (report.archived.is_empty(), decision_engine.archive()) {
+        println!(
+            "Archived {} files into {}",
+            report.archived.len(),
+            archive.path().display()
+        );
+    }
+    for (path, reason) in &report.failed {
+        eprintln!(
+            "Warning: Could not archive {}: {} (file was not deleted)",
+            path.display(),
+            reason
+        );
+    }
+
+    // Hand staged files over to the system trash
+    let report = decision_engine.commit_trash_decisions();
+    if !report.trashed.is_empty() {
+        println!("Moved {} files to trash", report.trashed.len());
+    }
+    for (path, reason) in &report.failed {
+        eprintln!(
+            "Warning: Could not trash {}: {} (file was not deleted)",
+            path.display(),
+            reason
+        );
+    }
+
     // Print summary after exit
     if config.dry_run {
         let stats = decision_engine.get_statistics();
         println!("\n[DRY RUN] Complete");
         println!("   Would have kept: {} files", stats.kept);
         println!("   Would have trashed: {} files", stats.trashed);
+        if config.delete_mode {
+            println!("   Would have deleted: {} files", stats.deleted);
+        }
+        if stats.archived > 0 {
+            println!("   Would have archived: {} files", stats.archived);
+        }
+    }
+
+    if let Some(Incoming::Scan(scan)) = incoming.as_mut() {
+        if let Some(e) = scan.discovery.take_error() {
+            eprintln!("Warning: Discovery stopped early: {}", e);
+        }
+    }
+    if let Some(saved_session) = saved_session {
+        save_progress(config, saved_session, &app_state, &decision_engine);
+    }
+    if let (Some(path), false) = (keep_list_file, config.dry_run) {
+        app_state.keep_list.record_decisions(&decision_engine);
+        if let Err(e) = app_state.keep_list.save(&path) {
+            eprintln!("Warning: Failed to save kept files: {}", e);
+        }
+    }
+
+    result.map(|_| ())
+}
+
+/// Loads the files kept in earlier runs and where to save them again. The
+/// path is None when the list could not be read, so it is never overwritten.
+fn load_keep_list() -> (KeepList, Option<PathBuf>) {
+    let Some(path) = keep_list_path() else {
+        return (KeepList::new(), None);
+    };
+
+    match KeepList::load(&path) {
+        Ok(keep_list) => (keep_list, Some(path)),
+        Err(e) => {
+            eprintln!("Warning: Failed to load kept files: {}", e);
+            (KeepList::new(), None)
+        }
+    }
+}
+
+/// Offers to continue the saved review of the directory. Returns the session
+/// to save progress into, and the saved review when it is resumed.
+fn resume_saved_session(config: &AppConfig) -> io::Result<(SavedSession, Option<SavedSession>)> {
+    let directory = config
+        .directory()
+        .canonicalize()
+        .unwrap_or_else(|_| config.directory().to_path_buf());
+    let new_session = SavedSession::new(&directory);
+
+    let Some(sessions_dir) = saved_sessions_dir() else {
+        return Ok((new_session, None));
+    };
+
+    let saved = match SavedSession::load(&sessions_dir, &directory) {
+        Ok(Some(saved)) if !config.fresh => saved,
+        Ok(_) => return Ok((new_session, None)),
+        Err(e) => {
+            eprintln!("Warning: Failed to load saved review: {}", e);
+            return Ok((new_session, None));
+        }
+    };
+
+    if !config.resume && !prompt_resume(&saved)? {
+        if !config.dry_run {
+            SavedSession::remove(&sessions_dir, &directory).ok();
+        }
+        return Ok((new_session, None));
+    }
+
+    Ok((saved.clone(), Some(saved)))
+}
+
+/// Discovery that keeps adding files while the review is under way
+struct Scan {
+    discovery: Discovery,
+    /// Saved review being resumed, whose kept files are skipped
+    resumed: Option<SavedSession>,
+    /// Whether to move the cursor to the resumed review's file once it turns up
+    return_to_cursor: bool,
+    /// Furthest the cursor has been; files after it can still be reordered
+    furthest: usize,
+}
+
+impl Scan {
+    /// Files found since the last call, less the ones a resumed review kept
+    fn take(&mut self, wait: bool) -> Vec<FileEntry> {
+        let files = if wait {
+            self.discovery.take_waiting()
+        } else {
+            self.discovery.take()
+        };
+        match &self.resumed {
+            Some(saved) => saved.skip_decided(files),
+            None => files,
+        }
+    }
+
+    /// Adds the files found since the last call to the review
+    fn receive(&mut self, app_state: &mut AppState, decision_engine: &mut DecisionEngine) {
+        let files = self.take(false);
+
+        if !files.is_empty() {
+            let unseen = unseen_start(&mut self.furthest, app_state, decision_engine);
+            app_state.add_files(files, unseen, |a, b| self.discovery.compare(a, b));
+            decision_engine.replace_undecided(unseen, &app_state.files[unseen..]);
+
+            let untouched = app_state.current_index == 0 && decision_engine.history.is_empty();
+            self.return_to_saved_cursor(app_state, untouched);
+        } else {
+            self.furthest = self.furthest.max(app_state.current_index);
+        }
+        app_state.scanning = !self.discovery.is_finished();
+    }
+
+    /// Moves the cursor to where the resumed review stopped once that file
+    /// is found, unless the user has started reviewing in the meantime
+    fn return_to_saved_cursor(&mut self, app_state: &mut AppState, untouched: bool) {
+        if !self.return_to_cursor {
+            return;
+        }
+        if !untouched {
+            self.return_to_cursor = false;
+            return;
+        }
+        let position = self
+            .resumed
+            .as_ref()
+            .and_then(|saved| saved.cursor_position(&app_state.files));
+        if let Some(index) = position {
+            app_state.jump_to(index);
+            self.furthest = index;
+            self.return_to_cursor = false;
+        }
+    }
+}
+
+/// Files that join the review after it has started
+enum Incoming {
+    /// Discovery is still finding files
+    Scan(Scan),
+    /// Folders kept in disk-hog review bring in their contents
+    Hogs(HogReview),
+}
+
+impl Incoming {
+    /// Adds whatever arrived since the last call to the review
+    fn receive(&mut self, app_state: &mut AppState, decision_engine: &mut DecisionEngine) {
+        match self {
+            Incoming::Scan(scan) => scan.receive(app_state, decision_engine),
+            Incoming::Hogs(hogs) => hogs.receive(app_state, decision_engine),
+        }
+    }
+}
+
+/// Disk-hog review, which opens up each folder the user keeps
+struct HogReview {
+    usage: DiskUsage,
+    /// Kept folders whose contents were queued already
+    expanded: HashSet<PathBuf>,
+    /// Furthest the cursor has been; files after it can still be reordered
+    furthest: usize,
+}
+
+impl HogReview {
+    /// Queues the contents of folders kept since the last call, sorted in
+    /// among the files not looked at yet
+    fn receive(&mut self, app_state: &mut AppState, decision_engine: &mut DecisionEngine) {
+        let kept_folders: Vec<PathBuf> = decision_engine
+            .history
+            .applied()
+            .iter()
+            .filter(|entry| entry.decision == Decision::Keep)
+            .map(|entry| &app_state.files[entry.index])
+            .filter(|file| file.is_dir() && !self.expanded.contains(&file.path))
+            .map(|file| file.path.clone())
+            .collect();
+
+        let mut files = Vec::new();
+        for folder in kept_folders {
+            files.extend(self.usage.children(&folder));
+            self.expanded.insert(folder);
+        }
+        if files.is_empty() {
+            self.furthest = self.furthest.max(app_state.current_index);
+            return;
+        }
+
+        let unseen = unseen_start(&mut self.furthest, app_state, decision_engine);
+        app_state.add_files(files, unseen, biggest_first);
+        decision_engine.replace_undecided(unseen, &app_state.files[unseen..]);
+    }
+}
+
+/// Where the files not looked at yet start: files the cursor has passed or
+/// that have a decision stay put, and the ones after can be reordered
+fn unseen_start(
+    furthest: &mut usize,
+    app_state: &AppState,
+    decision_engine: &DecisionEngine,
+) -> usize {
+    *furthest = (*furthest).max(app_state.current_index);
+    let decided = decision_engine.first_replaceable();
+    (*furthest + 1).max(decided).min(app_state.files.len())
+}
+
+/// Prints how many files a blocking discovery found so far, a few times a second
+fn scan_progress() -> impl FnMut(usize) {
+    let mut last_print: Option<Instant> = None;
+    move |found| {
+        if last_print.is_none_or(|at| at.elapsed() >= Duration::from_millis(100)) {
+            print!("\rScanning... {} files found", found);
+            io::stdout().flush().ok();
+            last_print = Some(Instant::now());
+        }
+    }
+}
+
+/// The reviewed directories, or the file list, for messages
+fn describe_roots(config: &AppConfig) -> String {
+    if config.file_list.is_some() {
+        return "the file list".to_string();
+    }
+    config
+        .roots
+        .iter()
+        .map(|root| root.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Reads the paths to review from stdin or a list file
+fn read_file_list(list: &FileList) -> io::Result<Vec<PathBuf>> {
+    let contents = match list {
+        FileList::Stdin => {
+            let mut stdin = io::stdin();
+            if stdin.is_terminal() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "--stdin reads paths piped in, as in: fd -e log | fswp --stdin",
+                ));
+            }
+            let mut contents = Vec::new();
+            stdin.read_to_end(&mut contents)?;
+            STDIN_USED.store(true, Ordering::Relaxed);
+            contents
+        }
+        FileList::File(path) => fs::read(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Cannot read file list {}: {}", path.display(), e),
+            )
+        })?,
+    };
+    Ok(parse_file_list(&contents))
+}
+
+/// Builds entries for the listed paths, warning about those that cannot be read
+fn load_file_list(paths: &[PathBuf], options: &DiscoveryOptions) -> io::Result<Vec<FileEntry>> {
+    let listed = load_listed_files(paths, options)?;
+    for (path, e) in &listed.unreadable {
+        eprintln!("Warning: Cannot read {}: {}", path.display(), e);
+    }
+    Ok(listed.files)
+}
+
+/// Explains why there is nothing to review
+fn print_no_files_found(config: &AppConfig, keep_list: &KeepList) {
+    match config.file_list {
+        Some(_) => println!("No files to review in the file list"),
+        None => println!("No files found in directory: {}", describe_roots(config)),
+    }
+    if config.file_type_filters.is_some() {
+        println!("(File type filters are active - try without filters)");
+    }
+    if !config.review_kept && !keep_list.is_empty() {
+        println!("(Files kept in earlier runs are hidden - use --review-kept to see them)");
+    }
+}
+
+/// Reads the answer to a prompt. Once stdin has carried a file list it is used
+/// up, so the answer comes from the terminal; without one it is left empty,
+/// which takes the default.
+fn read_answer() -> io::Result<String> {
+    let mut input = String::new();
+    if !STDIN_USED.load(Ordering::Relaxed) {
+        io::stdin().read_line(&mut input)?;
+    } else if let Ok(tty) = fs::File::open("/dev/tty") {
+        io::BufReader::new(tty).read_line(&mut input)?;
+    }
+    Ok(input)
+}
+
+/// Asks whether to resume a saved review
+fn prompt_resume(saved: &SavedSession) -> io::Result<bool> {
+    let saved_at: chrono::DateTime<chrono::Local> = saved.saved.into();
+    println!(
+        "Found a saved review of {} from {}: {} files decided, {} kept",
+        saved.directory.display(),
+        saved_at.format("%Y-%m-%d %H:%M"),
+        saved.decided.len(),
+        saved.kept_count()
+    );
+    print!("Resume where you left off? [Y/n] ");
+    io::stdout().flush()?;
+
+    let input = read_answer()?;
+
+    Ok(!matches!(input.trim().to_lowercase().as_str(), "n" | "no"))
+}
+
+/// Saves review progress for the next run, or forgets it once every file has a decision
+fn save_progress(
+    config: &AppConfig,
+    mut session: SavedSession,
+    app_state: &AppState,
+    decision_engine: &DecisionEngine,
+) {
+    let Some(sessions_dir) = saved_sessions_dir() else {
+        return;
+    };
+    if config.dry_run {
+        return;
+    }
+
+    let result = if is_all_files_processed(app_state, decision_engine) {
+        SavedSession::remove(&sessions_dir, &session.directory)
+    } else if decision_engine.history.is_empty() && app_state.current_index == 0 {
+        // Nothing worth resuming
+        Ok(())
+    } else {
+        session.record_decisions(decision_engine);
+        session.record_cursor(app_state.current_file());
+        session.saved = chrono::Utc::now();
+        session.save(&sessions_dir).map(|_| {
+            println!(
+                "Saved your progress; run fswp on {} again to resume",
+                config.directory().display()
+            )
+        })
+    };
+
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to save review progress: {}", e);
+    }
+}
+
+/// Directory holding the staging area and journal of every session
+fn staging_sessions_dir() -> PathBuf {
+    sessions_dir().unwrap_or_else(std::env::temp_dir)
+}
+
+/// How the user wants interrupted sessions resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecoveryChoice {
+    Restore,
+    Commit,
+    Skip,
+}
+
+/// Resolves interrupted sessions (`fswp recover`)
+fn run_recover(restore: bool, commit: bool) -> io::Result<()> {
+    let mut sessions = find_orphaned_sessions(&staging_sessions_dir())?;
+
+    if sessions.is_empty() {
+        println!("No interrupted sessions found");
+        return Ok(());
     }
 
-    result
+    let choice = if restore {
+        RecoveryChoice::Restore
+    } else if commit {
+        RecoveryChoice::Commit
+    } else {
+        describe_orphaned_sessions(&sessions);
+        prompt_recovery_choice()?
+    };
+
+    resolve_orphaned_sessions(&mut sessions, choice);
+    Ok(())
+}
+
+/// Offers to resolve interrupted sessions before a new review starts
+fn offer_recovery() -> io::Result<()> {
+    let mut sessions = match find_orphaned_sessions(&staging_sessions_dir()) {
+        Ok(sessions) => sessions,
+        Err(e) => {
+            eprintln!("Warning: Failed to check for interrupted sessions: {}", e);
+            return Ok(());
+        }
+    };
+
+    if sessions.is_empty() {
+        return Ok(());
+    }
+
+    println!("A previous fswp session did not finish cleanly.");
+    describe_orphaned_sessions(&sessions);
+    let choice = prompt_recovery_choice()?;
+    resolve_orphaned_sessions(&mut sessions, choice);
+    Ok(())
+}
+
+/// Prints what each interrupted session still has in staging
+fn describe_orphaned_sessions(sessions: &[OrphanedSession]) {
+    for session in sessions {
+        let created: chrono::DateTime<chrono::Local> = session.journal.created.into();
+        println!(
+            "Session from {}: {} staged files ({})",
+            created.format("%Y-%m-%d %H:%M"),
+            session.journal.entries.len(),
+            format_file_size(session.staged_size())
+        );
+        for entry in &session.journal.entries {
+            println!("   {}", entry.original_path.display());
+        }
+    }
+}
+
+/// Asks whether to restore, commit or skip interrupted sessions
+fn prompt_recovery_choice() -> io::Result<RecoveryChoice> {
+    print!("[r]estore files, [c]ommit them to trash, or [s]kip for now? ");
+    io::stdout().flush()?;
+
+    let input = read_answer()?;
+
+    Ok(match input.trim().to_lowercase().as_str() {
+        "r" | "restore" => RecoveryChoice::Restore,
+        "c" | "commit" => RecoveryChoice::Commit,
+        _ => RecoveryChoice::Skip,
+    })
+}
+
+/// Applies the recovery choice to every session and reports the outcome
+fn resolve_orphaned_sessions(sessions: &mut [OrphanedSession], choice: RecoveryChoice) {
+    if choice == RecoveryChoice::Skip {
+        println!("Left staged files untouched. Run `fswp recover` to resolve them later.");
+        return;
+    }
+
+    for session in sessions.iter_mut() {
+        let report = match choice {
+            RecoveryChoice::Restore => session.restore(),
+            RecoveryChoice::Commit => session.commit(),
+            RecoveryChoice::Skip => unreachable!(),
+        };
+
+        for path in &report.completed {
+            let verb = if choice == RecoveryChoice::Restore {
+                "Restored"
+            } else {
+                "Committed"
+            };
+            println!("{} {}", verb, path.display());
+        }
+        for (path, reason) in &report.failed {
+            eprintln!("Warning: Could not recover {}: {}", path.display(), reason);
+        }
+    }
+}
+
+/// Registers termination signals and returns a flag that is set when one arrives
+fn register_shutdown_signals() -> io::Result<Arc<AtomicBool>> {
+    let shutdown = Arc::new(AtomicBool::new(false));
+
+    for signal in signal_hook::consts::TERM_SIGNALS {
+        signal_hook::flag::register(*signal, Arc::clone(&shutdown))?;
+    }
+    #[cfg(unix)]
+    signal_hook::flag::register(signal_hook::consts::SIGHUP, Arc::clone(&shutdown))?;
+
+    Ok(shutdown)
 }
 
 /// Suspends the TUI terminal to allow external programs to run
@@ -141,15 +836,19 @@ fn resume_terminal<B: ratatui::backend::Backend + std::io::Write>(
     Ok(())
 }
 
-/// Main application loop
+/// Main application loop. Returns whether the user confirmed the pending
+/// permanent deletes (true when there were none).
 fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
     terminal: &mut Terminal<B>,
     app_state: &mut AppState,
     decision_engine: &mut DecisionEngine,
-    preview_manager: &mut SyncPreviewManager,
+    incoming: &mut Option<Incoming>,
     config: &AppConfig,
     user_config: &mut UserConfig,
-) -> io::Result<()> {
+    shutdown: &AtomicBool,
+) -> io::Result<bool> {
+    let mut preview_manager = SyncPreviewManager::new();
+    let preview_manager = &mut preview_manager;
     // Show welcome on first launch or if --welcome flag is set
     let should_show_welcome = config.show_welcome || !user_config.welcome_shown;
     let mut view_state = if should_show_welcome {
@@ -157,15 +856,59 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
     } else {
         ViewState::Browsing
     };
+    // Folders bound to the number keys
+    let move_destinations = config.move_slots(&user_config.move_destinations);
+    // Selected entry while the timeline is open
+    let mut timeline_selected = 0;
+    // Why the last jump or revert in the timeline failed
+    let mut timeline_error: Option<String> = None;
+    // Name being edited while renaming
+    let mut rename_dialog: Option<RenameDialog> = None;
+    // Text typed into a delete confirmation
+    let mut confirm_input = TextInput::default();
+    // Why the last delete confirmation was refused
+    let mut confirm_error: Option<String> = None;
+    // Copy to keep while confirming a duplicate group
+    let mut keep_copy: Option<usize> = None;
+    let trash_decision = if config.delete_mode {
+        Decision::Delete
+    } else {
+        Decision::Trash
+    };
 
     loop {
+        if shutdown.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+
+        if let Some(incoming) = incoming.as_mut() {
+            let was_done = is_all_files_processed(app_state, decision_engine);
+            incoming.receive(app_state, decision_engine);
+            let done = is_all_files_processed(app_state, decision_engine);
+            match view_state {
+                // Every file may have been decided before the scan finished
+                ViewState::Browsing if done && !was_done => view_state = ViewState::Summary,
+                // Keeping the last folder in hog review queued its contents
+                ViewState::Summary if was_done && !done => {
+                    view_state = ViewState::Browsing;
+                    app_state.next();
+                    preview_manager.reset();
+                }
+                _ => {}
+            }
+        }
+
         // Render based on current view state
         terminal.draw(|frame| {
-            render_with_preview(frame, app_state, preview_manager);
+            render_with_preview(frame, app_state, &decision_engine.history, preview_manager);
 
             // Render overlays
             match view_state {
-                ViewState::Help => render_help_overlay(frame),
+                ViewState::Help => render_help_overlay(
+                    frame,
+                    &move_destinations,
+                    !app_state.duplicate_groups.is_empty(),
+                ),
                 ViewState::Summary => {
                     let stats = decision_engine.get_statistics();
                     render_summary(frame, &stats);
@@ -176,6 +919,48 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                     }
                 }
                 ViewState::Welcome => render_welcome_overlay(frame),
+                ViewState::Timeline => render_timeline_overlay(
+                    frame,
+                    &decision_engine.history,
+                    &app_state.files,
+                    timeline_selected,
+                    timeline_error.as_deref(),
+                ),
+                ViewState::ConfirmDelete => {
+                    if let Some(file) = app_state.current_file() {
+                        render_confirm_delete_overlay(
+                            frame,
+                            file,
+                            &confirm_input,
+                            confirm_error.as_deref(),
+                        );
+                    }
+                }
+                ViewState::ConfirmBulkDelete => render_confirm_bulk_delete_overlay(
+                    frame,
+                    &decision_engine.pending_deletes(),
+                    &confirm_input,
+                    confirm_error.as_deref(),
+                ),
+                ViewState::Renaming => {
+                    if let (Some(file), Some(dialog)) = (app_state.current_file(), &rename_dialog) {
+                        render_rename_overlay(frame, file, dialog);
+                    }
+                }
+                ViewState::ConfirmDuplicates => {
+                    if let (Some(keep), Some((_, group))) = (keep_copy, app_state.current_group()) {
+                        let discard: Vec<&FileEntry> = group
+                            .filter(|&index| index != keep && !decision_engine.is_decided(index))
+                            .map(|index| &app_state.files[index])
+                            .collect();
+                        render_confirm_duplicates_overlay(
+                            frame,
+                            &app_state.files[keep],
+                            &discard,
+                            config.delete_mode,
+                        );
+                    }
+                }
                 ViewState::Browsing => {}
             }
         })?;
@@ -194,8 +979,74 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                         continue;
                     }
                     ViewState::Summary => {
-                        // Any key exits from summary
-                        break;
+                        // Any key exits from summary, once permanent deletes are confirmed
+                        if !decision_engine.is_dry_run()
+                            && !decision_engine.pending_deletes().is_empty()
+                        {
+                            confirm_input = TextInput::default();
+                            confirm_error = None;
+                            view_state = ViewState::ConfirmBulkDelete;
+                            continue;
+                        }
+                        return Ok(true);
+                    }
+                    ViewState::ConfirmDelete => {
+                        match handle_delete_confirm_input(key) {
+                            KeyAction::ConfirmDelete => {
+                                let confirmed = app_state
+                                    .current_file()
+                                    .is_some_and(|file| file.name == confirm_input.value());
+                                if !confirmed {
+                                    confirm_error = Some("Name does not match".to_string());
+                                } else {
+                                    match decision_engine
+                                        .record_decision(app_state.current_index, Decision::Delete)
+                                    {
+                                        Ok(()) => {
+                                            app_state.next();
+                                            preview_manager.reset();
+
+                                            view_state = if is_all_files_processed(
+                                                app_state,
+                                                decision_engine,
+                                            ) {
+                                                ViewState::Summary
+                                            } else {
+                                                ViewState::Browsing
+                                            };
+                                        }
+                                        Err(e) => confirm_error = Some(e.to_string()),
+                                    }
+                                }
+                            }
+                            KeyAction::CancelDelete => {
+                                view_state = ViewState::Browsing;
+                            }
+                            _ => {
+                                confirm_input.handle_key(key);
+                                confirm_error = None;
+                            }
+                        }
+                        continue;
+                    }
+                    ViewState::ConfirmBulkDelete => {
+                        match handle_delete_confirm_input(key) {
+                            KeyAction::ConfirmDelete => {
+                                let count = decision_engine.pending_deletes().len();
+                                if confirm_input.value().trim() == count.to_string() {
+                                    return Ok(true);
+                                }
+                                confirm_error = Some("Count does not match".to_string());
+                            }
+                            KeyAction::CancelDelete => {
+                                view_state = ViewState::Browsing;
+                            }
+                            _ => {
+                                confirm_input.handle_key(key);
+                                confirm_error = None;
+                            }
+                        }
+                        continue;
                     }
                     ViewState::ConfirmTrash => {
                         let action = handle_confirm_input(key);
@@ -206,7 +1057,6 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                                     .record_decision(app_state.current_index, Decision::Trash)
                                     .is_ok()
                                 {
-                                    app_state.record_decision(Decision::Trash);
                                     app_state.next();
                                     preview_manager.reset();
 
@@ -226,6 +1076,38 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                         }
                         continue;
                     }
+                    ViewState::ConfirmDuplicates => {
+                        match handle_confirm_input(key) {
+                            KeyAction::ConfirmTrash => {
+                                if let Some(keep) = keep_copy.take() {
+                                    match keep_only(
+                                        app_state,
+                                        decision_engine,
+                                        keep,
+                                        &trash_decision,
+                                    ) {
+                                        Ok(()) => preview_manager.reset(),
+                                        Err(e) => {
+                                            app_state.notice =
+                                                Some(format!("Cannot resolve duplicates: {}", e));
+                                        }
+                                    }
+                                }
+
+                                view_state = if is_all_files_processed(app_state, decision_engine) {
+                                    ViewState::Summary
+                                } else {
+                                    ViewState::Browsing
+                                };
+                            }
+                            KeyAction::CancelTrash => {
+                                keep_copy = None;
+                                view_state = ViewState::Browsing;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
                     ViewState::Welcome => {
                         // Any key dismisses welcome and starts browsing
                         view_state = ViewState::Browsing;
@@ -237,19 +1119,120 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                         }
                         continue;
                     }
+                    ViewState::Renaming => {
+                        let Some(dialog) = rename_dialog.as_mut() else {
+                            view_state = ViewState::Browsing;
+                            continue;
+                        };
+                        match handle_rename_input(key) {
+                            KeyAction::ConfirmRename => {
+                                let name = dialog.input.value().trim().to_string();
+                                let unchanged = app_state
+                                    .current_file()
+                                    .is_some_and(|file| file.name == name);
+                                if unchanged {
+                                    view_state = ViewState::Browsing;
+                                } else {
+                                    match decision_engine.record_decision(
+                                        app_state.current_index,
+                                        Decision::Rename { name },
+                                    ) {
+                                        Ok(()) => {
+                                            app_state.next();
+                                            preview_manager.reset();
+
+                                            view_state = if is_all_files_processed(
+                                                app_state,
+                                                decision_engine,
+                                            ) {
+                                                ViewState::Summary
+                                            } else {
+                                                ViewState::Browsing
+                                            };
+                                        }
+                                        Err(e) => dialog.error = Some(e.to_string()),
+                                    }
+                                }
+                            }
+                            KeyAction::CancelRename => {
+                                view_state = ViewState::Browsing;
+                            }
+                            KeyAction::NextSuggestion => dialog.next_suggestion(),
+                            _ => {
+                                if dialog.input.handle_key(key) {
+                                    dialog.error = None;
+                                    dialog.selected = None;
+                                }
+                            }
+                        }
+                        continue;
+                    }
+                    ViewState::Timeline => {
+                        let history = &decision_engine.history;
+                        timeline_error = None;
+                        match handle_timeline_input(key) {
+                            KeyAction::Next if timeline_selected + 1 < history.entries().len() => {
+                                timeline_selected += 1;
+                            }
+                            KeyAction::Previous => {
+                                timeline_selected = timeline_selected.saturating_sub(1);
+                            }
+                            KeyAction::TimelineJump => {
+                                let jumped = decision_engine.jump_to(timeline_selected + 1);
+                                resume_after_history(app_state, decision_engine);
+                                preview_manager.reset();
+                                match jumped {
+                                    Ok(()) => view_state = ViewState::Browsing,
+                                    Err(e) => {
+                                        // Point at where history actually stopped
+                                        let in_effect = decision_engine.history.len();
+                                        timeline_selected = in_effect.saturating_sub(1);
+                                        timeline_error = Some(format!(
+                                            "Jump stopped with {} decisions in effect: {}",
+                                            in_effect, e
+                                        ));
+                                    }
+                                }
+                            }
+                            KeyAction::TimelineRevert => {
+                                match decision_engine.revert(timeline_selected) {
+                                    Ok(entry) => {
+                                        app_state.jump_to(entry.index);
+                                        preview_manager.reset();
+                                        timeline_selected = timeline_selected.min(
+                                            decision_engine
+                                                .history
+                                                .entries()
+                                                .len()
+                                                .saturating_sub(1),
+                                        );
+                                    }
+                                    Err(e) => {
+                                        timeline_error = Some(format!("Cannot revert: {}", e))
+                                    }
+                                }
+                            }
+                            KeyAction::Timeline => {
+                                view_state = ViewState::Browsing;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
                     ViewState::Browsing => {}
                 }
 
                 let action = handle_key_event(key);
+                app_state.notice = None;
 
                 match action {
                     KeyAction::Quit => {
                         // Show summary before quitting if any decisions were made
                         let stats = decision_engine.get_statistics();
-                        if stats.kept > 0 || stats.trashed > 0 {
+                        if stats.decided() > 0 {
                             view_state = ViewState::Summary;
                         } else {
-                            break;
+                            return Ok(true);
                         }
                     }
                     KeyAction::Keep => {
@@ -257,7 +1240,6 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                             .record_decision(app_state.current_index, Decision::Keep)
                             .is_ok()
                         {
-                            app_state.record_decision(Decision::Keep);
                             app_state.next();
                             preview_manager.reset();
 
@@ -268,14 +1250,14 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                         }
                     }
                     KeyAction::Trash => {
-                        // Skip confirmation if flag set or dry-run mode
+                        // Skip confirmation if flag set or dry-run mode. Permanent
+                        // deletes are still confirmed in bulk before quitting.
                         if config.skip_confirm || decision_engine.is_dry_run() {
                             // Execute trash immediately
                             if decision_engine
-                                .record_decision(app_state.current_index, Decision::Trash)
+                                .record_decision(app_state.current_index, trash_decision.clone())
                                 .is_ok()
                             {
-                                app_state.record_decision(Decision::Trash);
                                 app_state.next();
                                 preview_manager.reset();
 
@@ -284,11 +1266,87 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                                     view_state = ViewState::Summary;
                                 }
                             }
+                        } else if config.delete_mode {
+                            confirm_input = TextInput::default();
+                            confirm_error = None;
+                            view_state = ViewState::ConfirmDelete;
                         } else {
                             // Show confirmation dialog
                             view_state = ViewState::ConfirmTrash;
                         }
                     }
+                    KeyAction::Move(slot) => {
+                        if let Some(dest) = move_destinations.get(slot) {
+                            let decision = Decision::Move { dest: dest.clone() };
+                            match decision_engine.record_decision(app_state.current_index, decision)
+                            {
+                                Ok(()) => {
+                                    app_state.next();
+                                    preview_manager.reset();
+
+                                    if is_all_files_processed(app_state, decision_engine) {
+                                        view_state = ViewState::Summary;
+                                    }
+                                }
+                                Err(e) => {
+                                    app_state.notice =
+                                        Some(format!("Cannot move to {:?}: {}", dest, e));
+                                }
+                            }
+                        }
+                    }
+                    KeyAction::ForgetKept => {
+                        if let Some(file) = app_state.current_file() {
+                            let path = file.path.clone();
+                            app_state.keep_list.forget(&path);
+                        }
+                    }
+                    KeyAction::Archive => {
+                        match decision_engine
+                            .record_decision(app_state.current_index, Decision::Archive)
+                        {
+                            Ok(()) => {
+                                app_state.next();
+                                preview_manager.reset();
+
+                                if is_all_files_processed(app_state, decision_engine) {
+                                    view_state = ViewState::Summary;
+                                }
+                            }
+                            Err(e) => {
+                                app_state.notice = Some(format!("Cannot archive: {}", e));
+                            }
+                        }
+                    }
+                    KeyAction::KeepOnly(rule) => {
+                        if let Some((_, group)) = app_state.current_group() {
+                            let keep = group.start + rule.pick(&app_state.files[group]);
+                            if config.skip_confirm || decision_engine.is_dry_run() {
+                                match keep_only(app_state, decision_engine, keep, &trash_decision) {
+                                    Ok(()) => {
+                                        preview_manager.reset();
+
+                                        if is_all_files_processed(app_state, decision_engine) {
+                                            view_state = ViewState::Summary;
+                                        }
+                                    }
+                                    Err(e) => {
+                                        app_state.notice =
+                                            Some(format!("Cannot resolve duplicates: {}", e));
+                                    }
+                                }
+                            } else {
+                                keep_copy = Some(keep);
+                                view_state = ViewState::ConfirmDuplicates;
+                            }
+                        }
+                    }
+                    KeyAction::Rename => {
+                        if let Some(file) = app_state.current_file() {
+                            rename_dialog = Some(RenameDialog::new(file));
+                            view_state = ViewState::Renaming;
+                        }
+                    }
                     KeyAction::Next => {
                         app_state.next();
                         preview_manager.reset();
@@ -297,15 +1355,33 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                         app_state.previous();
                         preview_manager.reset();
                     }
-                    KeyAction::Undo => {
-                        if decision_engine.undo().is_ok() {
-                            app_state.undo();
+                    KeyAction::Undo => match decision_engine.undo() {
+                        Ok(entry) => {
+                            app_state.jump_to(entry.index);
                             preview_manager.reset();
                             // Return to browsing if we were in summary
                             if view_state == ViewState::Summary {
                                 view_state = ViewState::Browsing;
                             }
                         }
+                        Err(e) => app_state.notice = Some(format!("Cannot undo: {}", e)),
+                    },
+                    KeyAction::Redo => match decision_engine.redo() {
+                        Ok(entry) => {
+                            app_state.jump_to(entry.index);
+                            app_state.next();
+                            preview_manager.reset();
+
+                            if is_all_files_processed(app_state, decision_engine) {
+                                view_state = ViewState::Summary;
+                            }
+                        }
+                        Err(e) => app_state.notice = Some(format!("Cannot redo: {}", e)),
+                    },
+                    KeyAction::Timeline => {
+                        timeline_selected = decision_engine.history.len().saturating_sub(1);
+                        timeline_error = None;
+                        view_state = ViewState::Timeline;
                     }
                     KeyAction::Help => {
                         view_state = ViewState::Help;
@@ -333,8 +1409,16 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
                             }
                         }
                     }
-                    KeyAction::ConfirmTrash | KeyAction::CancelTrash => {
-                        // These actions are only handled in ConfirmTrash state
+                    KeyAction::ConfirmTrash
+                    | KeyAction::CancelTrash
+                    | KeyAction::TimelineJump
+                    | KeyAction::TimelineRevert
+                    | KeyAction::ConfirmDelete
+                    | KeyAction::CancelDelete
+                    | KeyAction::ConfirmRename
+                    | KeyAction::CancelRename
+                    | KeyAction::NextSuggestion => {
+                        // These actions are only handled in their overlay states
                         // Ignore them here
                     }
                     KeyAction::None => {}
@@ -342,12 +1426,39 @@ fn run_loop<B: ratatui::backend::Backend + std::io::Write>(
             }
         }
     }
+}
+
+/// Moves the cursor to where reviewing should continue after jumping through history
+fn resume_after_history(app_state: &mut AppState, decision_engine: &DecisionEngine) {
+    let history = &decision_engine.history;
+    if let Some(entry) = history.next_redo() {
+        app_state.jump_to(entry.index);
+    } else if let Some(entry) = history.last_applied() {
+        app_state.jump_to(entry.index);
+        app_state.next();
+    } else {
+        app_state.jump_to(0);
+    }
+}
 
+/// Keeps the copy at `keep`, records `discard` for the rest of its duplicate
+/// group and moves on to the next group
+fn keep_only(
+    app_state: &mut AppState,
+    decision_engine: &mut DecisionEngine,
+    keep: usize,
+    discard: &Decision,
+) -> io::Result<()> {
+    let Some((_, group)) = app_state.current_group() else {
+        return Ok(());
+    };
+    decision_engine.resolve_duplicates(group, keep, discard.clone())?;
+    app_state.next_group();
     Ok(())
 }
 
 /// Checks if all files have been processed
 fn is_all_files_processed(app_state: &AppState, decision_engine: &DecisionEngine) -> bool {
     let stats = decision_engine.get_statistics();
-    stats.kept + stats.trashed >= app_state.files.len()
+    !app_state.scanning && stats.decided() >= app_state.files.len()
 }
@@ -1,7 +1,7 @@
 // Preview module for generating file previews with syntax highlighting, images, and PDFs
 #![allow(dead_code)]
 
-use crate::domain::FileEntry;
+use crate::domain::{FileEntry, FileType};
 use image::{DynamicImage, GenericImageView, Pixel};
 use pdfium_render::prelude::*;
 use ratatui::{
@@ -9,7 +9,7 @@ use ratatui::{
     text::{Line, Span},
 };
 use std::fs;
-use std::io;
+use std::io::{self, Read};
 use std::path::Path;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::ThemeSet;
@@ -40,7 +40,9 @@ pub fn detect_syntax_from_extension(extension: &str) -> Option<String> {
 
 /// Reads the first N lines of a text file
 pub fn read_file_lines(path: &Path, max_lines: usize) -> io::Result<Vec<String>> {
-    let content = fs::read_to_string(path)?;
+    // Text detection lets through Latin-1 and other non-UTF-8 text
+    let bytes = fs::read(path)?;
+    let content = String::from_utf8_lossy(&bytes);
     let lines: Vec<String> = content
         .lines()
         .take(max_lines)
@@ -99,7 +101,11 @@ pub fn generate_text_preview(file_entry: &FileEntry) -> io::Result<Vec<String>>
 
 /// Loads an image from a file path
 pub fn load_image(path: &Path) -> io::Result<DynamicImage> {
-    image::open(path).map_err(|e| io::Error::other(format!("Image loading error: {}", e)))
+    // The format comes from the contents, so mislabelled images still load
+    image::ImageReader::open(path)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|e| io::Error::other(format!("Image loading error: {}", e)))
 }
 
 /// Calculates new dimensions to fit image within max width and height while preserving aspect ratio
@@ -422,10 +428,382 @@ pub fn generate_pdf_preview(file_entry: &FileEntry) -> io::Result<PreviewContent
     }
 }
 
+/// Number of entries listed in an archive preview
+const MAX_ARCHIVE_ENTRIES: usize = 40;
+
+/// How much of a file is read to describe its format
+const HEADER_LEN: u64 = 4096;
+
+/// Generates a metadata preview for files whose contents are not displayed:
+/// archives, documents, media, fonts and executables
+pub fn generate_metadata_preview(file_entry: &FileEntry) -> io::Result<PreviewContent> {
+    let head = read_header(&file_entry.path).unwrap_or_default();
+    let format = describe_format(&head).or_else(|| {
+        file_entry
+            .path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_uppercase())
+    });
+
+    let mut lines = vec![
+        format!("{}: {}", file_entry.file_type.label(), file_entry.name),
+        format!("Size: {} bytes", file_entry.size),
+        format!(
+            "Modified: {}",
+            file_entry
+                .modified_date
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        ),
+    ];
+    if let Some(format) = format {
+        lines.push(format!("Format: {}", format));
+    }
+    lines.push(String::new());
+
+    match file_entry.file_type {
+        FileType::Archive => match list_archive(&file_entry.path, &head) {
+            Some(entries) => lines.extend(entries),
+            None => lines.push("[Archive contents not listed]".to_string()),
+        },
+        FileType::Document | FileType::Spreadsheet => {
+            lines.extend(document_details(&file_entry.path, &head));
+            lines.push("Press 'o' to open in your default viewer.".to_string());
+        }
+        FileType::Audio | FileType::Video => {
+            lines.push("Press 'o' to open in your default player.".to_string());
+        }
+        FileType::Font => {
+            lines.push("Press 'o' to open in your font viewer.".to_string());
+        }
+        FileType::Executable => {
+            lines.push("[Executable content not displayed]".to_string());
+        }
+        _ => lines.push("[Binary content not displayed]".to_string()),
+    }
+
+    Ok(PreviewContent::Text(lines))
+}
+
+/// The first `HEADER_LEN` bytes of the file at `path`
+fn read_header(path: &Path) -> io::Result<Vec<u8>> {
+    let mut head = Vec::new();
+    fs::File::open(path)?
+        .take(HEADER_LEN)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// A human-readable name for the format `head` starts with
+fn describe_format(head: &[u8]) -> Option<String> {
+    let at = |offset: usize, magic: &[u8]| head.get(offset..).is_some_and(|h| h.starts_with(magic));
+
+    let name = if at(0, b"PK\x03\x04") {
+        "ZIP"
+    } else if at(0, b"\x1f\x8b") {
+        "gzip"
+    } else if at(0, b"7z\xbc\xaf\x27\x1c") {
+        "7-Zip"
+    } else if at(0, b"\xfd7zXZ\x00") {
+        "xz"
+    } else if at(0, b"BZh") {
+        "bzip2"
+    } else if at(0, b"\x28\xb5\x2f\xfd") {
+        "Zstandard"
+    } else if at(0, b"Rar!\x1a\x07") {
+        "RAR"
+    } else if at(257, b"ustar") {
+        "tar"
+    } else if at(0, b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1") {
+        "Microsoft Office (97-2003)"
+    } else if at(0, b"{\\rtf") {
+        "Rich Text"
+    } else if at(0, b"ID3") {
+        "MP3"
+    } else if at(0, b"fLaC") {
+        "FLAC"
+    } else if at(0, b"OggS") {
+        "Ogg"
+    } else if at(0, b"MThd") {
+        "MIDI"
+    } else if at(0, b"RIFF") && at(8, b"WAVE") {
+        "WAV"
+    } else if at(0, b"RIFF") && at(8, b"AVI ") {
+        "AVI"
+    } else if at(0, b"FORM") && (at(8, b"AIFF") || at(8, b"AIFC")) {
+        "AIFF"
+    } else if at(4, b"ftyp") {
+        return Some(format!(
+            "MPEG-4 ({})",
+            String::from_utf8_lossy(&head[8..head.len().min(12)]).trim()
+        ));
+    } else if at(0, b"\x1a\x45\xdf\xa3") {
+        "Matroska / WebM"
+    } else if at(0, b"FLV\x01") {
+        "Flash Video"
+    } else if at(0, b"wOFF") {
+        "WOFF"
+    } else if at(0, b"wOF2") {
+        "WOFF2"
+    } else if at(0, b"OTTO") {
+        "OpenType"
+    } else if at(0, b"\x00\x01\x00\x00\x00") {
+        "TrueType"
+    } else if at(0, b"ttcf") {
+        "TrueType collection"
+    } else {
+        return describe_executable(head);
+    };
+    Some(name.to_string())
+}
+
+/// Container format and processor architecture of an ELF, PE or Mach-O file
+fn describe_executable(head: &[u8]) -> Option<String> {
+    let u16_at = |offset: usize, big_endian: bool| {
+        let bytes: [u8; 2] = head.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let u32_at = |offset: usize, big_endian: bool| {
+        let bytes: [u8; 4] = head.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    if head.starts_with(b"\x7fELF") {
+        let bits = if head.get(4) == Some(&2) { 64 } else { 32 };
+        let machine = match u16_at(18, head.get(5) == Some(&2))? {
+            0x03 => "x86",
+            0x08 => "MIPS",
+            0x14 => "PowerPC",
+            0x15 => "PowerPC 64",
+            0x28 => "ARM",
+            0x3e => "x86-64",
+            0xb7 => "AArch64",
+            0xf3 => "RISC-V",
+            _ => "unknown architecture",
+        };
+        return Some(format!("ELF {}-bit, {}", bits, machine));
+    }
+
+    if head.starts_with(b"MZ") {
+        let pe = u32_at(0x3c, false)? as usize;
+        if head.get(pe..pe + 4) != Some(b"PE\0\0") {
+            return Some("DOS executable".to_string());
+        }
+        let machine = match u16_at(pe + 4, false)? {
+            0x014c => "x86",
+            0x8664 => "x86-64",
+            0x01c0 | 0x01c4 => "ARM",
+            0xaa64 => "ARM64",
+            _ => "unknown architecture",
+        };
+        let kind = if u16_at(pe + 22, false)? & 0x2000 != 0 {
+            "DLL"
+        } else {
+            "executable"
+        };
+        return Some(format!("Windows {}, {}", kind, machine));
+    }
+
+    let (bits, big_endian) = match head.get(..4)? {
+        b"\xfe\xed\xfa\xce" => (32, true),
+        b"\xfe\xed\xfa\xcf" => (64, true),
+        b"\xce\xfa\xed\xfe" => (32, false),
+        b"\xcf\xfa\xed\xfe" => (64, false),
+        b"\xca\xfe\xba\xbe" => return Some("Mach-O universal binary or Java class".to_string()),
+        _ => return None,
+    };
+    let cpu = match u32_at(4, big_endian)? {
+        0x0000_0007 => "x86",
+        0x0100_0007 => "x86-64",
+        0x0000_000c => "ARM",
+        0x0100_000c => "ARM64",
+        0x0000_0012 | 0x0100_0012 => "PowerPC",
+        _ => "unknown architecture",
+    };
+    Some(format!("Mach-O {}-bit, {}", bits, cpu))
+}
+
+/// Entry count, total size and the first entry names of a zip or tar file.
+/// A tar file is only read as far as the listed entries, so a long one is
+/// summarised as "N+ entries" without decompressing the rest.
+fn list_archive(path: &Path, head: &[u8]) -> Option<Vec<String>> {
+    let entries: Vec<(String, u64)> = if head.starts_with(b"PK\x03\x04") {
+        let mut zip = zip::ZipArchive::new(fs::File::open(path).ok()?).ok()?;
+        (0..zip.len())
+            .filter_map(|i| {
+                zip.by_index(i)
+                    .ok()
+                    .map(|e| (e.name().to_string(), e.size()))
+            })
+            .collect()
+    } else if head.get(257..262) == Some(b"ustar") {
+        tar_entries(tar::Archive::new(fs::File::open(path).ok()?))?
+    } else if head.starts_with(b"\x1f\x8b") {
+        // Only a gzipped tar has entries; anything else is a single stream
+        let gz = flate2::read::GzDecoder::new(fs::File::open(path).ok()?);
+        tar_entries(tar::Archive::new(gz))?
+    } else {
+        return None;
+    };
+    let is_tar = !head.starts_with(b"PK\x03\x04");
+
+    let mut lines = if is_tar && entries.len() > MAX_ARCHIVE_ENTRIES {
+        vec![format!("{}+ entries", MAX_ARCHIVE_ENTRIES), String::new()]
+    } else {
+        let total: u64 = entries.iter().map(|(_, size)| size).sum();
+        vec![
+            format!("{} entries, {} bytes uncompressed", entries.len(), total),
+            String::new(),
+        ]
+    };
+    lines.extend(
+        entries
+            .iter()
+            .take(MAX_ARCHIVE_ENTRIES)
+            .map(|(name, size)| format!("  {}  ({} bytes)", name, size)),
+    );
+    if entries.len() > MAX_ARCHIVE_ENTRIES {
+        lines.push(if is_tar {
+            "  ... and more".to_string()
+        } else {
+            format!("  ... and {} more", entries.len() - MAX_ARCHIVE_ENTRIES)
+        });
+    }
+    Some(lines)
+}
+
+/// Names and sizes of the first entries of a tar stream, if it is one: one
+/// more than a preview lists, to tell whether there are others
+fn tar_entries<R: Read>(mut archive: tar::Archive<R>) -> Option<Vec<(String, u64)>> {
+    let mut entries = Vec::new();
+    for entry in archive.entries().ok()?.take(MAX_ARCHIVE_ENTRIES + 1) {
+        let entry = entry.ok()?;
+        let name = entry.path().ok()?.to_string_lossy().into_owned();
+        entries.push((name, entry.size()));
+    }
+    Some(entries)
+}
+
+/// Page, sheet or slide counts of Office Open XML and OpenDocument files
+fn document_details(path: &Path, head: &[u8]) -> Vec<String> {
+    if !head.starts_with(b"PK\x03\x04") {
+        return Vec::new();
+    }
+    let Some(zip) = fs::File::open(path)
+        .ok()
+        .and_then(|file| zip::ZipArchive::new(file).ok())
+    else {
+        return Vec::new();
+    };
+
+    let count = |prefix: &str| {
+        zip.file_names()
+            .filter(|name| name.starts_with(prefix) && name.ends_with(".xml"))
+            .count()
+    };
+    let mut lines = Vec::new();
+    for (label, prefix) in [
+        ("Sheets", "xl/worksheets/sheet"),
+        ("Slides", "ppt/slides/slide"),
+    ] {
+        let n = count(prefix);
+        if n > 0 {
+            lines.push(format!("{}: {}", label, n));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(format!("{} parts", zip.len()));
+    }
+    lines.push(String::new());
+    lines
+}
+
+/// How many levels of a directory preview's tree are expanded
+const MAX_TREE_DEPTH: usize = 3;
+
+/// Generates a tree listing of a directory reviewed as a whole
+pub fn generate_directory_preview(file_entry: &FileEntry) -> io::Result<PreviewContent> {
+    let mut lines = vec![
+        format!("Directory: {}", file_entry.name),
+        format!(
+            "Size: {} bytes in {} files",
+            file_entry.size,
+            file_entry.file_count.unwrap_or(0)
+        ),
+        format!(
+            "Last modified inside: {}",
+            file_entry
+                .modified_date
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        ),
+        String::new(),
+        format!("{}/", file_entry.name),
+    ];
+    let max_lines = lines.len() + MAX_PREVIEW_LINES;
+    tree_lines(&file_entry.path, "", 1, &mut lines, max_lines)?;
+
+    Ok(PreviewContent::Text(lines))
+}
+
+/// Appends the contents of `dir` to `lines` as tree branches, folders first
+fn tree_lines(
+    dir: &Path,
+    prefix: &str,
+    depth: usize,
+    lines: &mut Vec<String>,
+    max_lines: usize,
+) -> io::Result<()> {
+    let mut entries: Vec<(String, Option<fs::Metadata>)> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            (name, entry.metadata().ok())
+        })
+        .collect();
+    let is_dir = |metadata: &Option<fs::Metadata>| metadata.as_ref().is_some_and(|m| m.is_dir());
+    entries.sort_by(|(a_name, a), (b_name, b)| is_dir(b).cmp(&is_dir(a)).then(a_name.cmp(b_name)));
+
+    for (i, (name, metadata)) in entries.iter().enumerate() {
+        if lines.len() >= max_lines {
+            lines.push(format!("{}… {} more", prefix, entries.len() - i));
+            return Ok(());
+        }
+
+        let last = i + 1 == entries.len();
+        let branch = if last { "└── " } else { "├── " };
+        let path = dir.join(name);
+
+        if is_dir(metadata) {
+            if depth < MAX_TREE_DEPTH {
+                lines.push(format!("{}{}{}/", prefix, branch, name));
+                let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+                tree_lines(&path, &child_prefix, depth + 1, lines, max_lines).ok();
+            } else {
+                // Too deep to expand: sum it up instead
+                let inner = FileEntry::from_dir(&path).ok();
+                let count = inner.and_then(|e| e.file_count).unwrap_or(0);
+                lines.push(format!("{}{}{}/ ({} files)", prefix, branch, name, count));
+            }
+        } else {
+            let size = metadata.as_ref().map_or(0, |m| m.len());
+            lines.push(format!("{}{}{} ({} bytes)", prefix, branch, name, size));
+        }
+    }
+
+    Ok(())
+}
+
 /// Generates a preview for any file type
 pub fn generate_preview(file_entry: &FileEntry) -> io::Result<PreviewContent> {
-    use crate::domain::FileType;
-
     match file_entry.file_type {
         FileType::Text => generate_text_preview(file_entry).map(PreviewContent::Text),
         FileType::Binary => Ok(PreviewContent::Text(vec![
@@ -436,13 +814,20 @@ pub fn generate_preview(file_entry: &FileEntry) -> io::Result<PreviewContent> {
         ])),
         FileType::Image => generate_image_preview(file_entry),
         FileType::Pdf => generate_pdf_preview(file_entry),
+        FileType::Directory => generate_directory_preview(file_entry),
+        FileType::Document
+        | FileType::Spreadsheet
+        | FileType::Archive
+        | FileType::Audio
+        | FileType::Video
+        | FileType::Font
+        | FileType::Executable => generate_metadata_preview(file_entry),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::FileType;
     use chrono::Utc;
     use std::fs;
     use std::path::PathBuf;
@@ -528,6 +913,10 @@ mod tests {
             size: rust_code.len() as u64,
             modified_date: Utc::now(),
             file_type: FileType::Text,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         // Copy to the expected path
@@ -559,6 +948,10 @@ mod tests {
             size: content.len() as u64,
             modified_date: Utc::now(),
             file_type: FileType::Text,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_text_preview(&file_entry).unwrap();
@@ -575,6 +968,10 @@ mod tests {
             size: 1024,
             modified_date: Utc::now(),
             file_type: FileType::Binary,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_preview(&file_entry).unwrap();
@@ -588,6 +985,169 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generate_preview_archive_lists_entries() {
+        use std::io::Write;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("photos.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        for name in ["a.jpg", "b.jpg"] {
+            zip.start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"pixels").unwrap();
+        }
+        zip.finish().unwrap();
+
+        let file_entry = FileEntry::from_path(&path).unwrap();
+        assert_eq!(file_entry.file_type, FileType::Archive);
+
+        match generate_preview(&file_entry).unwrap() {
+            PreviewContent::Text(lines) => {
+                assert_eq!(lines[0], "Archive: photos.zip");
+                assert!(lines.contains(&"Format: ZIP".to_string()));
+                assert!(lines.contains(&"2 entries, 12 bytes uncompressed".to_string()));
+                assert!(lines.iter().any(|line| line.contains("b.jpg")));
+            }
+            _ => panic!("Expected Text preview for archive"),
+        }
+    }
+
+    #[test]
+    fn test_generate_preview_long_tar_gz_stops_after_listed_entries() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("logs.tar.gz");
+        let gz = flate2::write::GzEncoder::new(
+            fs::File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut tar = tar::Builder::new(gz);
+        for i in 0..MAX_ARCHIVE_ENTRIES + 10 {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(3);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, format!("{}.log", i), &b"log"[..])
+                .unwrap();
+        }
+        tar.into_inner().unwrap().finish().unwrap();
+
+        let file_entry = FileEntry::from_path(&path).unwrap();
+        match generate_preview(&file_entry).unwrap() {
+            PreviewContent::Text(lines) => {
+                assert!(lines.contains(&format!("{}+ entries", MAX_ARCHIVE_ENTRIES)));
+                assert!(lines.iter().any(|line| line.contains("0.log")));
+                assert!(!lines
+                    .iter()
+                    .any(|line| line.contains(&format!("{}.log", MAX_ARCHIVE_ENTRIES))));
+                assert_eq!(lines.last().unwrap(), "  ... and more");
+            }
+            _ => panic!("Expected Text preview for archive"),
+        }
+    }
+
+    #[test]
+    fn test_generate_preview_media_and_fonts() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        for (name, contents, heading) in [
+            (
+                "song.mp3",
+                &b"ID3\x04\x00\x00\x00\x00"[..],
+                "Audio: song.mp3",
+            ),
+            (
+                "clip",
+                &b"\x00\x00\x00\x18ftypisom\x00\x00"[..],
+                "Video: clip",
+            ),
+            (
+                "font.woff2",
+                &b"wOF2\x00\x01\x00\x00"[..],
+                "Font: font.woff2",
+            ),
+        ] {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, contents).unwrap();
+            let file_entry = FileEntry::from_path(&path).unwrap();
+
+            match generate_preview(&file_entry).unwrap() {
+                PreviewContent::Text(lines) => {
+                    assert_eq!(lines[0], heading);
+                    assert!(lines.iter().any(|line| line.starts_with("Format: ")));
+                }
+                _ => panic!("Expected Text preview for {}", name),
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_directory_preview() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("site");
+        fs::create_dir_all(dir.join("assets/img/icons/small")).unwrap();
+        fs::write(dir.join("index.html"), b"<html>").unwrap();
+        fs::write(dir.join("assets/style.css"), b"body {}").unwrap();
+        fs::write(dir.join("assets/img/icons/small/a.png"), b"png").unwrap();
+        fs::write(dir.join("assets/img/icons/small/b.png"), b"png").unwrap();
+
+        let file_entry = FileEntry::from_dir(&dir).unwrap();
+        match generate_preview(&file_entry).unwrap() {
+            PreviewContent::Text(lines) => {
+                assert_eq!(lines[0], "Directory: site");
+                assert_eq!(lines[1], "Size: 19 bytes in 4 files");
+                let tree = &lines[4..];
+                assert_eq!(
+                    tree,
+                    [
+                        "site/",
+                        "├── assets/",
+                        "│   ├── img/",
+                        "│   │   └── icons/ (2 files)",
+                        "│   └── style.css (7 bytes)",
+                        "└── index.html (6 bytes)",
+                    ]
+                );
+            }
+            _ => panic!("Expected Text preview for directory"),
+        }
+    }
+
+    #[test]
+    fn test_describe_executable() {
+        let mut elf = vec![0u8; 64];
+        elf[..6].copy_from_slice(b"\x7fELF\x02\x01");
+        elf[18] = 0x3e;
+        assert_eq!(
+            describe_executable(&elf).as_deref(),
+            Some("ELF 64-bit, x86-64")
+        );
+
+        let mut pe = vec![0u8; 256];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c] = 0x80;
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x84..0x86].copy_from_slice(&0xaa64u16.to_le_bytes());
+        pe[0x96..0x98].copy_from_slice(&0x2000u16.to_le_bytes());
+        assert_eq!(
+            describe_executable(&pe).as_deref(),
+            Some("Windows DLL, ARM64")
+        );
+
+        let macho = b"\xcf\xfa\xed\xfe\x0c\x00\x00\x01";
+        assert_eq!(
+            describe_executable(macho).as_deref(),
+            Some("Mach-O 64-bit, ARM64")
+        );
+        assert_eq!(describe_executable(b"plain text"), None);
+    }
+
     #[test]
     fn test_generate_preview_pdf_dispatches_correctly() {
         // Test that generate_preview correctly dispatches PDF files to generate_pdf_preview
@@ -598,6 +1158,10 @@ mod tests {
             size: 4096,
             modified_date: Utc::now(),
             file_type: FileType::Pdf,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_preview(&file_entry).unwrap();
@@ -721,6 +1285,21 @@ mod tests {
         assert_eq!(loaded.dimensions(), (10, 10));
     }
 
+    #[test]
+    fn test_load_image_with_wrong_extension() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let png_path = temp_dir.path().join("test.png");
+        let img = image::RgbImage::from_fn(10, 10, |_, _| image::Rgb([0, 255, 0]));
+        img.save(&png_path).unwrap();
+        let image_path = temp_dir.path().join("test.jpg");
+        fs::rename(&png_path, &image_path).unwrap();
+
+        let loaded = load_image(&image_path).unwrap();
+        assert_eq!(loaded.dimensions(), (10, 10));
+    }
+
     #[test]
     fn test_load_image_nonexistent() {
         let result = load_image(Path::new("/nonexistent/image.png"));
@@ -748,6 +1327,10 @@ mod tests {
             size: fs::metadata(&image_path).unwrap().len(),
             modified_date: Utc::now(),
             file_type: FileType::Image,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_image_preview(&file_entry).unwrap();
@@ -787,6 +1370,10 @@ mod tests {
             size: fs::metadata(&image_path).unwrap().len(),
             modified_date: Utc::now(),
             file_type: FileType::Image,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_preview(&file_entry).unwrap();
@@ -884,6 +1471,10 @@ mod tests {
             size: fs::metadata(&pdf_path).unwrap().len(),
             modified_date: Utc::now(),
             file_type: FileType::Pdf,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_pdf_preview(&file_entry).unwrap();
@@ -909,6 +1500,10 @@ mod tests {
             size: 0,
             modified_date: Utc::now(),
             file_type: FileType::Pdf,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_pdf_preview(&file_entry).unwrap();
@@ -945,6 +1540,10 @@ mod tests {
             size: fs::metadata(&pdf_path).unwrap().len(),
             modified_date: Utc::now(),
             file_type: FileType::Pdf,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_pdf_preview(&file_entry).unwrap();
@@ -992,6 +1591,10 @@ mod tests {
             size: fs::metadata(&pdf_path).unwrap().len(),
             modified_date: Utc::now(),
             file_type: FileType::Pdf,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         };
 
         let preview = generate_preview(&file_entry).unwrap();
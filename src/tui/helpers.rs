@@ -1,3 +1,5 @@
+use std::path::{Component, Path};
+
 /// Calculates progress percentage
 pub fn calculate_progress(current: usize, total: usize) -> f64 {
     if total == 0 {
@@ -24,6 +26,33 @@ pub fn format_file_size(size: u64) -> String {
     }
 }
 
+/// Shortens a move destination to its last two components for display
+pub fn format_destination(dest: &Path) -> String {
+    let names: Vec<_> = dest
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect();
+
+    match names.as_slice() {
+        [] => dest.display().to_string(),
+        [name] => name.to_string(),
+        [.., parent, name] => format!("{}/{}", parent, name),
+    }
+}
+
+/// The directories of a relative file path with a trailing slash, or "" for a top-level file
+pub fn parent_prefix(relative: &Path) -> String {
+    match relative.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => {
+            format!("{}{}", parent.display(), std::path::MAIN_SEPARATOR)
+        }
+        _ => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,4 +74,20 @@ mod tests {
         assert_eq!(format_file_size(1024 * 1024), "1.0 MB");
         assert_eq!(format_file_size(1024 * 1024 * 1024), "1.0 GB");
     }
+
+    #[test]
+    fn test_format_destination() {
+        assert_eq!(
+            format_destination(Path::new("/home/me/Photos/2024")),
+            "Photos/2024"
+        );
+        assert_eq!(format_destination(Path::new("Receipts")), "Receipts");
+        assert_eq!(format_destination(Path::new("/")), "/");
+    }
+
+    #[test]
+    fn test_parent_prefix() {
+        assert_eq!(parent_prefix(Path::new("notes.txt")), "");
+        assert_eq!(parent_prefix(Path::new("old/2023/notes.txt")), "old/2023/");
+    }
 }
@@ -1,3 +1,4 @@
+use crate::domain::KeepRule;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// Represents the result of handling a key event
@@ -19,6 +20,34 @@ pub enum KeyAction {
     Previous,
     /// Undo last decision
     Undo,
+    /// Redo last undone decision
+    Redo,
+    /// Move current file to the destination in this slot (0-based)
+    Move(usize),
+    /// Permanently delete the file once its name has been typed
+    ConfirmDelete,
+    /// Close a delete confirmation without deleting
+    CancelDelete,
+    /// Forget that the current file was kept in an earlier run
+    ForgetKept,
+    /// Append current file to the session archive
+    Archive,
+    /// Keep the file of the current duplicate group chosen by the rule, trash the rest
+    KeepOnly(KeepRule),
+    /// Open the rename editor for the current file
+    Rename,
+    /// Rename the file to the name being edited
+    ConfirmRename,
+    /// Close the rename editor without renaming
+    CancelRename,
+    /// Fill the rename editor with the next suggested name
+    NextSuggestion,
+    /// Toggle the decision timeline
+    Timeline,
+    /// Jump back (or forward) to the selected point in the timeline
+    TimelineJump,
+    /// Revert only the selected decision in the timeline
+    TimelineRevert,
     /// Toggle help overlay
     Help,
     /// Open current file in editor/application
@@ -39,6 +68,9 @@ pub fn handle_key_event(key: KeyEvent) -> KeyAction {
         (KeyCode::Right, KeyModifiers::NONE) => KeyAction::Keep,
         (KeyCode::Char('k'), KeyModifiers::NONE) => KeyAction::Keep,
 
+        // Forget an earlier keep: K
+        (KeyCode::Char('K'), KeyModifiers::NONE | KeyModifiers::SHIFT) => KeyAction::ForgetKept,
+
         // Trash: Left arrow or t
         (KeyCode::Left, KeyModifiers::NONE) => KeyAction::Trash,
         (KeyCode::Char('t'), KeyModifiers::NONE) => KeyAction::Trash,
@@ -53,6 +85,39 @@ pub fn handle_key_event(key: KeyEvent) -> KeyAction {
         (KeyCode::Char('u'), KeyModifiers::NONE) => KeyAction::Undo,
         (KeyCode::Char('z'), KeyModifiers::CONTROL) => KeyAction::Undo,
 
+        // Move to destination slot: 1-9
+        (KeyCode::Char(c @ '1'..='9'), KeyModifiers::NONE) => {
+            KeyAction::Move(c as usize - '1' as usize)
+        }
+
+        // Redo: r or Ctrl+Y
+        (KeyCode::Char('r'), KeyModifiers::NONE) => KeyAction::Redo,
+        (KeyCode::Char('y'), KeyModifiers::CONTROL) => KeyAction::Redo,
+
+        // Timeline: h
+        (KeyCode::Char('h'), KeyModifiers::NONE) => KeyAction::Timeline,
+
+        // Archive: a
+        (KeyCode::Char('a'), KeyModifiers::NONE) => KeyAction::Archive,
+
+        // Keep one file of a duplicate group: N (newest), O (oldest), P (shortest path), L (largest)
+        (KeyCode::Char('N'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            KeyAction::KeepOnly(KeepRule::Newest)
+        }
+        (KeyCode::Char('O'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            KeyAction::KeepOnly(KeepRule::Oldest)
+        }
+        (KeyCode::Char('P'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            KeyAction::KeepOnly(KeepRule::ShortestPath)
+        }
+        (KeyCode::Char('L'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            KeyAction::KeepOnly(KeepRule::Largest)
+        }
+
+        // Rename: n or F2
+        (KeyCode::Char('n'), KeyModifiers::NONE) => KeyAction::Rename,
+        (KeyCode::F(2), KeyModifiers::NONE) => KeyAction::Rename,
+
         // Help: ?
         (KeyCode::Char('?'), KeyModifiers::NONE) => KeyAction::Help,
 
@@ -81,6 +146,55 @@ pub fn handle_confirm_input(key: KeyEvent) -> KeyAction {
     }
 }
 
+/// Maps keyboard events to timeline actions
+/// Used when ViewState is Timeline
+pub fn handle_timeline_input(key: KeyEvent) -> KeyAction {
+    match (key.code, key.modifiers) {
+        // Select entry
+        (KeyCode::Down, KeyModifiers::NONE) => KeyAction::Next,
+        (KeyCode::Up, KeyModifiers::NONE) => KeyAction::Previous,
+        (KeyCode::Char('j'), KeyModifiers::NONE) => KeyAction::Next,
+        (KeyCode::Char('i'), KeyModifiers::NONE) => KeyAction::Previous,
+
+        // Jump to the selected point: Enter
+        (KeyCode::Enter, KeyModifiers::NONE) => KeyAction::TimelineJump,
+
+        // Revert only the selected decision: x or Delete
+        (KeyCode::Char('x'), KeyModifiers::NONE) => KeyAction::TimelineRevert,
+        (KeyCode::Delete, KeyModifiers::NONE) => KeyAction::TimelineRevert,
+
+        // Close: h, q or Esc
+        (KeyCode::Char('h'), KeyModifiers::NONE) => KeyAction::Timeline,
+        (KeyCode::Char('q'), KeyModifiers::NONE) => KeyAction::Timeline,
+        (KeyCode::Esc, KeyModifiers::NONE) => KeyAction::Timeline,
+
+        _ => KeyAction::None,
+    }
+}
+
+/// Maps keyboard events to typed delete confirmation actions
+/// Used when ViewState is ConfirmDelete or ConfirmBulkDelete. Keys mapped to None are text edits.
+pub fn handle_delete_confirm_input(key: KeyEvent) -> KeyAction {
+    match (key.code, key.modifiers) {
+        (KeyCode::Enter, KeyModifiers::NONE) => KeyAction::ConfirmDelete,
+        (KeyCode::Esc, KeyModifiers::NONE) => KeyAction::CancelDelete,
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) => KeyAction::CancelDelete,
+        _ => KeyAction::None,
+    }
+}
+
+/// Maps keyboard events to rename editor actions
+/// Used when ViewState is Renaming. Keys mapped to None are text edits.
+pub fn handle_rename_input(key: KeyEvent) -> KeyAction {
+    match (key.code, key.modifiers) {
+        (KeyCode::Enter, KeyModifiers::NONE) => KeyAction::ConfirmRename,
+        (KeyCode::Esc, KeyModifiers::NONE) => KeyAction::CancelRename,
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) => KeyAction::CancelRename,
+        (KeyCode::Tab, KeyModifiers::NONE) => KeyAction::NextSuggestion,
+        _ => KeyAction::None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +253,129 @@ mod tests {
         assert_eq!(handle_key_event(key), KeyAction::Undo);
     }
 
+    #[test]
+    fn test_key_redo() {
+        let key = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Redo);
+
+        let key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(handle_key_event(key), KeyAction::Redo);
+    }
+
+    #[test]
+    fn test_key_move_slots() {
+        let key = KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Move(0));
+
+        let key = KeyEvent::new(KeyCode::Char('9'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Move(8));
+
+        let key = KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::None);
+    }
+
+    #[test]
+    fn test_delete_confirm_keys() {
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(handle_delete_confirm_input(key), KeyAction::ConfirmDelete);
+
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(handle_delete_confirm_input(key), KeyAction::CancelDelete);
+
+        // 'y' is text here, not a shortcut for confirming
+        let key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert_eq!(handle_delete_confirm_input(key), KeyAction::None);
+    }
+
+    #[test]
+    fn test_key_forget_kept() {
+        let key = KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT);
+        assert_eq!(handle_key_event(key), KeyAction::ForgetKept);
+
+        let key = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Keep);
+    }
+
+    #[test]
+    fn test_key_keep_only() {
+        let key = KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert_eq!(handle_key_event(key), KeyAction::KeepOnly(KeepRule::Newest));
+
+        let key = KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT);
+        assert_eq!(handle_key_event(key), KeyAction::KeepOnly(KeepRule::Oldest));
+
+        let key = KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT);
+        assert_eq!(
+            handle_key_event(key),
+            KeyAction::KeepOnly(KeepRule::ShortestPath)
+        );
+
+        let key = KeyEvent::new(KeyCode::Char('L'), KeyModifiers::SHIFT);
+        assert_eq!(
+            handle_key_event(key),
+            KeyAction::KeepOnly(KeepRule::Largest)
+        );
+
+        // Lowercase keys keep their own meaning
+        let key = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Open);
+    }
+
+    #[test]
+    fn test_key_archive() {
+        let key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Archive);
+    }
+
+    #[test]
+    fn test_key_rename() {
+        let key = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Rename);
+
+        let key = KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Rename);
+    }
+
+    #[test]
+    fn test_rename_keys() {
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(handle_rename_input(key), KeyAction::ConfirmRename);
+
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(handle_rename_input(key), KeyAction::CancelRename);
+
+        let key = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(handle_rename_input(key), KeyAction::NextSuggestion);
+
+        // Letters are text, including ones bound to actions while browsing
+        let key = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(handle_rename_input(key), KeyAction::None);
+    }
+
+    #[test]
+    fn test_key_timeline() {
+        let key = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(handle_key_event(key), KeyAction::Timeline);
+    }
+
+    #[test]
+    fn test_timeline_keys() {
+        let key = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(handle_timeline_input(key), KeyAction::Next);
+
+        let key = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert_eq!(handle_timeline_input(key), KeyAction::Previous);
+
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(handle_timeline_input(key), KeyAction::TimelineJump);
+
+        let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(handle_timeline_input(key), KeyAction::TimelineRevert);
+
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(handle_timeline_input(key), KeyAction::Timeline);
+    }
+
     #[test]
     fn test_key_help() {
         let key = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE);
@@ -2,22 +2,33 @@
 pub mod colors;
 pub mod helpers;
 pub mod input;
+pub mod text_input;
 
 // Re-exports
 pub use colors::*;
-pub use helpers::{calculate_progress, format_file_size};
-pub use input::{handle_confirm_input, handle_key_event, KeyAction};
+pub use helpers::{calculate_progress, format_destination, format_file_size, parent_prefix};
+pub use input::{
+    handle_confirm_input, handle_delete_confirm_input, handle_key_event, handle_rename_input,
+    handle_timeline_input, KeyAction,
+};
+pub use text_input::TextInput;
 
 use crate::async_preview::{PreviewState, SyncPreviewManager};
-use crate::domain::{AppState, DecisionStatistics};
+use crate::domain::{
+    suggest_names, wasted_bytes, AppState, Decision, DecisionStatistics, FileEntry, History,
+    KeepRule,
+};
 use crate::preview;
+
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph, Wrap},
     Frame,
 };
+use std::path::PathBuf;
 
 /// UI view state
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,10 +43,62 @@ pub enum ViewState {
     ConfirmTrash,
     /// Welcome screen shown on first launch
     Welcome,
+    /// Timeline of every decision, for jumping back or reverting one
+    Timeline,
+    /// Inline editor for renaming the current file
+    Renaming,
+    /// Typed confirmation for permanently deleting the current file
+    ConfirmDelete,
+    /// Typed confirmation for permanently deleting every staged file on quit
+    ConfirmBulkDelete,
+    /// Confirmation for keeping one copy of a duplicate group and trashing the rest
+    ConfirmDuplicates,
+}
+
+/// State of the rename editor
+#[derive(Debug, Clone)]
+pub struct RenameDialog {
+    pub input: TextInput,
+    /// Names offered with Tab, from the file's metadata
+    pub suggestions: Vec<String>,
+    /// Suggestion currently in the input, if one was picked
+    pub selected: Option<usize>,
+    /// Why the last rename attempt failed
+    pub error: Option<String>,
+}
+
+impl RenameDialog {
+    /// Opens the editor on the file's current name, with the cursor before the extension
+    pub fn new(file: &FileEntry) -> Self {
+        let mut input = TextInput::new(file.name.as_str());
+        if let Some(dot) = file.name.rfind('.').filter(|&dot| dot > 0) {
+            input.set_cursor(file.name[..dot].chars().count());
+        }
+
+        Self {
+            input,
+            suggestions: suggest_names(file),
+            selected: None,
+            error: None,
+        }
+    }
+
+    /// Replaces the input with the next suggestion, wrapping around
+    pub fn next_suggestion(&mut self) {
+        if self.suggestions.is_empty() {
+            return;
+        }
+        let next = self
+            .selected
+            .map_or(0, |i| (i + 1) % self.suggestions.len());
+        self.input.set_value(self.suggestions[next].as_str());
+        self.selected = Some(next);
+        self.error = None;
+    }
 }
 
 /// Renders the TUI (legacy, without async preview)
-pub fn render(frame: &mut Frame, state: &AppState) {
+pub fn render(frame: &mut Frame, state: &AppState, history: &History) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -45,15 +108,17 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         ])
         .split(frame.area());
 
-    render_header_polished(frame, chunks[0], state);
-    render_content(frame, chunks[1], state);
-    render_footer_polished(frame, chunks[2]);
+    render_header_polished(frame, chunks[0], state, history);
+    let preview_area = render_duplicate_group(frame, chunks[1], state, history);
+    render_content(frame, preview_area, state);
+    render_footer_polished(frame, chunks[2], state);
 }
 
 /// Renders the TUI with async preview support
 pub fn render_with_preview(
     frame: &mut Frame,
     state: &AppState,
+    history: &History,
     preview_manager: &mut SyncPreviewManager,
 ) {
     let chunks = Layout::default()
@@ -65,9 +130,10 @@ pub fn render_with_preview(
         ])
         .split(frame.area());
 
-    render_header_polished(frame, chunks[0], state);
-    render_content_async(frame, chunks[1], state, preview_manager);
-    render_footer_polished(frame, chunks[2]);
+    render_header_polished(frame, chunks[0], state, history);
+    let preview_area = render_duplicate_group(frame, chunks[1], state, history);
+    render_content_async(frame, preview_area, state, preview_manager);
+    render_footer_polished(frame, chunks[2], state);
 }
 
 /// Renders the summary screen at the end
@@ -95,9 +161,9 @@ pub fn render_summary(frame: &mut Frame, stats: &DecisionStatistics) {
     let total = stats.total_files;
     let kept = stats.kept;
     let trashed = stats.trashed;
-    let remaining = total.saturating_sub(kept + trashed);
+    let remaining = total.saturating_sub(stats.decided());
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Summary",
@@ -136,21 +202,98 @@ pub fn render_summary(frame: &mut Frame, stats: &DecisionStatistics) {
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
-        Line::from(vec![
-            Span::styled("   ○ ", Style::default().fg(TEXT_SECONDARY)),
-            Span::raw("Skipped:  "),
+    ];
+
+    if stats.deleted > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("   ✗ ", Style::default().fg(ACCENT_PRIMARY)),
+            Span::raw("Deleted:  "),
             Span::styled(
-                format!("{}", remaining),
-                Style::default().fg(TEXT_SECONDARY),
+                format!("{}", stats.deleted),
+                Style::default()
+                    .fg(ACCENT_PRIMARY)
+                    .add_modifier(Modifier::BOLD),
             ),
-        ]),
+        ]));
+    }
+
+    if stats.moved > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("   ↗ ", Style::default().fg(ACCENT_MOVE)),
+            Span::raw("Moved:    "),
+            Span::styled(
+                format!("{}", stats.moved),
+                Style::default()
+                    .fg(ACCENT_MOVE)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        for (dest, count) in &stats.moved_by_destination {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("     {}: ", format_destination(dest)),
+                    Style::default().fg(TEXT_SECONDARY),
+                ),
+                Span::styled(format!("{}", count), Style::default().fg(ACCENT_MOVE)),
+            ]));
+        }
+    }
+
+    if stats.renamed > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("   ✎ ", Style::default().fg(ACCENT_HIGHLIGHT)),
+            Span::raw("Renamed:  "),
+            Span::styled(
+                format!("{}", stats.renamed),
+                Style::default()
+                    .fg(ACCENT_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    if stats.archived > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("   ▣ ", Style::default().fg(ACCENT_ARCHIVE)),
+            Span::raw("Archived: "),
+            Span::styled(
+                format!("{}", stats.archived),
+                Style::default()
+                    .fg(ACCENT_ARCHIVE)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    lines.extend([Line::from(vec![
+        Span::styled("   ○ ", Style::default().fg(TEXT_SECONDARY)),
+        Span::raw("Skipped:  "),
+        Span::styled(
+            format!("{}", remaining),
+            Style::default().fg(TEXT_SECONDARY),
+        ),
+    ])]);
+
+    if stats.freed_bytes > 0 {
+        lines.push(Line::from(vec![
+            Span::raw("   Reclaimed: "),
+            Span::styled(
+                format_file_size(stats.freed_bytes),
+                Style::default()
+                    .fg(ACCENT_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    lines.extend([
         Line::from(""),
         Line::from(""),
         Line::from(Span::styled(
             "Press any key to exit",
             Style::default().fg(TEXT_SECONDARY),
         )),
-    ];
+    ]);
 
     let paragraph = Paragraph::new(lines)
         .alignment(Alignment::Center)
@@ -159,8 +302,8 @@ pub fn render_summary(frame: &mut Frame, stats: &DecisionStatistics) {
     frame.render_widget(paragraph, inner);
 }
 
-/// Renders the help overlay
-pub fn render_help_overlay(frame: &mut Frame) {
+/// Renders the help overlay, listing the configured move destinations
+pub fn render_help_overlay(frame: &mut Frame, move_destinations: &[PathBuf], duplicates: bool) {
     let area = frame.area();
     let help_area = centered_rect(50, 70, area);
 
@@ -178,7 +321,7 @@ pub fn render_help_overlay(frame: &mut Frame) {
     let inner = block.inner(help_area);
     frame.render_widget(block, help_area);
 
-    let help_lines = vec![
+    let mut help_lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Keyboard Shortcuts",
@@ -199,6 +342,38 @@ pub fn render_help_overlay(frame: &mut Frame) {
             Span::styled("t", Style::default().fg(ACCENT_PRIMARY)),
             Span::raw("     Trash file"),
         ]),
+    ];
+
+    for (slot, dest) in move_destinations.iter().enumerate() {
+        help_lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", slot + 1), Style::default().fg(ACCENT_MOVE)),
+            Span::raw(format!("          Move to {}", format_destination(dest))),
+        ]));
+    }
+
+    if duplicates {
+        help_lines.extend([
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  N ", Style::default().fg(ACCENT_HIGHLIGHT)),
+                Span::raw("          Keep newest copy, trash the rest"),
+            ]),
+            Line::from(vec![
+                Span::styled("  O ", Style::default().fg(ACCENT_HIGHLIGHT)),
+                Span::raw("          Keep oldest copy, trash the rest"),
+            ]),
+            Line::from(vec![
+                Span::styled("  P ", Style::default().fg(ACCENT_HIGHLIGHT)),
+                Span::raw("          Keep shortest path, trash the rest"),
+            ]),
+            Line::from(vec![
+                Span::styled("  L ", Style::default().fg(ACCENT_HIGHLIGHT)),
+                Span::raw("          Keep largest copy, trash the rest"),
+            ]),
+        ]);
+    }
+
+    help_lines.extend([
         Line::from(""),
         Line::from(vec![
             Span::styled("  ↑↓ ", Style::default().fg(TEXT_SECONDARY)),
@@ -213,6 +388,26 @@ pub fn render_help_overlay(frame: &mut Frame) {
             Span::styled("Ctrl+Z", Style::default().fg(ACCENT_HIGHLIGHT)),
             Span::raw("  Undo"),
         ]),
+        Line::from(vec![
+            Span::styled("  r ", Style::default().fg(ACCENT_HIGHLIGHT)),
+            Span::raw("or "),
+            Span::styled("Ctrl+Y", Style::default().fg(ACCENT_HIGHLIGHT)),
+            Span::raw("  Redo"),
+        ]),
+        Line::from(vec![
+            Span::styled("  h ", Style::default().fg(ACCENT_HIGHLIGHT)),
+            Span::raw("          Decision history"),
+        ]),
+        Line::from(vec![
+            Span::styled("  n ", Style::default().fg(ACCENT_HIGHLIGHT)),
+            Span::raw("or "),
+            Span::styled("F2", Style::default().fg(ACCENT_HIGHLIGHT)),
+            Span::raw("      Rename file"),
+        ]),
+        Line::from(vec![
+            Span::styled("  a ", Style::default().fg(ACCENT_ARCHIVE)),
+            Span::raw("          Archive file"),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("  o ", Style::default().fg(TEXT_SECONDARY)),
@@ -235,7 +430,7 @@ pub fn render_help_overlay(frame: &mut Frame) {
             "Press ? or Esc to close",
             Style::default().fg(TEXT_SECONDARY),
         )),
-    ];
+    ]);
 
     let paragraph = Paragraph::new(help_lines)
         .alignment(Alignment::Center)
@@ -328,10 +523,34 @@ pub fn render_welcome_overlay(frame: &mut Frame) {
             Span::styled("  ↓ / j  ", Style::default().fg(TEXT_SECONDARY)),
             Span::raw("      Next file"),
         ]),
+        Line::from(vec![
+            Span::styled("  1-9    ", Style::default().fg(TEXT_SECONDARY)),
+            Span::raw("      Move file into a --move-to folder"),
+        ]),
+        Line::from(vec![
+            Span::styled("  n      ", Style::default().fg(TEXT_SECONDARY)),
+            Span::raw("      Rename file (Tab cycles suggestions)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  a      ", Style::default().fg(TEXT_SECONDARY)),
+            Span::raw("      Archive file (undo extracts it)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  K      ", Style::default().fg(TEXT_SECONDARY)),
+            Span::raw("      Forget that a file was kept before"),
+        ]),
         Line::from(vec![
             Span::styled("  u      ", Style::default().fg(TEXT_SECONDARY)),
             Span::raw("      Undo last decision"),
         ]),
+        Line::from(vec![
+            Span::styled("  r      ", Style::default().fg(TEXT_SECONDARY)),
+            Span::raw("      Redo an undone decision"),
+        ]),
+        Line::from(vec![
+            Span::styled("  h      ", Style::default().fg(TEXT_SECONDARY)),
+            Span::raw("      Browse decision history"),
+        ]),
         Line::from(vec![
             Span::styled("  o      ", Style::default().fg(TEXT_SECONDARY)),
             Span::raw("      Open file in editor"),
@@ -448,10 +667,7 @@ pub fn render_confirm_trash_overlay(frame: &mut Frame, file: &crate::domain::Fil
         ]),
         Line::from(vec![
             Span::styled("  Type: ", Style::default().fg(TEXT_SECONDARY)),
-            Span::styled(
-                format!("{:?}", file.file_type),
-                Style::default().fg(TEXT_PRIMARY),
-            ),
+            Span::styled(file.file_type.label(), Style::default().fg(TEXT_PRIMARY)),
         ]),
         Line::from(""),
         Line::from(""),
@@ -484,167 +700,755 @@ pub fn render_confirm_trash_overlay(frame: &mut Frame, file: &crate::domain::Fil
     frame.render_widget(paragraph, inner);
 }
 
-/// Renders a loading overlay
-pub fn render_loading_overlay(frame: &mut Frame, file: &crate::domain::FileEntry) {
+/// Renders the confirmation for keeping one copy of a duplicate group
+pub fn render_confirm_duplicates_overlay(
+    frame: &mut Frame,
+    keep: &FileEntry,
+    discard: &[&FileEntry],
+    delete_mode: bool,
+) {
     let area = frame.area();
-    let loading_area = centered_rect(40, 30, area);
+    let confirm_area = centered_rect(60, 60, area);
 
-    // Clear background
-    frame.render_widget(Clear, loading_area);
+    frame.render_widget(Clear, confirm_area);
 
+    let verb = if delete_mode { "Delete" } else { "Trash" };
     let block = Block::default()
-        .title(" Loading Preview ")
+        .title(format!(" ⚠ Keep One Copy, {} {} ", verb, discard.len()))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(ACCENT_HIGHLIGHT))
+        .border_style(Style::default().fg(ACCENT_PRIMARY))
         .style(Style::default().bg(BG_DARK));
 
-    let inner = block.inner(loading_area);
-    frame.render_widget(block, loading_area);
-
-    // Simple animation based on current time
-    let spinners = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
-    let spinner_idx = (now / 100) as usize % spinners.len();
-    let spinner = spinners[spinner_idx];
+    let inner = block.inner(confirm_area);
+    frame.render_widget(block, confirm_area);
 
-    let lines = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                format!("  {} ", spinner),
-                Style::default().fg(ACCENT_HIGHLIGHT),
-            ),
-            Span::styled("Processing file", Style::default().fg(TEXT_PRIMARY)),
-        ]),
+    let mut lines = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("  Name: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled("→ Keep   ", Style::default().fg(ACCENT_SECONDARY)),
             Span::styled(
-                &file.name,
+                keep.path.display().to_string(),
                 Style::default()
                     .fg(TEXT_PRIMARY)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
-        Line::from(vec![
-            Span::styled("  Size: ", Style::default().fg(TEXT_SECONDARY)),
+        Line::from(""),
+    ];
+    for file in discard {
+        lines.push(Line::from(vec![
             Span::styled(
-                format_file_size(file.size),
-                Style::default().fg(TEXT_PRIMARY),
+                format!("← {:<7}", verb),
+                Style::default().fg(ACCENT_PRIMARY),
             ),
-        ]),
-        Line::from(vec![
-            Span::styled("  Type: ", Style::default().fg(TEXT_SECONDARY)),
             Span::styled(
-                format!("{:?}", file.file_type),
-                Style::default().fg(TEXT_PRIMARY),
+                file.path.display().to_string(),
+                Style::default().fg(TEXT_SECONDARY),
             ),
-        ]),
+        ]));
+    }
+    lines.extend([
         Line::from(""),
         Line::from(Span::styled(
-            "  Please wait...",
-            Style::default()
-                .fg(TEXT_SECONDARY)
-                .add_modifier(Modifier::ITALIC),
+            "You can undo this with 'u', one file at a time.",
+            Style::default().fg(TEXT_SECONDARY),
         )),
-    ];
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[Y]", Style::default().fg(ACCENT_SECONDARY)),
+            Span::raw("es  "),
+            Span::styled("[Enter]", Style::default().fg(ACCENT_SECONDARY)),
+            Span::raw("     "),
+            Span::styled("[N]", Style::default().fg(ACCENT_PRIMARY)),
+            Span::raw("o  "),
+            Span::styled("[Esc]", Style::default().fg(ACCENT_PRIMARY)),
+        ]),
+    ]);
 
     let paragraph = Paragraph::new(lines)
-        .alignment(Alignment::Left)
-        .style(Style::default().fg(TEXT_PRIMARY));
+        .style(Style::default().fg(TEXT_PRIMARY))
+        .wrap(Wrap { trim: false });
 
     frame.render_widget(paragraph, inner);
 }
 
-/// Helper to create a centered rect
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+/// Symbol, label (padded to a common width) and color shown for a decision
+fn decision_badge(decision: &Decision) -> (&'static str, &'static str, Color) {
+    match decision {
+        Decision::Keep => ("→", "Keep   ", ACCENT_SECONDARY),
+        Decision::Trash => ("←", "Trash  ", ACCENT_PRIMARY),
+        Decision::Delete => ("✗", "Delete ", ACCENT_PRIMARY),
+        Decision::Move { .. } => ("↗", "Move   ", ACCENT_MOVE),
+        Decision::Rename { .. } => ("✎", "Rename ", ACCENT_HIGHLIGHT),
+        Decision::Archive => ("▣", "Archive", ACCENT_ARCHIVE),
+    }
 }
 
-/// Renders the polished header with progress bar
-fn render_header_polished(frame: &mut Frame, area: Rect, state: &AppState) {
+/// Renders the decision timeline, oldest first, with undone decisions dimmed
+pub fn render_timeline_overlay(
+    frame: &mut Frame,
+    history: &History,
+    files: &[FileEntry],
+    selected: usize,
+    error: Option<&str>,
+) {
+    let area = frame.area();
+    let timeline_area = centered_rect(60, 70, area);
+
+    // Clear background
+    frame.render_widget(Clear, timeline_area);
+
+    let block = Block::default()
+        .title(" Decision History ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_HIGHLIGHT))
+        .style(Style::default().bg(BG_DARK));
+
+    let inner = block.inner(timeline_area);
+    frame.render_widget(block, timeline_area);
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(2), Constraint::Length(2)])
-        .split(area);
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(if error.is_some() { 2 } else { 1 }), // Error
+            Constraint::Length(1),                                   // Hint
+        ])
+        .split(inner);
+
+    let entries = history.entries();
+    let lines: Vec<Line> = if entries.is_empty() {
+        vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "No decisions yet",
+                Style::default().fg(TEXT_SECONDARY),
+            )),
+        ]
+    } else {
+        // Keep the selected entry inside the visible window
+        let height = chunks[0].height as usize;
+        let start = (selected + 1).saturating_sub(height);
+
+        entries
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(height)
+            .map(|(position, entry)| {
+                let applied = history.is_applied(position);
+                let (symbol, label, color) = decision_badge(&entry.decision);
+                let mut name = files
+                    .get(entry.index)
+                    .map(|f| f.name.clone())
+                    .unwrap_or_else(|| "?".to_string());
+                match &entry.decision {
+                    Decision::Move { dest } => {
+                        name = format!("{}  →  {}", name, format_destination(dest));
+                    }
+                    Decision::Rename { name: new_name } => {
+                        name = format!("{}  →  {}", name, new_name);
+                    }
+                    Decision::Keep | Decision::Trash | Decision::Delete | Decision::Archive => {}
+                }
 
-    // Title and file info
-    let (title_text, file_info) = if let Some(file) = state.current_file() {
-        let size_str = format_file_size(file.size);
-        let file_type = format!("{:?}", file.file_type);
-        (
-            format!(" File {}/{} ", state.current_index + 1, state.files.len()),
-            vec![
-                Span::styled(
-                    &file.name,
+                let mut style = if applied {
+                    Style::default().fg(TEXT_PRIMARY)
+                } else {
                     Style::default()
-                        .fg(TEXT_PRIMARY)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw("  "),
-                Span::styled(
-                    format!("({} • {})", size_str, file_type),
-                    Style::default().fg(TEXT_SECONDARY),
-                ),
-            ],
-        )
-    } else {
-        (
-            " Fswp ".to_string(),
-            vec![Span::styled(
-                "No files to review",
-                Style::default().fg(TEXT_SECONDARY),
-            )],
-        )
+                        .fg(TEXT_SECONDARY)
+                        .add_modifier(Modifier::DIM | Modifier::CROSSED_OUT)
+                };
+                let mut decision_style = if applied {
+                    Style::default().fg(color)
+                } else {
+                    style
+                };
+                if position == selected {
+                    style = style.bg(BORDER_COLOR).add_modifier(Modifier::BOLD);
+                    decision_style = decision_style.bg(BORDER_COLOR);
+                }
+
+                Line::from(vec![
+                    Span::styled(if position == selected { " ▶ " } else { "   " }, style),
+                    Span::styled(format!("{:>3}. ", position + 1), style),
+                    Span::styled(format!("{} {}  ", symbol, label), decision_style),
+                    Span::styled(name, style),
+                ])
+            })
+            .collect()
     };
 
-    let title_line = Line::from(vec![Span::styled(
-        title_text,
-        Style::default()
-            .fg(ACCENT_HIGHLIGHT)
-            .add_modifier(Modifier::BOLD),
-    )]);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(TEXT_PRIMARY)),
+        chunks[0],
+    );
 
-    let info_line = Line::from(file_info);
+    let hint = Line::from(vec![
+        Span::styled("Enter ", Style::default().fg(ACCENT_HIGHLIGHT)),
+        Span::styled("Jump here", Style::default().fg(TEXT_SECONDARY)),
+        Span::raw("  │  "),
+        Span::styled("x ", Style::default().fg(ACCENT_PRIMARY)),
+        Span::styled("Revert this one", Style::default().fg(TEXT_SECONDARY)),
+        Span::raw("  │  "),
+        Span::styled("Esc ", Style::default().fg(TEXT_SECONDARY)),
+        Span::styled("Close", Style::default().fg(TEXT_SECONDARY)),
+    ]);
 
-    let header = Paragraph::new(vec![title_line, info_line])
-        .block(
-            Block::default()
-                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(BORDER_COLOR)),
-        )
-        .alignment(Alignment::Left);
+    if let Some(error) = error {
+        frame.render_widget(
+            Paragraph::new(Span::styled(error, Style::default().fg(ACCENT_PRIMARY)))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            chunks[1],
+        );
+    }
 
-    frame.render_widget(header, chunks[0]);
+    frame.render_widget(Paragraph::new(hint).alignment(Alignment::Center), chunks[2]);
+}
 
-    // Progress bar
-    let total = state.files.len();
-    let processed = state.decisions_stack.len();
-    let progress = if total > 0 {
-        processed as f64 / total as f64
-    } else {
+/// Renders the rename editor for the current file
+pub fn render_rename_overlay(frame: &mut Frame, file: &FileEntry, dialog: &RenameDialog) {
+    let area = frame.area();
+    let rename_area = centered_rect(60, 60, area);
+
+    // Clear background
+    frame.render_widget(Clear, rename_area);
+
+    let block = Block::default()
+        .title(" ✎ Rename ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_HIGHLIGHT))
+        .style(Style::default().bg(BG_DARK));
+
+    let inner = block.inner(rename_area);
+    frame.render_widget(block, rename_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Current name
+            Constraint::Length(3), // Input
+            Constraint::Length(2), // Error
+            Constraint::Min(0),    // Suggestions
+            Constraint::Length(1), // Hint
+        ])
+        .split(inner);
+
+    let current = Line::from(vec![
+        Span::styled("  Current: ", Style::default().fg(TEXT_SECONDARY)),
+        Span::styled(&file.name, Style::default().fg(TEXT_PRIMARY)),
+    ]);
+    frame.render_widget(Paragraph::new(current), chunks[0]);
+
+    render_text_input(frame, chunks[1], &dialog.input, ACCENT_HIGHLIGHT);
+
+    if let Some(error) = &dialog.error {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(ACCENT_PRIMARY),
+            ))
+            .wrap(Wrap { trim: false }),
+            chunks[2],
+        );
+    }
+
+    let mut suggestion_lines = vec![Line::from(Span::styled(
+        "  Suggestions",
+        Style::default()
+            .fg(TEXT_SECONDARY)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    for (i, suggestion) in dialog.suggestions.iter().enumerate() {
+        let style = if dialog.selected == Some(i) {
+            Style::default()
+                .fg(ACCENT_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(TEXT_SECONDARY)
+        };
+        let marker = if dialog.selected == Some(i) {
+            "  ▶ "
+        } else {
+            "    "
+        };
+        suggestion_lines.push(Line::from(Span::styled(
+            format!("{}{}", marker, suggestion),
+            style,
+        )));
+    }
+    frame.render_widget(Paragraph::new(suggestion_lines), chunks[3]);
+
+    let hint = Line::from(vec![
+        Span::styled("Enter ", Style::default().fg(ACCENT_SECONDARY)),
+        Span::styled("Rename", Style::default().fg(TEXT_SECONDARY)),
+        Span::raw("  │  "),
+        Span::styled("Tab ", Style::default().fg(ACCENT_HIGHLIGHT)),
+        Span::styled("Suggest", Style::default().fg(TEXT_SECONDARY)),
+        Span::raw("  │  "),
+        Span::styled("Esc ", Style::default().fg(ACCENT_PRIMARY)),
+        Span::styled("Cancel", Style::default().fg(TEXT_SECONDARY)),
+    ]);
+    frame.render_widget(Paragraph::new(hint).alignment(Alignment::Center), chunks[4]);
+}
+
+/// Renders a bordered single-line input and places the terminal cursor in it
+fn render_text_input(frame: &mut Frame, area: Rect, input: &TextInput, color: Color) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color));
+    let inner = block.inner(area);
+
+    // Scroll the text horizontally so the cursor stays visible
+    let width = inner.width.max(1) as usize;
+    let cursor = input.cursor();
+    let offset = (cursor + 1).saturating_sub(width);
+    let visible: String = input.value().chars().skip(offset).take(width).collect();
+
+    frame.render_widget(
+        Paragraph::new(visible)
+            .style(Style::default().fg(TEXT_PRIMARY))
+            .block(block),
+        area,
+    );
+    frame.set_cursor_position((inner.x + (cursor - offset) as u16, inner.y));
+}
+
+/// Renders the confirmation for permanently deleting one file, which
+/// requires typing the file name
+pub fn render_confirm_delete_overlay(
+    frame: &mut Frame,
+    file: &FileEntry,
+    input: &TextInput,
+    error: Option<&str>,
+) {
+    let area = frame.area();
+    let confirm_area = centered_rect(60, 60, area);
+
+    // Clear background
+    frame.render_widget(Clear, confirm_area);
+
+    let block = Block::default()
+        .title(" ⚠ Permanently Delete ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_PRIMARY))
+        .style(Style::default().bg(BG_DARK));
+
+    let inner = block.inner(confirm_area);
+    frame.render_widget(block, confirm_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(9), // Explanation
+            Constraint::Length(3), // Input
+            Constraint::Min(0),    // Error
+            Constraint::Length(1), // Hint
+        ])
+        .split(inner);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  File: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(&file.name, Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  Size: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(
+                format_file_size(file.size),
+                Style::default().fg(TEXT_PRIMARY),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  This file will NOT go to the trash.",
+            Style::default()
+                .fg(ACCENT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "  It is deleted for good when you quit; until then 'u' undoes it.",
+            Style::default().fg(TEXT_SECONDARY),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Type the file name to confirm:",
+            Style::default().fg(TEXT_PRIMARY),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    render_text_input(frame, chunks[1], input, ACCENT_PRIMARY);
+
+    if let Some(error) = error {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(ACCENT_PRIMARY),
+            ))
+            .wrap(Wrap { trim: false }),
+            chunks[2],
+        );
+    }
+
+    frame.render_widget(
+        Paragraph::new(confirm_hint("Delete")).alignment(Alignment::Center),
+        chunks[3],
+    );
+}
+
+/// Renders the final confirmation before permanently deleting every staged
+/// file, which requires typing how many files will be deleted
+pub fn render_confirm_bulk_delete_overlay(
+    frame: &mut Frame,
+    files: &[&FileEntry],
+    input: &TextInput,
+    error: Option<&str>,
+) {
+    let area = frame.area();
+    let confirm_area = centered_rect(60, 70, area);
+
+    // Clear background
+    frame.render_widget(Clear, confirm_area);
+
+    let block = Block::default()
+        .title(" ⚠ Permanently Delete Files ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_PRIMARY))
+        .style(Style::default().bg(BG_DARK));
+
+    let inner = block.inner(confirm_area);
+    frame.render_widget(block, confirm_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Summary and file list
+            Constraint::Length(2), // Prompt
+            Constraint::Length(3), // Input
+            Constraint::Length(1), // Error
+            Constraint::Length(1), // Hint
+        ])
+        .split(inner);
+
+    let total_size: u64 = files.iter().map(|f| f.size).sum();
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {} files ({})", files.len(), format_file_size(total_size)),
+            Style::default()
+                .fg(ACCENT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "  will be permanently deleted, not trashed.",
+            Style::default().fg(ACCENT_PRIMARY),
+        )),
+        Line::from(""),
+    ];
+
+    // List as many names as fit, then say how many more there are
+    let room = (chunks[0].height as usize).saturating_sub(lines.len());
+    let shown = if files.len() > room {
+        room.saturating_sub(1)
+    } else {
+        files.len()
+    };
+    for file in &files[..shown] {
+        lines.push(Line::from(Span::styled(
+            format!("    {}", file.name),
+            Style::default().fg(TEXT_PRIMARY),
+        )));
+    }
+    if shown < files.len() {
+        lines.push(Line::from(Span::styled(
+            format!("    … and {} more", files.len() - shown),
+            Style::default().fg(TEXT_SECONDARY),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            format!("  Type {} to confirm:", files.len()),
+            Style::default().fg(TEXT_PRIMARY),
+        )),
+        chunks[1],
+    );
+
+    render_text_input(frame, chunks[2], input, ACCENT_PRIMARY);
+
+    if let Some(error) = error {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(ACCENT_PRIMARY),
+            )),
+            chunks[3],
+        );
+    }
+
+    frame.render_widget(
+        Paragraph::new(confirm_hint("Delete and quit")).alignment(Alignment::Center),
+        chunks[4],
+    );
+}
+
+/// "Enter <action> │ Esc Cancel" hint line for typed confirmations
+fn confirm_hint(action: &str) -> Line<'_> {
+    Line::from(vec![
+        Span::styled("Enter ", Style::default().fg(ACCENT_PRIMARY)),
+        Span::styled(action, Style::default().fg(TEXT_SECONDARY)),
+        Span::raw("  │  "),
+        Span::styled("Esc ", Style::default().fg(ACCENT_SECONDARY)),
+        Span::styled("Cancel", Style::default().fg(TEXT_SECONDARY)),
+    ])
+}
+
+/// Renders a loading overlay
+pub fn render_loading_overlay(frame: &mut Frame, file: &crate::domain::FileEntry) {
+    let area = frame.area();
+    let loading_area = centered_rect(40, 30, area);
+
+    // Clear background
+    frame.render_widget(Clear, loading_area);
+
+    let block = Block::default()
+        .title(" Loading Preview ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ACCENT_HIGHLIGHT))
+        .style(Style::default().bg(BG_DARK));
+
+    let inner = block.inner(loading_area);
+    frame.render_widget(block, loading_area);
+
+    // Simple animation based on current time
+    let spinners = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let spinner_idx = (now / 100) as usize % spinners.len();
+    let spinner = spinners[spinner_idx];
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("  {} ", spinner),
+                Style::default().fg(ACCENT_HIGHLIGHT),
+            ),
+            Span::styled("Processing file", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Name: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(
+                &file.name,
+                Style::default()
+                    .fg(TEXT_PRIMARY)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Size: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(
+                format_file_size(file.size),
+                Style::default().fg(TEXT_PRIMARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Type: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(file.file_type.label(), Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Please wait...",
+            Style::default()
+                .fg(TEXT_SECONDARY)
+                .add_modifier(Modifier::ITALIC),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Left)
+        .style(Style::default().fg(TEXT_PRIMARY));
+
+    frame.render_widget(paragraph, inner);
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// The reviewed directory `file` came from, shortened, when there are several
+fn root_label(state: &AppState, file: &FileEntry) -> Option<String> {
+    file.root
+        .as_deref()
+        .filter(|_| state.several_roots)
+        .map(format_destination)
+}
+
+/// Renders the polished header with progress bar
+fn render_header_polished(frame: &mut Frame, area: Rect, state: &AppState, history: &History) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Length(2)])
+        .split(area);
+
+    // Title and file info
+    let (title_text, mut file_info) = if let Some(file) = state.current_file() {
+        let size_str = match file.file_count {
+            Some(count) => format!("{} in {} files", format_file_size(file.size), count),
+            None => format_file_size(file.size),
+        };
+        let file_type = file.file_type.label();
+        (
+            format!(" File {}/{} ", state.current_index + 1, state.files.len()),
+            vec![
+                Span::styled(
+                    parent_prefix(state.relative_path(file)),
+                    Style::default().fg(TEXT_SECONDARY),
+                ),
+                Span::styled(
+                    &file.name,
+                    Style::default()
+                        .fg(TEXT_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    format!("({} • {})", size_str, file_type),
+                    Style::default().fg(TEXT_SECONDARY),
+                ),
+            ],
+        )
+    } else {
+        (
+            " Fswp ".to_string(),
+            vec![Span::styled(
+                "No files to review",
+                Style::default().fg(TEXT_SECONDARY),
+            )],
+        )
+    };
+
+    if state
+        .current_file()
+        .is_some_and(|file| state.keep_list.contains(file))
+    {
+        file_info.push(Span::styled(
+            "  ✓ kept before (K to forget)",
+            Style::default().fg(ACCENT_SECONDARY),
+        ));
+    }
+
+    let mut title_spans = vec![Span::styled(
+        title_text,
+        Style::default()
+            .fg(ACCENT_HIGHLIGHT)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if let Some((position, group)) = state.current_group() {
+        title_spans.push(Span::styled(
+            format!(
+                "Group {}/{} • {} files • {} reclaimable ",
+                position + 1,
+                state.duplicate_groups.len(),
+                group.len(),
+                format_file_size(wasted_bytes(&state.files[group]))
+            ),
+            Style::default().fg(TEXT_SECONDARY),
+        ));
+    }
+    if let Some(root) = state
+        .current_file()
+        .and_then(|file| root_label(state, file))
+    {
+        title_spans.push(Span::styled(
+            format!("in {} ", root),
+            Style::default().fg(TEXT_SECONDARY),
+        ));
+    }
+    if state.scanning {
+        title_spans.push(Span::styled(
+            format!("Scanning… {} found ", state.files.len()),
+            Style::default().fg(ACCENT_SECONDARY),
+        ));
+    }
+    if state.disk_hogs {
+        let stats = DecisionStatistics::tally(history, &state.files);
+        title_spans.push(Span::styled(
+            format!(
+                "Reclaimed {} • ~{} projected ",
+                format_file_size(stats.freed_bytes),
+                format_file_size(stats.projected_bytes())
+            ),
+            Style::default().fg(ACCENT_SECONDARY),
+        ));
+    }
+    if state.delete_mode {
+        title_spans.push(Span::styled(
+            " PERMANENT DELETE MODE ",
+            Style::default()
+                .fg(BG_DARK)
+                .bg(ACCENT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let title_line = Line::from(title_spans);
+
+    let info_line = Line::from(file_info);
+
+    let header = Paragraph::new(vec![title_line, info_line])
+        .block(
+            Block::default()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(BORDER_COLOR)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(header, chunks[0]);
+
+    // Progress bar
+    let total = state.files.len();
+    let processed = history.len();
+    let progress = if total > 0 {
+        processed as f64 / total as f64
+    } else {
         0.0
     };
 
@@ -667,6 +1471,126 @@ fn render_header_polished(frame: &mut Frame, area: Rect, state: &AppState) {
     frame.render_widget(gauge, chunks[1]);
 }
 
+/// Renders the copies of the current duplicate group side by side above the
+/// preview. Returns the area left for the preview, which is all of `area`
+/// outside duplicate review.
+fn render_duplicate_group(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    history: &History,
+) -> Rect {
+    let Some((_, group)) = state.current_group() else {
+        return area;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(10), Constraint::Min(0)])
+        .split(area);
+
+    let start = group.start;
+    let copies = &state.files[group.clone()];
+    let newest = start + KeepRule::Newest.pick(copies);
+    let oldest = start + KeepRule::Oldest.pick(copies);
+    let shortest = start + KeepRule::ShortestPath.pick(copies);
+    // Only worth pointing out when the copies differ in size, as similar images do
+    let largest = copies
+        .iter()
+        .any(|f| f.size != copies[0].size)
+        .then(|| start + KeepRule::Largest.pick(copies));
+    let date = |date: Option<DateTime<Utc>>| {
+        date.map(|d| d.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "—".to_string())
+    };
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Ratio(1, copies.len() as u32);
+            copies.len()
+        ])
+        .split(chunks[0]);
+
+    for (index, column) in group.zip(columns.iter()) {
+        let file = &state.files[index];
+        let selected = index == state.current_index;
+        let field = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::styled(label, Style::default().fg(TEXT_SECONDARY)),
+                Span::styled(value, Style::default().fg(TEXT_PRIMARY)),
+            ])
+        };
+
+        let tags: Vec<&str> = [
+            (newest, "newest"),
+            (oldest, "oldest"),
+            (shortest, "shortest path"),
+        ]
+        .into_iter()
+        .chain(largest.map(|largest| (largest, "largest")))
+        .filter(|&(pick, _)| pick == index)
+        .map(|(_, tag)| tag)
+        .collect();
+
+        let decision = history
+            .applied()
+            .iter()
+            .rev()
+            .find(|entry| entry.index == index)
+            .map(|entry| {
+                let (symbol, label, color) = decision_badge(&entry.decision);
+                Span::styled(
+                    format!("{} {}", symbol, label.trim_end()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                )
+            })
+            .unwrap_or_else(|| Span::styled("Undecided", Style::default().fg(TEXT_SECONDARY)));
+
+        let lines = vec![
+            Line::from(Span::styled(
+                &file.name,
+                Style::default()
+                    .fg(TEXT_PRIMARY)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "{}{}",
+                    root_label(state, file)
+                        .map(|root| format!("{}: ", root))
+                        .unwrap_or_default(),
+                    parent_prefix(state.relative_path(file))
+                ),
+                Style::default().fg(TEXT_SECONDARY),
+            )),
+            field("Size     ", format_file_size(file.size)),
+            field("Modified ", date(Some(file.modified_date))),
+            field("Created  ", date(file.created_date)),
+            field("Accessed ", date(file.accessed_date)),
+            Line::from(Span::styled(
+                tags.join(" · "),
+                Style::default().fg(ACCENT_HIGHLIGHT),
+            )),
+            Line::from(decision),
+        ];
+
+        let border_color = if selected {
+            ACCENT_HIGHLIGHT
+        } else {
+            BORDER_COLOR
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(border_color))
+            .title(format!(" Copy {} ", index - start + 1));
+        frame.render_widget(Paragraph::new(lines).block(block), *column);
+    }
+
+    chunks[1]
+}
+
 /// Renders the main content area (synchronous version)
 fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
     use crate::preview::PreviewContent;
@@ -684,7 +1608,7 @@ fn render_content(frame: &mut Frame, area: Rect, state: &AppState) {
                 Line::from(format!("File: {}", file.name)),
                 Line::from(format!("Path: {}", file.path.display())),
                 Line::from(format!("Size: {} bytes", file.size)),
-                Line::from(format!("Type: {:?}", file.file_type)),
+                Line::from(format!("Type: {}", file.file_type.label())),
             ],
         };
 
@@ -800,7 +1724,7 @@ fn render_content_async(
                     Line::from(format!("  File: {}", file.name)),
                     Line::from(format!("  Path: {}", file.path.display())),
                     Line::from(format!("  Size: {}", format_file_size(file.size))),
-                    Line::from(format!("  Type: {:?}", file.file_type)),
+                    Line::from(format!("  Type: {}", file.file_type.label())),
                 ];
 
                 let paragraph = Paragraph::new(error_lines)
@@ -822,15 +1746,34 @@ fn render_content_async(
 }
 
 /// Renders the polished footer with styled controls
-fn render_footer_polished(frame: &mut Frame, area: Rect) {
-    let controls = Line::from(vec![
+fn render_footer_polished(frame: &mut Frame, area: Rect, state: &AppState) {
+    if let Some(notice) = &state.notice {
+        let footer = Paragraph::new(Span::styled(
+            format!("[!] {}", notice),
+            Style::default().fg(ACCENT_PRIMARY),
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(ACCENT_PRIMARY)),
+        )
+        .alignment(Alignment::Center);
+        frame.render_widget(footer, area);
+        return;
+    }
+
+    let mut controls = vec![
         Span::styled(
             " ← ",
             Style::default()
                 .fg(ACCENT_PRIMARY)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled("Trash", Style::default().fg(TEXT_SECONDARY)),
+        Span::styled(
+            if state.delete_mode { "Delete" } else { "Trash" },
+            Style::default().fg(TEXT_SECONDARY),
+        ),
         Span::raw("  │  "),
         Span::styled(
             "→ ",
@@ -840,6 +1783,15 @@ fn render_footer_polished(frame: &mut Frame, area: Rect) {
         ),
         Span::styled("Keep", Style::default().fg(TEXT_SECONDARY)),
         Span::raw("  │  "),
+    ];
+    if state.current_group().is_some() {
+        controls.extend([
+            Span::styled("N/O/P/L ", Style::default().fg(ACCENT_HIGHLIGHT)),
+            Span::styled("Keep one", Style::default().fg(TEXT_SECONDARY)),
+            Span::raw("  │  "),
+        ]);
+    }
+    controls.extend([
         Span::styled("↑↓ ", Style::default().fg(TEXT_SECONDARY)),
         Span::styled("Navigate", Style::default().fg(TEXT_SECONDARY)),
         Span::raw("  │  "),
@@ -853,7 +1805,7 @@ fn render_footer_polished(frame: &mut Frame, area: Rect) {
         Span::styled("Quit", Style::default().fg(TEXT_SECONDARY)),
     ]);
 
-    let footer = Paragraph::new(controls)
+    let footer = Paragraph::new(Line::from(controls))
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -868,7 +1820,7 @@ fn render_footer_polished(frame: &mut Frame, area: Rect) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{FileEntry, FileType};
+    use crate::domain::{FileType, HistoryEntry};
     use chrono::Utc;
     use ratatui::{backend::TestBackend, Terminal};
     use std::path::PathBuf;
@@ -880,6 +1832,10 @@ mod tests {
             size: 1024,
             modified_date: Utc::now(),
             file_type: FileType::Text,
+            accessed_date: None,
+            created_date: None,
+            file_count: None,
+            root: None,
         }
     }
 
@@ -894,7 +1850,7 @@ mod tests {
 
             terminal
                 .draw(|frame| {
-                    render(frame, &state);
+                    render(frame, &state, &History::new());
                 })
                 .unwrap();
 
@@ -924,7 +1880,7 @@ mod tests {
 
             terminal
                 .draw(|frame| {
-                    render(frame, &state);
+                    render(frame, &state, &History::new());
                 })
                 .unwrap();
 
@@ -937,6 +1893,105 @@ mod tests {
             assert!(buffer_str.contains("File 1/2"));
         }
 
+        #[test]
+        fn test_render_header_shows_relative_path() {
+            let mut file = create_test_entry("notes.txt");
+            file.path = PathBuf::from("/home/me/Downloads/old/notes.txt");
+            let mut state = AppState::new(vec![file]);
+            state.root = PathBuf::from("/home/me/Downloads");
+            let backend = TestBackend::new(80, 24);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render(frame, &state, &History::new());
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("old/notes.txt"));
+        }
+
+        #[test]
+        fn test_render_header_shows_root_of_each_file() {
+            let mut file = create_test_entry("notes.txt");
+            file.path = PathBuf::from("/home/me/Desktop/old/notes.txt");
+            file.root = Some(PathBuf::from("/home/me/Desktop"));
+            let mut state = AppState::new(vec![file]);
+            state.root = PathBuf::from("/home/me/Downloads");
+            let render_header = |state: &AppState| {
+                let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+                terminal
+                    .draw(|frame| render(frame, state, &History::new()))
+                    .unwrap();
+                let buffer = terminal.backend().buffer().clone();
+                buffer
+                    .content()
+                    .iter()
+                    .map(|c| c.symbol())
+                    .collect::<String>()
+            };
+
+            assert!(!render_header(&state).contains("in me/Desktop"));
+            state.several_roots = true;
+            assert!(render_header(&state).contains("in me/Desktop"));
+        }
+
+        #[test]
+        fn test_render_header_while_scanning() {
+            let mut state =
+                AppState::new(vec![create_test_entry("a.txt"), create_test_entry("b.txt")]);
+            let render_header = |state: &AppState| {
+                let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+                terminal
+                    .draw(|frame| render(frame, state, &History::new()))
+                    .unwrap();
+                let buffer = terminal.backend().buffer().clone();
+                buffer
+                    .content()
+                    .iter()
+                    .map(|c| c.symbol())
+                    .collect::<String>()
+            };
+
+            state.scanning = true;
+            assert!(render_header(&state).contains("Scanning… 2 found"));
+
+            state.scanning = false;
+            assert!(!render_header(&state).contains("Scanning"));
+        }
+
+        #[test]
+        fn test_render_header_in_hog_review() {
+            let mut state = AppState::new(vec![
+                create_test_entry("a.iso"),
+                create_test_entry("b.iso"),
+                create_test_entry("c.iso"),
+            ]);
+            state.disk_hogs = true;
+            let mut history = History::new();
+            history.push(HistoryEntry {
+                index: 0,
+                decision: Decision::Trash,
+            });
+            history.push(HistoryEntry {
+                index: 1,
+                decision: Decision::Keep,
+            });
+
+            let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+            terminal
+                .draw(|frame| render(frame, &state, &history))
+                .unwrap();
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            // Half of what was decided went, so half of what is left is projected to go too
+            assert!(buffer_str.contains("Reclaimed 1.0 KB • ~1.5 KB projected"));
+        }
+
         #[test]
         fn test_render_footer() {
             let state = AppState::new(vec![create_test_entry("test.txt")]);
@@ -945,7 +2000,7 @@ mod tests {
 
             terminal
                 .draw(|frame| {
-                    render(frame, &state);
+                    render(frame, &state, &History::new());
                 })
                 .unwrap();
 
@@ -959,6 +2014,27 @@ mod tests {
             assert!(buffer_str.contains("Quit"));
         }
 
+        #[test]
+        fn test_render_footer_notice() {
+            let mut state = AppState::new(vec![create_test_entry("test.txt")]);
+            state.notice = Some("Destination already exists".to_string());
+            let backend = TestBackend::new(80, 24);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render(frame, &state, &History::new());
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let content = buffer.content();
+            let buffer_str: String = content.iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("Destination already exists"));
+            assert!(!buffer_str.contains("Quit"));
+        }
+
         #[test]
         fn test_render_header_progress() {
             let files = vec![
@@ -974,7 +2050,7 @@ mod tests {
 
             terminal
                 .draw(|frame| {
-                    render(frame, &state);
+                    render(frame, &state, &History::new());
                 })
                 .unwrap();
 
@@ -987,6 +2063,240 @@ mod tests {
             assert!(buffer_str.contains("file2.txt"));
         }
 
+        #[test]
+        fn test_render_header_progress_from_history() {
+            let files = vec![
+                create_test_entry("file1.txt"),
+                create_test_entry("file2.txt"),
+            ];
+            let state = AppState::new(files);
+            let mut history = History::new();
+            history.push(HistoryEntry {
+                index: 0,
+                decision: Decision::Keep,
+            });
+
+            let backend = TestBackend::new(80, 24);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render(frame, &state, &history);
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("50% (1/2)"));
+        }
+
+        #[test]
+        fn test_render_timeline_overlay() {
+            let files = vec![
+                create_test_entry("file1.txt"),
+                create_test_entry("file2.txt"),
+            ];
+            let mut history = History::new();
+            history.push(HistoryEntry {
+                index: 0,
+                decision: Decision::Trash,
+            });
+            history.push(HistoryEntry {
+                index: 1,
+                decision: Decision::Keep,
+            });
+            history.undo();
+
+            let backend = TestBackend::new(80, 30);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render_timeline_overlay(frame, &history, &files, 1, None);
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("Decision History"));
+            assert!(buffer_str.contains("file1.txt"));
+            assert!(buffer_str.contains("file2.txt"));
+            assert!(buffer_str.contains("Jump here"));
+        }
+
+        #[test]
+        fn test_render_timeline_overlay_error() {
+            let files = vec![create_test_entry("file1.txt")];
+            let mut history = History::new();
+            history.push(HistoryEntry {
+                index: 0,
+                decision: Decision::Trash,
+            });
+
+            let backend = TestBackend::new(80, 30);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render_timeline_overlay(
+                        frame,
+                        &history,
+                        &files,
+                        0,
+                        Some("Cannot revert: Original path is occupied"),
+                    );
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("Cannot revert: Original path is occupied"));
+            assert!(buffer_str.contains("Jump here"));
+        }
+
+        #[test]
+        fn test_render_confirm_delete_overlay_error() {
+            let file = create_test_entry("notes.txt");
+            let input = TextInput::new("note.txt");
+
+            let backend = TestBackend::new(80, 30);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render_confirm_delete_overlay(
+                        frame,
+                        &file,
+                        &input,
+                        Some("Name does not match"),
+                    );
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("Permanently Delete"));
+            assert!(buffer_str.contains("Name does not match"));
+        }
+
+        #[test]
+        fn test_render_rename_overlay() {
+            let file = create_test_entry("document(3).pdf");
+            let mut dialog = RenameDialog::new(&file);
+            assert_eq!(dialog.input.cursor(), "document(3)".len());
+
+            dialog.next_suggestion();
+            assert_eq!(dialog.input.value(), dialog.suggestions[0]);
+
+            let backend = TestBackend::new(80, 30);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render_rename_overlay(frame, &file, &dialog);
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("Rename"));
+            assert!(buffer_str.contains("document(3).pdf"));
+            assert!(buffer_str.contains("document.pdf"));
+        }
+
+        #[test]
+        fn test_render_delete_mode_labels() {
+            let mut state = AppState::new(vec![create_test_entry("test.txt")]);
+            state.delete_mode = true;
+            let backend = TestBackend::new(100, 24);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render(frame, &state, &History::new());
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("PERMANENT DELETE MODE"));
+            assert!(buffer_str.contains("Delete"));
+            assert!(!buffer_str.contains("Trash"));
+        }
+
+        #[test]
+        fn test_render_confirm_bulk_delete_overlay() {
+            let files: Vec<FileEntry> = (0..30)
+                .map(|i| create_test_entry(&format!("file{}.txt", i)))
+                .collect();
+            let refs: Vec<&FileEntry> = files.iter().collect();
+            let input = TextInput::new("3");
+
+            let backend = TestBackend::new(80, 30);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render_confirm_bulk_delete_overlay(
+                        frame,
+                        &refs,
+                        &input,
+                        Some("Count does not match"),
+                    );
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("30 files (30.0 KB)"));
+            assert!(buffer_str.contains("will be permanently deleted"));
+            assert!(buffer_str.contains("file0.txt"));
+            assert!(buffer_str.contains("more"));
+            assert!(buffer_str.contains("Type 30 to confirm"));
+            assert!(buffer_str.contains("Count does not match"));
+        }
+
+        #[test]
+        fn test_render_duplicate_group() {
+            let mut copy = create_test_entry("setup (1).dmg");
+            copy.path = PathBuf::from("old/setup (1).dmg");
+            let state = AppState::from_duplicate_groups(vec![
+                vec![create_test_entry("setup.dmg"), copy],
+                vec![create_test_entry("a.txt"), create_test_entry("b.txt")],
+            ]);
+            let mut history = History::new();
+            history.push(HistoryEntry {
+                index: 1,
+                decision: Decision::Trash,
+            });
+            let backend = TestBackend::new(100, 30);
+            let mut terminal = Terminal::new(backend).unwrap();
+
+            terminal
+                .draw(|frame| {
+                    render(frame, &state, &history);
+                })
+                .unwrap();
+
+            let buffer = terminal.backend().buffer().clone();
+            let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+            assert!(buffer_str.contains("Group 1/2"));
+            assert!(buffer_str.contains("Copy 1"));
+            assert!(buffer_str.contains("Copy 2"));
+            assert!(buffer_str.contains("old/"));
+            assert!(buffer_str.contains("← Trash"));
+            assert!(buffer_str.contains("Undecided"));
+            assert!(buffer_str.contains("Keep one"));
+        }
+
         #[test]
         fn test_render_help_overlay() {
             let backend = TestBackend::new(80, 30);
@@ -994,7 +2304,7 @@ mod tests {
 
             terminal
                 .draw(|frame| {
-                    render_help_overlay(frame);
+                    render_help_overlay(frame, &[PathBuf::from("/home/me/Receipts")], false);
                 })
                 .unwrap();
 
@@ -1006,14 +2316,23 @@ mod tests {
             assert!(buffer_str.contains("Help"));
             assert!(buffer_str.contains("Keep"));
             assert!(buffer_str.contains("Trash"));
+            assert!(buffer_str.contains("Move to me/Receipts"));
         }
 
         #[test]
         fn test_render_summary() {
             let stats = DecisionStatistics {
                 total_files: 10,
-                kept: 6,
+                kept: 5,
                 trashed: 3,
+                deleted: 0,
+                moved: 1,
+                renamed: 0,
+                archived: 0,
+                moved_by_destination: [(PathBuf::from("/home/me/Receipts"), 1)].into(),
+                freed_bytes: 0,
+                decided_bytes: 0,
+                pending_bytes: 0,
             };
 
             let backend = TestBackend::new(80, 30);
@@ -1031,6 +2350,8 @@ mod tests {
 
             // Check for summary content
             assert!(buffer_str.contains("Summary") || buffer_str.contains("Complete"));
+            assert!(buffer_str.contains("Moved"));
+            assert!(buffer_str.contains("me/Receipts: 1"));
         }
 
         #[test]
@@ -0,0 +1,135 @@
+//! Single-line text input used for editing file names
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Editable text with a cursor, counted in characters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Creates an input holding `value` with the cursor at the end
+    pub fn new(value: impl Into<String>) -> Self {
+        let mut input = Self::default();
+        input.set_value(value);
+        input
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Cursor position in characters from the start
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replaces the text and moves the cursor to the end
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.chars().count();
+    }
+
+    pub fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.value.chars().count());
+    }
+
+    /// Applies an editing key. Returns false for keys the input does not handle.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let len = self.value.chars().count();
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('a'), KeyModifiers::CONTROL) | (KeyCode::Home, _) => self.cursor = 0,
+            (KeyCode::Char('e'), KeyModifiers::CONTROL) | (KeyCode::End, _) => self.cursor = len,
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                let at = self.byte_index();
+                self.value.replace_range(..at, "");
+                self.cursor = 0;
+            }
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                let at = self.byte_index();
+                self.value.insert(at, c);
+                self.cursor += 1;
+            }
+            (KeyCode::Backspace, _) if self.cursor > 0 => {
+                self.cursor -= 1;
+                let at = self.byte_index();
+                self.value.remove(at);
+            }
+            (KeyCode::Delete, _) if self.cursor < len => {
+                let at = self.byte_index();
+                self.value.remove(at);
+            }
+            (KeyCode::Left, _) => self.cursor = self.cursor.saturating_sub(1),
+            (KeyCode::Right, _) => self.cursor = (self.cursor + 1).min(len),
+            (KeyCode::Backspace | KeyCode::Delete, _) => {}
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode) {
+        input.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_text_input_typing_and_backspace() {
+        let mut input = TextInput::new("photo");
+        press(&mut input, KeyCode::Char('s'));
+        assert_eq!(input.value(), "photos");
+
+        press(&mut input, KeyCode::Backspace);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "phot");
+        assert_eq!(input.cursor(), 4);
+    }
+
+    #[test]
+    fn test_text_input_edit_in_middle() {
+        let mut input = TextInput::new("IMG.jpg");
+        input.set_cursor(3);
+        press(&mut input, KeyCode::Char('_'));
+        press(&mut input, KeyCode::Char('1'));
+        assert_eq!(input.value(), "IMG_1.jpg");
+
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(input.value(), "MG_1.jpg");
+    }
+
+    #[test]
+    fn test_text_input_multibyte() {
+        let mut input = TextInput::new("café");
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "caé");
+
+        press(&mut input, KeyCode::End);
+        press(&mut input, KeyCode::Char('!'));
+        assert_eq!(input.value(), "caé!");
+    }
+
+    #[test]
+    fn test_text_input_ignores_other_keys() {
+        let mut input = TextInput::new("a");
+        assert!(!input.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(!input.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)));
+        assert_eq!(input.value(), "a");
+    }
+}